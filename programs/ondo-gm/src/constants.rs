@@ -47,14 +47,60 @@ pub const WHITELIST_SEED: &[u8] = b"whitelist";
 pub const ORACLE_SANITY_CHECK_SEED: &[u8] = b"sanity_check";
 /// Seed for attestation ID PDA
 pub const ATTESTATION_ID_SEED: &[u8] = b"attestation_id";
+/// Seed for queued Order PDA
+pub const ORDER_SEED: &[u8] = b"order";
+/// Seed for LimitOrder PDA
+pub const LIMIT_ORDER_SEED: &[u8] = b"limit_order";
+/// Seed for RecurringOrder PDA
+pub const RECURRING_ORDER_SEED: &[u8] = b"recurring_order";
+/// Seed for MintRequest PDA
+pub const MINT_REQUEST_SEED: &[u8] = b"mint_request";
+/// Seed for SettlementAsset PDA
+pub const SETTLEMENT_ASSET_SEED: &[u8] = b"settlement_asset";
+/// Seed for PushedPrice PDA
+pub const PUSHED_PRICE_SEED: &[u8] = b"pushed_price";
+/// Seed for RedemptionQueue PDA
+pub const REDEMPTION_QUEUE_SEED: &[u8] = b"redemption_queue";
+/// Seed for USDonReserves PDA
+pub const USDON_RESERVES_SEED: &[u8] = b"usdon_reserves";
+/// Seed for MinterAllowance PDA
+pub const MINTER_ALLOWANCE_SEED: &[u8] = b"minter_allowance";
+/// Seed for RetrievalDestination PDA
+pub const RETRIEVAL_DESTINATION_SEED: &[u8] = b"retrieval_destination";
+/// Seed for RetrievalLimit PDA
+pub const RETRIEVAL_LIMIT_SEED: &[u8] = b"retrieval_limit";
+/// Seed for PendingRetrieval PDA
+pub const PENDING_RETRIEVAL_SEED: &[u8] = b"pending_retrieval";
+/// Seed for UserNotionalLimit PDA
+pub const USER_NOTIONAL_LIMIT_SEED: &[u8] = b"user_notional_limit";
+/// Seed for LimitGroup PDA
+pub const LIMIT_GROUP_SEED: &[u8] = b"limit_group";
+/// Seed for LimitProfile PDA
+pub const LIMIT_PROFILE_SEED: &[u8] = b"limit_profile";
+
+/// Maximum number of claims held by the redemption queue
+pub const MAX_REDEMPTION_CLAIMS: usize = 64;
 
 /// Pyth price feed ID for USDC/USD
 pub const USDC_PYTH_ID: &str = "eaa020c61cc479712813461ce153894a96a6c00b21ed0cfc2798d1f9a9e9c94a";
-pub const USDC_PYTH_ORACLE_ADDRESS: Pubkey =
-    pubkey!("Dpw1EAVrSB1ibxiDQyTAW6Zip3J4Btk2x4SgApQCeFbX");
+
+/// Switchboard On-Demand program, owner of its pull feed accounts
+pub const SWITCHBOARD_ON_DEMAND_PROGRAM_ID: Pubkey =
+    pubkey!("SBondMDrcV3K4kxZR1HNVT7osZxAHVHgYXL5Ze1oMUv");
 
 /// Minimum price threshold for USDC (in scaled units)
+/// Default when no depeg floor is configured on the USDonManagerState
 pub const MIN_PRICE: u64 = 98_000_000;
+/// The USDC peg (in scaled units)
+pub const USDC_PEG_PRICE: u64 = 100_000_000;
+/// Lowest depeg floor that can be configured for USDC (in scaled units)
+pub const MIN_PRICE_LOWER_BOUND: u64 = 90_000_000;
+/// Highest depeg ceiling that can be configured for USDC (in scaled units)
+pub const MAX_PRICE_UPPER_BOUND: u64 = 110_000_000;
+/// Widest band around the peg within which USDC can be converted at the oracle price (10%)
+pub const MAX_CONVERSION_BAND_BPS: u64 = 1_000;
+/// Highest fee that can be charged on PSM deposits and redemptions (1%)
+pub const MAX_PSM_FEE_BPS: u64 = 100;
 pub const USDC_PRICE_DECIMALS: u8 = 8;
 pub const MAX_AGE_UPPER_BOUND: u64 = SECONDS_PER_DAY as u64;
 
@@ -64,8 +110,14 @@ pub const MAX_SECONDS_EXPIRATION: i64 = 365 * SECONDS_PER_DAY;
 /// Default attestation expiration time in seconds
 pub const MAX_ATTESTATION_EXPIRATION: i64 = 30;
 
+/// Time in seconds after which an unsettled mint request can be refunded (3 days)
+/// Long enough for a request made before a weekend to be settled when the market reopens
+pub const MINT_REQUEST_TIMEOUT: i64 = 259_200;
+
 /// Default rate limit window in seconds (1 hour)
 pub const DEFAULT_LIMIT_WINDOW: u64 = 3600;
+/// Number of additional rate limit tiers on a TokenLimit account
+pub const MAX_TOKEN_LIMIT_TIERS: usize = 3;
 /// Buy side identifier for attestations
 pub const BUY: u8 = 0x30;
 /// Sell side identifier for attestations
@@ -81,7 +133,11 @@ pub const PRICE_SCALING_FACTOR: i64 = 1_000_000_000;
 /// 10,000 basis points = 100% - Divisor for basis point calculations
 pub const BASIS_POINTS_DIVISOR: u64 = 10_000;
 
-pub const CONFIDENCE_THRESHOLD: u128 = 1;
+/// Maximum oracle confidence interval, in basis points of the price (1%)
+/// Default when no threshold is configured on the USDonManagerState
+pub const CONFIDENCE_THRESHOLD_BPS: u64 = 100;
+/// Highest confidence threshold that can be configured, in basis points (5%)
+pub const MAX_CONFIDENCE_THRESHOLD_BPS: u64 = 500;
 
 /// The maximum amount of tokens that can be minted in a single admin mint operation
 /// 10,000,000,000,000,000 units = 10 million tokens with 9 decimals
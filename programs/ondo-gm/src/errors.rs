@@ -90,4 +90,90 @@ pub enum OndoError {
     MetadataFieldTooLong,
     #[msg("Time since last update has a negative value")]
     NegativeTimeSinceLastUpdate,
+    #[msg("Escrowed amount is insufficient to settle the trade")]
+    InsufficientEscrowBalance,
+    #[msg("Orders can only be queued outside market hours")]
+    MarketOpen,
+    #[msg("Attested price does not satisfy the order's limit price")]
+    LimitPriceNotMet,
+    #[msg("Order has expired")]
+    OrderExpired,
+    #[msg("Order has not expired")]
+    OrderNotExpired,
+    #[msg("Invalid recurring order interval")]
+    InvalidInterval,
+    #[msg("Recurring order installment is not due yet")]
+    InstallmentNotDue,
+    #[msg("Execution price exceeds the maximum price of the request")]
+    MaxPriceExceeded,
+    #[msg("Settlement asset is disabled")]
+    SettlementAssetDisabled,
+    #[msg("Invalid settlement asset")]
+    InvalidSettlementAsset,
+    #[msg("Oracle price account is not owned by the expected program")]
+    InvalidOracleOwner,
+    #[msg("Invalid oracle divergence")]
+    InvalidOracleDivergence,
+    #[msg("Primary and secondary oracle prices diverge beyond the allowed threshold")]
+    OracleDivergence,
+    #[msg("Invalid USDC price bounds")]
+    InvalidUSDCPriceBounds,
+    #[msg("Invalid confidence threshold")]
+    InvalidConfidenceThreshold,
+    #[msg("USDC price is above the maximum price")]
+    USDCAboveMaximumPrice,
+    #[msg("Invalid conversion band")]
+    InvalidConversionBand,
+    #[msg("USDC price is outside the oracle conversion band")]
+    ConversionRateOutOfBand,
+    #[msg("Vault balance is insufficient to cover the payout")]
+    InsufficientVaultLiquidity,
+    #[msg("Redemption queue is full")]
+    RedemptionQueueFull,
+    #[msg("Redemption queue account not provided")]
+    RedemptionQueueNotProvided,
+    #[msg("Invalid PSM fee")]
+    InvalidPSMFee,
+    #[msg("PSM deposits are paused")]
+    PSMDepositPaused,
+    #[msg("PSM redemptions are paused")]
+    PSMRedemptionPaused,
+    #[msg("Amount out is below the minimum amount out")]
+    SlippageExceeded,
+    #[msg("Invalid reserves configuration")]
+    InvalidReservesConfig,
+    #[msg("USDon minting is paused")]
+    USDonMintingPaused,
+    #[msg("Mint would exceed the USDon supply cap")]
+    USDonSupplyCapExceeded,
+    #[msg("Invalid retrieval timelock")]
+    InvalidRetrievalTimelock,
+    #[msg("Retrieval amount is above the timelock threshold")]
+    RetrievalRequiresTimelock,
+    #[msg("Retrieval timelock has not expired")]
+    RetrievalNotExecutable,
+    #[msg("Invalid rate limit tier")]
+    InvalidLimitTier,
+    #[msg("User notional limit account not provided")]
+    UserNotionalLimitNotProvided,
+    #[msg("Limit group account not provided")]
+    LimitGroupNotProvided,
+    #[msg("Invalid limit group")]
+    InvalidLimitGroup,
+    #[msg("Limit profile account not provided")]
+    LimitProfileNotProvided,
+    #[msg("Invalid limit profile")]
+    InvalidLimitProfile,
+    #[msg("Invalid OndoUser account")]
+    InvalidOndoUser,
+    #[msg("Redemption claim not found")]
+    RedemptionClaimNotFound,
+    #[msg("Settled amount differs from the requested amount")]
+    MintAmountMismatch,
+    #[msg("Mint has an unsupported Token-2022 extension")]
+    UnsupportedMintExtension,
+    #[msg("Reserves attestation is missing or stale")]
+    StaleReservesAttestation,
+    #[msg("Installment does not spend the per-installment amount")]
+    InstallmentUnderspent,
 }
@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 
-use crate::state::RoleType;
+use crate::state::{OndoUserLimitsUpdate, RoleType};
 
 /// Event emitted when a role is granted to a user
 /// Fields:
@@ -29,23 +29,29 @@ pub struct RoleRevoked {
 /// Event emitted when a rate limit is set for a user
 /// Fields:
 /// - user: The public key of the user for whom the rate limit is set
-/// - limit: The rate limit value
+/// - limit: The mint rate limit value
+/// - redeem_limit: The redeem rate limit value, None if redemptions use the mint rate limit
 #[event]
 pub struct RateLimitUserSet {
     pub user: Pubkey,
     pub limit: u64,
+    pub redeem_limit: Option<u64>,
 }
 
 /// Event emitted when a rate limit is set for a token
 /// Fields:
 /// - token: The public key of the token for which the rate limit is set
-/// - limit: The rate limit value
-/// - limit_window: The time window for the rate limit
+/// - limit: The mint rate limit value
+/// - limit_window: The time window for the mint rate limit
+/// - redeem_limit: The redeem rate limit value, None if redemptions use the mint rate limit
+/// - redeem_limit_window: The time window for the redeem rate limit, None if redemptions use the mint window
 #[event]
 pub struct RateLimitTokenSet {
     pub token: Pubkey,
     pub limit: Option<u64>,
     pub limit_window: Option<u64>,
+    pub redeem_limit: Option<u64>,
+    pub redeem_limit_window: Option<u64>,
 }
 
 /// Event emitted when a sanity check is set for a mint
@@ -151,9 +157,11 @@ pub struct GMTokenPaused {
 /// Event emitted when a trade is executed
 /// Fields:
 /// - execution_id: The unique identifier of the trade execution
+/// - conversion_rate: The USD price the settlement asset was converted at, scaled by USDC_PRICE_DECIMALS
 #[event]
 pub struct TradeExecuted {
     pub execution_id: u128,
+    pub conversion_rate: u64,
 }
 
 /// Event emitted when the trading hours offset is set
@@ -199,3 +207,634 @@ pub struct UserRemovedFromWhitelist {
     pub user: Pubkey,
     pub removed_by: Pubkey,
 }
+
+/// Event emitted when a user queues an order outside market hours
+/// Fields:
+/// - order: The public key of the order account
+/// - user: The public key of the user who queued the order
+/// - mint: The public key of the GM Token being minted or redeemed
+/// - is_buy: Boolean indicating if the order is a mint (true) or a redemption (false)
+/// - is_usdon: Boolean indicating if the order settles in USDon (true) or USDC (false)
+/// - deposit_amount: The amount escrowed by the order
+#[event]
+pub struct OrderCreated {
+    pub order: Pubkey,
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub is_buy: bool,
+    pub is_usdon: bool,
+    pub deposit_amount: u64,
+}
+
+/// Event emitted when a queued order is settled by an operator
+/// Fields:
+/// - order: The public key of the order account
+/// - user: The public key of the user who queued the order
+/// - price: The attested price the order was settled at
+/// - amount: The amount of GM Tokens minted or redeemed
+/// - refunded: The escrowed amount returned to the user
+/// - operator: The public key of the operator who settled the order
+#[event]
+pub struct OrderSettled {
+    pub order: Pubkey,
+    pub user: Pubkey,
+    pub price: u64,
+    pub amount: u64,
+    pub refunded: u64,
+    pub operator: Pubkey,
+}
+
+/// Event emitted when a user cancels a queued order
+/// Fields:
+/// - order: The public key of the order account
+/// - user: The public key of the user who cancelled the order
+/// - refunded: The escrowed amount returned to the user
+#[event]
+pub struct OrderCancelled {
+    pub order: Pubkey,
+    pub user: Pubkey,
+    pub refunded: u64,
+}
+
+/// Event emitted when a user places a limit order
+/// Fields:
+/// - order: The public key of the limit order account
+/// - user: The public key of the user who placed the order
+/// - mint: The public key of the GM Token being minted or redeemed
+/// - is_buy: Boolean indicating if the order is a mint (true) or a redemption (false)
+/// - is_usdon: Boolean indicating if the order settles in USDon (true) or USDC (false)
+/// - deposit_amount: The amount escrowed by the order
+/// - limit_price: The worst price the order can be filled at
+/// - expires_at: The timestamp after which the order can no longer be filled
+#[event]
+pub struct LimitOrderCreated {
+    pub order: Pubkey,
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub is_buy: bool,
+    pub is_usdon: bool,
+    pub deposit_amount: u64,
+    pub limit_price: u64,
+    pub expires_at: i64,
+}
+
+/// Event emitted when a limit order is filled in full or in part
+/// Fields:
+/// - order: The public key of the limit order account
+/// - user: The public key of the user who placed the order
+/// - price: The attested price of the fill
+/// - amount: The amount of GM Tokens minted or redeemed by the fill
+/// - filled_amount: The total amount of GM Tokens filled so far
+/// - remaining_deposit: The escrowed amount left after the fill
+/// - operator: The public key of the operator who filled the order
+#[event]
+pub struct LimitOrderFilled {
+    pub order: Pubkey,
+    pub user: Pubkey,
+    pub price: u64,
+    pub amount: u64,
+    pub filled_amount: u64,
+    pub remaining_deposit: u64,
+    pub operator: Pubkey,
+}
+
+/// Event emitted when a limit order is cancelled or closed after expiry
+/// Fields:
+/// - order: The public key of the limit order account
+/// - user: The public key of the user who placed the order
+/// - refunded: The escrowed amount returned to the user
+/// - closed_by: The public key of the account that closed the order
+#[event]
+pub struct LimitOrderCancelled {
+    pub order: Pubkey,
+    pub user: Pubkey,
+    pub refunded: u64,
+    pub closed_by: Pubkey,
+}
+
+/// Event emitted when a user creates a recurring purchase schedule
+/// Fields:
+/// - order: The public key of the recurring order account
+/// - user: The public key of the user who created the schedule
+/// - mint: The public key of the GM Token being purchased
+/// - amount_per_execution: The maximum USDon spent by a single installment
+/// - interval: The number of seconds between installments
+/// - next_execution_at: The timestamp from which the first installment can be executed
+/// - deposit_amount: The USDon allowance escrowed at creation
+#[event]
+pub struct RecurringOrderCreated {
+    pub order: Pubkey,
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub amount_per_execution: u64,
+    pub interval: i64,
+    pub next_execution_at: i64,
+    pub deposit_amount: u64,
+}
+
+/// Event emitted when a user tops up the allowance of a recurring purchase schedule
+/// Fields:
+/// - order: The public key of the recurring order account
+/// - user: The public key of the user who funded the schedule
+/// - amount: The amount of USDon added to the allowance
+#[event]
+pub struct RecurringOrderFunded {
+    pub order: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+}
+
+/// Event emitted when an installment of a recurring purchase is executed
+/// Fields:
+/// - order: The public key of the recurring order account
+/// - user: The public key of the user who created the schedule
+/// - price: The attested price of the installment
+/// - amount: The amount of GM Tokens minted
+/// - spent: The amount of USDon spent
+/// - next_execution_at: The timestamp from which the next installment can be executed
+/// - cranker: The public key of the account that executed the installment
+#[event]
+pub struct RecurringOrderExecuted {
+    pub order: Pubkey,
+    pub user: Pubkey,
+    pub price: u64,
+    pub amount: u64,
+    pub spent: u64,
+    pub next_execution_at: i64,
+    pub cranker: Pubkey,
+}
+
+/// Event emitted when a user cancels a recurring purchase schedule
+/// Fields:
+/// - order: The public key of the recurring order account
+/// - user: The public key of the user who cancelled the schedule
+/// - refunded: The remaining USDon allowance returned to the user
+#[event]
+pub struct RecurringOrderCancelled {
+    pub order: Pubkey,
+    pub user: Pubkey,
+    pub refunded: u64,
+}
+
+/// Event emitted when a user locks funds for a two-phase mint
+/// Fields:
+/// - request: The public key of the mint request account
+/// - user: The public key of the user who requested the mint
+/// - mint: The public key of the GM Token being minted
+/// - is_usdon: Boolean indicating if the request is paid in USDon (true) or USDC (false)
+/// - deposit_amount: The amount of the quote asset locked
+/// - max_price: The highest execution price the user accepts
+/// - amount: The amount of GM Tokens requested, what the deposit buys at the max price
+/// - expires_at: The timestamp from which the request can be refunded
+#[event]
+pub struct MintRequestCreated {
+    pub request: Pubkey,
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub is_usdon: bool,
+    pub deposit_amount: u64,
+    pub max_price: u64,
+    pub amount: u64,
+    pub expires_at: i64,
+}
+
+/// Event emitted when a mint request is settled at the final execution price
+/// Fields:
+/// - request: The public key of the mint request account
+/// - user: The public key of the user who requested the mint
+/// - price: The attested execution price
+/// - amount: The amount of GM Tokens minted
+/// - refunded: The locked amount returned to the user
+/// - operator: The public key of the operator who settled the request
+#[event]
+pub struct MintRequestSettled {
+    pub request: Pubkey,
+    pub user: Pubkey,
+    pub price: u64,
+    pub amount: u64,
+    pub refunded: u64,
+    pub operator: Pubkey,
+}
+
+/// Event emitted when a timed out mint request is refunded
+/// Fields:
+/// - request: The public key of the mint request account
+/// - user: The public key of the user who requested the mint
+/// - refunded: The locked amount returned to the user
+/// - closed_by: The public key of the account that closed the request
+#[event]
+pub struct MintRequestRefunded {
+    pub request: Pubkey,
+    pub user: Pubkey,
+    pub refunded: u64,
+    pub closed_by: Pubkey,
+}
+
+/// Event emitted when a settlement asset is registered or its configuration changes
+/// Fields:
+/// - mint: The public key of the settlement asset mint
+/// - vault: The public key of the vault holding the asset
+/// - priced_at_par: Boolean indicating if the asset is exchanged at par without an oracle check
+/// - price_update: The public key of the asset's price oracle, default if priced at par
+/// - price_update_owner: The owner of the price oracle account, default for the adapter's owner
+/// - oracle_max_age: The maximum age of an oracle price in seconds
+/// - confidence_threshold_bps: The maximum oracle confidence interval in basis points
+/// - min_price: The depeg floor of the asset
+/// - max_price: The depeg ceiling of the asset
+/// - enabled: Boolean indicating if the asset can be used for settlement
+/// - authority: The public key of the admin who made the change
+#[event]
+pub struct SettlementAssetSet {
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub priced_at_par: bool,
+    pub price_update: Pubkey,
+    pub price_update_owner: Pubkey,
+    pub oracle_max_age: u64,
+    pub confidence_threshold_bps: u64,
+    pub min_price: u64,
+    pub max_price: u64,
+    pub enabled: bool,
+    pub authority: Pubkey,
+}
+
+/// Event emitted when an admin pushes a price
+/// Fields:
+/// - pushed_price: The public key of the pushed price account
+/// - mint: The public key of the mint the price is for
+/// - price: The new price
+/// - conf: The confidence interval of the new price
+/// - authority: The public key of the admin who pushed the price
+#[event]
+pub struct PricePushed {
+    pub pushed_price: Pubkey,
+    pub mint: Pubkey,
+    pub price: u64,
+    pub conf: u64,
+    pub authority: Pubkey,
+}
+
+/// Event emitted when a redemption is partly settled in USDon because the vault is short
+/// Fields:
+/// - user: The public key of the user being paid out
+/// - vault: The public key of the vault that could not cover the payout
+/// - requested: The amount that should have been paid out of the vault
+/// - paid: The amount actually paid out of the vault
+/// - usdon_amount: The amount of USDon the user received instead of the remainder
+#[event]
+pub struct VaultShortfall {
+    pub user: Pubkey,
+    pub vault: Pubkey,
+    pub requested: u64,
+    pub paid: u64,
+    pub usdon_amount: u64,
+}
+
+/// Event emitted when the part of a redemption the USDC vault cannot cover is queued
+/// Fields:
+/// - claim_id: The ID of the claim in the redemption queue
+/// - user: The public key of the user who redeemed
+/// - destination: The USDC token account the claim is paid to
+/// - amount: The amount of USDC owed
+#[event]
+pub struct RedemptionQueued {
+    pub claim_id: u64,
+    pub user: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+}
+
+/// Event emitted when a queued redemption claim is paid, in full or in part
+/// Fields:
+/// - claim_id: The ID of the claim in the redemption queue
+/// - user: The public key of the user who redeemed
+/// - amount: The amount of USDC paid
+/// - remaining: The amount of USDC still owed to the claim
+/// - cranker: The public key of the account that processed the queue
+#[event]
+pub struct RedemptionClaimPaid {
+    pub claim_id: u64,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub remaining: u64,
+    pub cranker: Pubkey,
+}
+
+/// Event emitted when USDC is deposited into the PSM for USDon
+/// Fields:
+/// - user: The public key of the depositing user
+/// - usdc_amount: The amount of USDC deposited
+/// - usdon_amount: The amount of USDon minted to the user
+/// - fee: The USDon value retained as fee
+/// - conversion_rate: The USDC price the deposit was converted at
+#[event]
+pub struct PSMDeposit {
+    pub user: Pubkey,
+    pub usdc_amount: u64,
+    pub usdon_amount: u64,
+    pub fee: u64,
+    pub conversion_rate: u64,
+}
+
+/// Event emitted when USDon is redeemed from the PSM for USDC
+/// Fields:
+/// - user: The public key of the redeeming user
+/// - usdon_amount: The amount of USDon burned
+/// - usdc_amount: The amount of USDC paid to the user
+/// - fee: The USDon value retained as fee
+/// - conversion_rate: The USDC price the redemption was converted at
+#[event]
+pub struct PSMRedemption {
+    pub user: Pubkey,
+    pub usdon_amount: u64,
+    pub usdc_amount: u64,
+    pub fee: u64,
+    pub conversion_rate: u64,
+}
+
+/// Event emitted when the custodian attests the off-chain reserves backing USDon
+/// Fields:
+/// - custodian: The public key of the custodian
+/// - offchain_reserves: The attested off-chain reserves, in USDon base units
+/// - attested_at: Unix timestamp of the attestation
+#[event]
+pub struct ReservesAttested {
+    pub custodian: Pubkey,
+    pub offchain_reserves: u64,
+    pub attested_at: i64,
+}
+
+/// Event emitted when the reserves backing USDon are reconciled with the USDon supply
+/// Fields:
+/// - onchain_reserves: The USDC vault balance, in USDon base units
+/// - offchain_reserves: The counted off-chain reserves, 0 if the attestation is stale
+/// - supply: The USDon supply
+/// - collateralization_bps: The backing of the supply in basis points
+/// - minting_paused: Whether USDon minting is paused after the reconciliation
+/// - cranker: The public key of the account that triggered the reconciliation
+#[event]
+pub struct ReservesReconciled {
+    pub onchain_reserves: u64,
+    pub offchain_reserves: u64,
+    pub supply: u64,
+    pub collateralization_bps: u64,
+    pub minting_paused: bool,
+    pub cranker: Pubkey,
+}
+
+/// Event emitted when the guardian sets the mint allowance of a USDon minter
+/// Fields:
+/// - minter: The public key of the minter
+/// - rate_limit: The maximum amount of USDon the minter can mint in the limit window
+/// - limit_window: The limit window in seconds
+/// - authority: The public key of the guardian who set the allowance
+#[event]
+pub struct MinterAllowanceSet {
+    pub minter: Pubkey,
+    pub rate_limit: u64,
+    pub limit_window: u64,
+    pub authority: Pubkey,
+}
+
+/// Event emitted when the USDon supply ceiling is set
+/// Fields:
+/// - prev_usdon_supply_cap: The previous supply ceiling, 0 if none was set
+/// - new_usdon_supply_cap: The new supply ceiling, 0 removes the ceiling
+#[event]
+pub struct USDonSupplyCapSet {
+    pub prev_usdon_supply_cap: u64,
+    pub new_usdon_supply_cap: u64,
+}
+
+/// Event emitted when a token account is added to or removed from the retrieval destination allowlist
+/// Fields:
+/// - destination: The token account
+/// - is_allowed: Whether tokens can be retrieved to the token account
+/// - authority: The public key of the guardian who updated the allowlist
+#[event]
+pub struct RetrievalDestinationSet {
+    pub destination: Pubkey,
+    pub is_allowed: bool,
+    pub authority: Pubkey,
+}
+
+/// Event emitted when the withdrawal limit of a mint is set
+/// Fields:
+/// - token: The public key of the token mint
+/// - rate_limit: The maximum amount that can be retrieved in the limit window
+/// - limit_window: The limit window in seconds
+/// - timelock_threshold: Retrievals above this amount must be timelocked, 0 if disabled
+/// - timelock_delay: The timelock delay in seconds
+/// - authority: The public key of the guardian who set the limit
+#[event]
+pub struct RetrievalLimitSet {
+    pub token: Pubkey,
+    pub rate_limit: u64,
+    pub limit_window: u64,
+    pub timelock_threshold: u64,
+    pub timelock_delay: u64,
+    pub authority: Pubkey,
+}
+
+/// Event emitted when a timelocked retrieval is requested
+/// Fields:
+/// - request_id: The ID of the request
+/// - token: The public key of the token mint being withdrawn
+/// - to: The destination address receiving the tokens
+/// - amount: The amount of tokens to withdraw
+/// - executable_at: Unix timestamp from which the retrieval can be executed
+/// - authority: The public key of the admin who requested the retrieval
+#[event]
+pub struct RetrievalRequested {
+    pub request_id: u64,
+    pub token: Pubkey,
+    pub to: Pubkey,
+    pub amount: u64,
+    pub executable_at: i64,
+    pub authority: Pubkey,
+}
+
+/// Event emitted when a timelocked retrieval is cancelled
+/// Fields:
+/// - request_id: The ID of the request
+/// - token: The public key of the token mint
+/// - to: The destination address of the cancelled retrieval
+/// - amount: The amount of tokens of the cancelled retrieval
+/// - authority: The public key of the guardian who cancelled the retrieval
+#[event]
+pub struct RetrievalCancelled {
+    pub request_id: u64,
+    pub token: Pubkey,
+    pub to: Pubkey,
+    pub amount: u64,
+    pub authority: Pubkey,
+}
+
+/// Event emitted when net-flow rate limiting is enabled or disabled for a token
+/// Fields:
+/// - token: The public key of the token
+/// - enabled: Whether net-flow mode is enabled
+#[event]
+pub struct TokenNetFlowModeSet {
+    pub token: Pubkey,
+    pub enabled: bool,
+}
+
+/// Event emitted when a rate limit tier is set for a token
+/// Fields:
+/// - token: The public key of the token
+/// - index: The index of the tier
+/// - rate_limit: The rate limit of the tier
+/// - limit_window: The time window of the tier, 0 if the tier was cleared
+#[event]
+pub struct TokenLimitTierSet {
+    pub token: Pubkey,
+    pub index: u8,
+    pub rate_limit: u64,
+    pub limit_window: u64,
+}
+
+/// Event emitted when the cross-token notional limits are set
+/// Fields:
+/// - notional_rate_limit: The maximum notional across all users in the limit window
+/// - notional_limit_window: The global limit window, 0 if disabled
+/// - user_notional_rate_limit: The maximum notional per user in the limit window
+/// - user_notional_limit_window: The per-user limit window, 0 if disabled
+#[event]
+pub struct NotionalLimitsSet {
+    pub notional_rate_limit: u64,
+    pub notional_limit_window: u64,
+    pub user_notional_rate_limit: u64,
+    pub user_notional_limit_window: u64,
+}
+
+/// Event emitted when the rate limit of a limit group is set
+/// Fields:
+/// - limit_group: The address of the limit group
+/// - mint: The GM Token mint the limit applies to
+/// - group_id: The ID of the group
+/// - rate_limit: The shared rate limit
+/// - limit_window: The time window for the shared rate limit
+#[event]
+pub struct LimitGroupSet {
+    pub limit_group: Pubkey,
+    pub mint: Pubkey,
+    pub group_id: u64,
+    pub rate_limit: u64,
+    pub limit_window: u64,
+}
+
+/// Event emitted when a user is attached to or detached from a limit group
+/// Fields:
+/// - user: The owner of the OndoUser account
+/// - mint: The GM Token mint
+/// - limit_group: The limit group the user is attached to, None if detached
+#[event]
+pub struct OndoUserLimitGroupSet {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub limit_group: Option<Pubkey>,
+}
+
+/// Event emitted when a trade skips the per-user rate limit because the user holds the `RateLimitExemptRole`
+/// Fields:
+/// - user: The user the trade is executed for
+/// - mint: The GM Token mint
+/// - amount: The amount charged to the token-level rate limit
+/// - is_buy: Whether the trade is a mint (true) or a redemption (false)
+#[event]
+pub struct RateLimitExemptTrade {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub is_buy: bool,
+}
+
+/// Event emitted when the limits of a limit profile are set
+/// Fields:
+/// - limit_profile: The address of the LimitProfile account
+/// - profile_id: The ID of the profile
+/// - rate_limit: The mint rate limit of each linked user
+/// - limit_window: The mint limit window in seconds
+/// - redeem_rate_limit: The redeem rate limit, None if it falls back to the mint rate limit
+/// - redeem_limit_window: The redeem limit window in seconds, None if it falls back to the mint window
+#[event]
+pub struct LimitProfileSet {
+    pub limit_profile: Pubkey,
+    pub profile_id: u64,
+    pub rate_limit: u64,
+    pub limit_window: u64,
+    pub redeem_rate_limit: Option<u64>,
+    pub redeem_limit_window: Option<u64>,
+}
+
+/// Event emitted when an OndoUser is linked to, or unlinked from, a limit profile
+/// Fields:
+/// - user: The owner of the OndoUser account
+/// - mint: The GM Token mint
+/// - limit_profile: The LimitProfile the user is linked to, None if unlinked
+#[event]
+pub struct OndoUserLimitProfileSet {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub limit_profile: Option<Pubkey>,
+}
+
+/// Event emitted when the default limit profile of a token is set
+/// Fields:
+/// - token: The GM Token mint
+/// - limit_profile: The LimitProfile new users are linked to, None to copy the default user limits
+#[event]
+pub struct TokenDefaultLimitProfileSet {
+    pub token: Pubkey,
+    pub limit_profile: Option<Pubkey>,
+}
+
+/// Event emitted when the limits of many OndoUser accounts are updated at once
+/// Fields:
+/// - ondo_users: The addresses of the updated OndoUser accounts
+/// - update: The update applied to each account
+#[event]
+pub struct OndoUserLimitsBatchSet {
+    pub ondo_users: Vec<Pubkey>,
+    pub update: OndoUserLimitsUpdate,
+}
+
+/// Event emitted when the secondary USDC oracle price cannot be used while the primary can,
+/// so the primary price is accepted without the divergence cross-check
+/// Fields:
+/// - price_update: The secondary USDC price oracle account
+/// - primary_price: The accepted primary USDC price
+#[event]
+pub struct USDCSecondaryOracleUnusable {
+    pub price_update: Pubkey,
+    pub primary_price: u64,
+}
+
+/// Event emitted when a queued redemption claim is re-routed to another token account
+/// Fields:
+/// - claim_id: The ID of the claim in the redemption queue
+/// - user: The public key of the user who redeemed
+/// - previous_destination: The USDC token account the claim was paid to
+/// - destination: The USDC token account the claim is paid to from now on
+/// - guardian: The public key of the guardian who re-routed the claim
+#[event]
+pub struct RedemptionClaimRerouted {
+    pub claim_id: u64,
+    pub user: Pubkey,
+    pub previous_destination: Pubkey,
+    pub destination: Pubkey,
+    pub guardian: Pubkey,
+}
+
+/// Event emitted when the claim at the front of the redemption queue is moved to the back
+/// Fields:
+/// - claim_id: The ID of the claim in the redemption queue
+/// - user: The public key of the user who redeemed
+/// - guardian: The public key of the guardian who deferred the claim
+#[event]
+pub struct RedemptionClaimDeferred {
+    pub claim_id: u64,
+    pub user: Pubkey,
+    pub guardian: Pubkey,
+}
@@ -1,14 +1,19 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{prelude::*, Discriminator};
 use anchor_spl::token_interface::Mint;
 
 use crate::{
     constants::*,
     errors::OndoError,
     events::{
-        GMTokenMintingPaused, GMTokenRedemptionPaused, RateLimitUserSet, RoleGranted, RoleRevoked,
-        SetTradingHoursOffset, TokenManagerMintingPaused, TokenManagerRedemptionPaused,
+        GMTokenMintingPaused, GMTokenRedemptionPaused, NotionalLimitsSet, OndoUserLimitsBatchSet,
+        RateLimitUserSet, RoleGranted, RoleRevoked, SetTradingHoursOffset,
+        TokenManagerMintingPaused, TokenManagerRedemptionPaused,
     },
-    state::{GMTokenManagerState, OndoUser, RoleType, Roles, TokenLimit},
+    state::{
+        GMTokenManagerState, NotionalUsage, OndoUser, OndoUserLimitsUpdate, RoleType, Roles,
+        TokenLimit,
+    },
+    utils::migrate_account_len,
 };
 
 /// Initialize the `GmTokenManagerState` account
@@ -80,6 +85,12 @@ impl<'info> InitializeGMTokenManager<'info> {
             bump: bumps.gmtoken_manager_state,
             attestation_signer_secp,
             trading_hours_offset,
+            notional_rate_limit: 0,
+            notional_limit_window: 0,
+            mint_notional_usage: NotionalUsage::default(),
+            redeem_notional_usage: NotionalUsage::default(),
+            user_notional_rate_limit: 0,
+            user_notional_limit_window: 0,
         });
 
         Ok(())
@@ -88,7 +99,7 @@ impl<'info> InitializeGMTokenManager<'info> {
 
 /// Grant a GM Token Manager role to a user by initializing a `Roles` account
 /// Requires `ADMIN_ROLE_GMTOKEN_MANAGER` role
-/// Only the `PauserRoleGmtokenManager` or `IssuanceHoursRole` roles can be added
+/// Only the `PauserRoleGmtokenManager`, `IssuanceHoursRole`, `OrderOperatorRole` or `RateLimitExemptRole` roles can be added
 #[derive(Accounts)]
 #[instruction(role: RoleType, user: Pubkey)]
 pub struct GMTokenManagerGrantRole<'info> {
@@ -129,7 +140,7 @@ pub struct GMTokenManagerGrantRole<'info> {
 impl<'info> GMTokenManagerGrantRole<'info> {
     /// Add a GM Token Manager role to a user
     /// # Arguments
-    /// * `role` - The role to grant (must be `PauserRoleGmtokenManager` or `IssuanceHoursRole`)
+    /// * `role` - The role to grant (must be `PauserRoleGmtokenManager`, `IssuanceHoursRole`, `OrderOperatorRole` or `RateLimitExemptRole`)
     /// * `user` - The public key of the user to grant the role to
     /// * `bumps` - The PDA bumps for account derivation
     /// # Returns
@@ -140,11 +151,14 @@ impl<'info> GMTokenManagerGrantRole<'info> {
         user: Pubkey,
         bumps: &GMTokenManagerGrantRoleBumps,
     ) -> Result<()> {
-        // Only allow PauserRoleGmtokenManager and IssuanceHoursRole roles to be created
+        // Only allow PauserRoleGmtokenManager, IssuanceHoursRole, OrderOperatorRole and RateLimitExemptRole roles to be created
         require!(
             matches!(
                 role,
-                RoleType::PauserRoleGMTokenManager | RoleType::IssuanceHoursRole
+                RoleType::PauserRoleGMTokenManager
+                    | RoleType::IssuanceHoursRole
+                    | RoleType::OrderOperatorRole
+                    | RoleType::RateLimitExemptRole
             ),
             OndoError::InvalidRoleType
         );
@@ -167,7 +181,7 @@ impl<'info> GMTokenManagerGrantRole<'info> {
 
 /// Revoke a GM Token Manager role from a user by closing their `Roles` account
 /// Requires `ADMIN_ROLE_GMTOKEN_MANAGER` role
-/// Only the `PauserRoleGmtokenManager` and `IssuanceHoursRole` roles can be removed
+/// Only the `PauserRoleGmtokenManager`, `IssuanceHoursRole`, `OrderOperatorRole` and `RateLimitExemptRole` roles can be removed
 #[derive(Accounts)]
 pub struct GMTokenManagerRevokeRole<'info> {
     /// The account with the authority to revoke GM Token Manager roles
@@ -211,11 +225,14 @@ impl<'info> GMTokenManagerRevokeRole<'info> {
     /// # Returns
     /// * `Result<()>` - Ok if the role is successfully revoked, Err otherwise
     pub fn revoke_gmtoken_manager_role(&mut self) -> Result<()> {
-        // Only allow PauserRoleGmtokenManager or IssuanceHoursRole roles to be revoked
+        // Only allow PauserRoleGmtokenManager, IssuanceHoursRole, OrderOperatorRole or RateLimitExemptRole roles to be revoked
         require!(
             matches!(
                 self.role_to_revoke.role,
-                RoleType::PauserRoleGMTokenManager | RoleType::IssuanceHoursRole
+                RoleType::PauserRoleGMTokenManager
+                    | RoleType::IssuanceHoursRole
+                    | RoleType::OrderOperatorRole
+                    | RoleType::RateLimitExemptRole
             ),
             OndoError::InvalidRoleType
         );
@@ -353,7 +370,7 @@ impl<'info> GMTokenManagerTokenPauser<'info> {
 
 /// Pause/Unpause subscriptions/redemptions for all GM Tokens
 /// Requires `ADMIN_ROLE_GMTOKEN_MANAGER` role
-/// Can also set attestation signer
+/// Can also set attestation signer and the cross-token notional limits
 #[derive(Accounts)]
 pub struct GMTokenManagerAdminGlobalPauser<'info> {
     /// The account with the authority to execute the unpause/configuration operation
@@ -437,6 +454,93 @@ impl<'info> GMTokenManagerAdminGlobalPauser<'info> {
 
         Ok(())
     }
+
+    /// Set the USD notional limits applied across all GM Tokens
+    /// Mints and redemptions are limited separately. The usage of the global limit is reset.
+    /// # Arguments
+    /// * `notional_rate_limit` - The maximum notional across all users in the limit window
+    /// * `notional_limit_window` - The global limit window in seconds, 0 disables the global limit
+    /// * `user_notional_rate_limit` - The maximum notional per user in the limit window
+    /// * `user_notional_limit_window` - The per-user limit window in seconds, 0 disables the per-user limit
+    /// # Returns
+    /// * `Result<()>` - Ok if the limits are successfully set, Err otherwise
+    pub fn set_notional_limits(
+        &mut self,
+        notional_rate_limit: u64,
+        notional_limit_window: u64,
+        user_notional_rate_limit: u64,
+        user_notional_limit_window: u64,
+    ) -> Result<()> {
+        let state = &mut self.gmtoken_manager_state;
+        state.notional_rate_limit = notional_rate_limit;
+        state.notional_limit_window = notional_limit_window;
+        state.mint_notional_usage = NotionalUsage::default();
+        state.redeem_notional_usage = NotionalUsage::default();
+        state.user_notional_rate_limit = user_notional_rate_limit;
+        state.user_notional_limit_window = user_notional_limit_window;
+
+        emit!(NotionalLimitsSet {
+            notional_rate_limit,
+            notional_limit_window,
+            user_notional_rate_limit,
+            user_notional_limit_window,
+        });
+
+        Ok(())
+    }
+}
+
+/// Grow the `GmTokenManagerState` account to the current layout
+/// Requires `ADMIN_ROLE_GMTOKEN_MANAGER` role
+/// Fields added since the account was created read as zero, which disables them
+#[derive(Accounts)]
+pub struct MigrateGMTokenManagerState<'info> {
+    /// The account with the authority to execute the migration, pays for the extra rent
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The `Roles` account verifying the authority has the `ADMIN_ROLE_GMTOKEN_MANAGER` role
+    /// # PDA Seeds
+    /// - `ADMIN_ROLE_GMTOKEN_MANAGER`
+    /// - The authority's address
+    #[account(
+        seeds = [RoleType::ADMIN_ROLE_GMTOKEN_MANAGER, authority.key().as_ref()],
+        bump = authority_role_account.bump,
+    )]
+    pub authority_role_account: Account<'info, Roles>,
+
+    /// The `GmTokenManagerState` account to be migrated
+    /// # PDA Seeds
+    /// - `GMTOKEN_MANAGER_STATE_SEED`
+    ///
+    /// CHECK: Seeds and owner constraints validate the account.
+    /// Not deserialized as it may be smaller than the current layout.
+    #[account(
+        mut,
+        seeds = [GMTOKEN_MANAGER_STATE_SEED],
+        bump,
+        owner = crate::ID,
+    )]
+    pub gmtoken_manager_state: UncheckedAccount<'info>,
+
+    /// The system program
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateGMTokenManagerState<'info> {
+    /// Resize the account to the current layout, topping up rent if needed
+    /// Does nothing if the account already has the current size
+    /// # Returns
+    /// * `Result<()>` - Ok if the account is migrated, Err otherwise
+    pub fn migrate_gmtoken_manager_state(&self) -> Result<()> {
+        migrate_account_len(
+            &self.gmtoken_manager_state.to_account_info(),
+            GMTokenManagerState::DISCRIMINATOR,
+            8 + GMTokenManagerState::INIT_SPACE,
+            &self.authority.to_account_info(),
+            &self.system_program.to_account_info(),
+        )
+    }
 }
 
 /// Pause subscription/redemptions for a GM Token
@@ -566,44 +670,163 @@ pub struct GMTokenManagerAdminSetUserLimits<'info> {
 impl<'info> GMTokenManagerAdminSetUserLimits<'info> {
     /// Set the rate limit for a specific user on a GM Token
     /// # Arguments
-    /// * `rate_limit` - The new rate limit for the user (maximum tokens per window)
+    /// * `rate_limit` - The new mint rate limit for the user (maximum tokens per window)
+    /// * `limit_window` - The new mint limit window in seconds, 0 uses the token default
+    /// * `redeem_rate_limit` - The new redeem rate limit for the user, None to use the mint rate limit
+    /// * `redeem_limit_window` - The new redeem limit window in seconds, None to use the mint window,
+    ///   0 uses the token default
     /// # Returns
     /// * `Result<()>` - Ok if the user rate limit is successfully set, Err otherwise
-    pub fn set_ondo_user_limits(&mut self, rate_limit: u64, limit_window: u64) -> Result<()> {
-        // Set the rate_limit field
-        self.ondo_user.rate_limit = Some(rate_limit);
-
-        // Set the limit_window field
-        self.ondo_user.limit_window = Some(limit_window);
-
+    pub fn set_ondo_user_limits(
+        &mut self,
+        rate_limit: u64,
+        limit_window: u64,
+        redeem_rate_limit: Option<u64>,
+        redeem_limit_window: Option<u64>,
+    ) -> Result<()> {
         // If limit_window is set to 0, default to token_limit's default_user_limit_window if set,
         // otherwise use DEFAULT_LIMIT_WINDOW.
-        if self.ondo_user.limit_window == Some(0) {
-            if self.token_limit.default_user_limit_window.is_none() {
-                self.ondo_user.limit_window = Some(DEFAULT_LIMIT_WINDOW);
-            } else {
-                self.ondo_user.limit_window = self.token_limit.default_user_limit_window;
-            }
-        }
-
-        // Initialize rate_used fields if not already set
-        if self.ondo_user.mint_capacity_used.is_none() {
-            self.ondo_user.mint_capacity_used = Some(0);
-        }
-        if self.ondo_user.redeem_capacity_used.is_none() {
-            self.ondo_user.redeem_capacity_used = Some(0);
-        }
+        let limit_window = if limit_window == 0 {
+            self.token_limit
+                .default_user_limit_window
+                .unwrap_or(DEFAULT_LIMIT_WINDOW)
+        } else {
+            limit_window
+        };
+
+        // If redeem_limit_window is set to 0, default to token_limit's default user redeem
+        // window, then its default user window, otherwise use DEFAULT_LIMIT_WINDOW.
+        let redeem_limit_window = match redeem_limit_window {
+            Some(0) => Some(
+                self.token_limit
+                    .default_user_redeem_limit_window
+                    .or(self.token_limit.default_user_limit_window)
+                    .unwrap_or(DEFAULT_LIMIT_WINDOW),
+            ),
+            window => window,
+        };
+
+        // Set the limits, None redeem fields make redemptions use the mint limits
+        self.ondo_user.set_limits(
+            rate_limit,
+            limit_window,
+            redeem_rate_limit,
+            redeem_limit_window,
+        );
 
         // Emit event for rate limit set
         emit!(RateLimitUserSet {
             user: self.ondo_user.owner,
             limit: rate_limit,
+            redeem_limit: redeem_rate_limit,
         });
 
         Ok(())
     }
 }
 
+/// Update the limits of many OndoUser accounts, across any GM Tokens
+/// Requires `ADMIN_ROLE_GMTOKEN_MANAGER` role
+///
+/// OndoUser accounts are passed via remaining_accounts, constraints:
+/// 1. Accounts must be marked writable
+/// 2. No other accounts should present in `remaining_accounts`
+/// 3. Each account must be an OndoUser PDA of this program
+#[derive(Accounts)]
+pub struct GMTokenManagerAdminBatchSetUserLimits<'info> {
+    /// The account with the authority to set user limits
+    pub authority: Signer<'info>,
+
+    /// The `Roles` account verifying the authority has the `ADMIN_ROLE_GMTOKEN_MANAGER` role
+    /// # PDA Seeds
+    /// - `ADMIN_ROLE_GMTOKEN_MANAGER`
+    /// - The authority's address
+    #[account(
+        seeds = [RoleType::ADMIN_ROLE_GMTOKEN_MANAGER, authority.key().as_ref()],
+        bump = authority_role_account.bump,
+    )]
+    pub authority_role_account: Account<'info, Roles>,
+}
+
+impl<'info> GMTokenManagerAdminBatchSetUserLimits<'info> {
+    /// Apply the same update to every OndoUser account in remaining_accounts
+    /// Limit windows must be set explicitly since the accounts can belong to different tokens
+    /// # Arguments
+    /// * `update` - The update to apply to each account
+    /// * `remaining_accounts` - The OndoUser accounts to update
+    /// # Returns
+    /// * `Result<()>` - Ok if every account is successfully updated, Err otherwise
+    /// # Errors
+    /// * `OndoError::InvalidRateLimit` - If a limit window is 0
+    /// * `OndoError::ProgramMismatch` - If an account is not owned by the program
+    /// * `OndoError::InvalidOndoUser` - If an account is not at its OndoUser PDA
+    pub fn batch_set_ondo_user_limits(
+        &mut self,
+        update: OndoUserLimitsUpdate,
+        remaining_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        if let OndoUserLimitsUpdate::SetLimits {
+            limit_window,
+            redeem_limit_window,
+            ..
+        } = update
+        {
+            require_gt!(limit_window, 0, OndoError::InvalidRateLimit);
+            if let Some(window) = redeem_limit_window {
+                require_gt!(window, 0, OndoError::InvalidRateLimit);
+            }
+        }
+
+        let mut ondo_users = Vec::with_capacity(remaining_accounts.len());
+
+        for ondo_user_info in remaining_accounts.iter() {
+            require_keys_eq!(*ondo_user_info.owner, crate::ID, OndoError::ProgramMismatch);
+            require!(ondo_user_info.is_writable, ErrorCode::AccountNotMutable);
+
+            // Deserialize the OndoUser account and validate its PDA derivation
+            let mut ondo_user: Account<OndoUser> = Account::try_from(ondo_user_info)?;
+            let expected_address = Pubkey::create_program_address(
+                &[
+                    ONDO_USER_SEED,
+                    ondo_user.owner.as_ref(),
+                    ondo_user.mint.as_ref(),
+                    &[ondo_user.bump],
+                ],
+                &crate::ID,
+            )
+            .map_err(|_| OndoError::InvalidOndoUser)?;
+            require_keys_eq!(
+                ondo_user_info.key(),
+                expected_address,
+                OndoError::InvalidOndoUser
+            );
+
+            match update {
+                OndoUserLimitsUpdate::SetLimits {
+                    rate_limit,
+                    limit_window,
+                    redeem_rate_limit,
+                    redeem_limit_window,
+                } => ondo_user.set_limits(
+                    rate_limit,
+                    limit_window,
+                    redeem_rate_limit,
+                    redeem_limit_window,
+                ),
+                OndoUserLimitsUpdate::ResetUsage => ondo_user.reset_usage(),
+            }
+
+            // Write the updated account back
+            ondo_user.exit(&crate::ID)?;
+            ondo_users.push(ondo_user_info.key());
+        }
+
+        emit!(OndoUserLimitsBatchSet { ondo_users, update });
+
+        Ok(())
+    }
+}
+
 /// Set trading hours offset for the GM token manager
 /// Requires `ADMIN_ROLE_GMTOKEN_MANAGER` or `ISSUANCE_HOURS_ROLE` role
 #[derive(Accounts)]
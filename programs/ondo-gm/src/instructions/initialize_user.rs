@@ -56,6 +56,7 @@ impl<'info> InitializeUser<'info> {
     /// # Arguments
     /// * `rate_limit` - Optional maximum number of tokens that can be minted/redeemed within the limit window.
     /// * `limit_window` - Optional time window (in seconds) for the rate limit.
+    ///   Separate redeem limits can be set afterwards with `set_ondo_user_limits`.
     /// * `bumps` - Bumps for PDA derivation.
     /// # Returns
     /// * `Result<()>` - Result indicating success or failure.
@@ -77,6 +78,10 @@ impl<'info> InitializeUser<'info> {
                     redeem_capacity_used: Some(0), // Initialize to 0 when rate limits are set
                     redeem_last_updated: None,
                     bump: bumps.ondo_user,
+                    redeem_rate_limit: None,
+                    redeem_limit_window: None,
+                    limit_group: None,
+                    limit_profile: None,
                 })
             }
             _ => self.ondo_user.set_inner(OndoUser {
@@ -89,6 +94,10 @@ impl<'info> InitializeUser<'info> {
                 redeem_capacity_used: None,
                 redeem_last_updated: None,
                 bump: bumps.ondo_user,
+                redeem_rate_limit: None,
+                redeem_limit_window: None,
+                limit_group: None,
+                limit_profile: None,
             }),
         }
 
@@ -0,0 +1,143 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    constants::{LIMIT_GROUP_SEED, ONDO_USER_SEED},
+    errors::OndoError,
+    events::{LimitGroupSet, OndoUserLimitGroupSet},
+    state::{LimitGroup, OndoUser, RoleType, Roles},
+};
+
+/// Set the shared rate limit of a limit group for a GM Token, creating the group if needed
+/// Requires `ADMIN_ROLE_GMTOKEN_MANAGER` role
+#[derive(Accounts)]
+#[instruction(group_id: u64)]
+pub struct SetLimitGroup<'info> {
+    /// The account with the authority to set the limit group, pays for account creation
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The `Roles` account verifying the authority has the `ADMIN_ROLE_GMTOKEN_MANAGER` role
+    /// # PDA Seeds
+    /// - `ADMIN_ROLE_GMTOKEN_MANAGER`
+    /// - The authority's address
+    #[account(
+        seeds = [RoleType::ADMIN_ROLE_GMTOKEN_MANAGER, authority.key().as_ref()],
+        bump = authority_role_account.bump,
+    )]
+    pub authority_role_account: Account<'info, Roles>,
+
+    /// The GM Token mint the limit applies to
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// The `LimitGroup` account
+    /// # PDA Seeds
+    /// - `LIMIT_GROUP_SEED`
+    /// - Mint address
+    /// - The group ID (little endian)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + LimitGroup::INIT_SPACE,
+        seeds = [LIMIT_GROUP_SEED, mint.key().as_ref(), &group_id.to_le_bytes()],
+        bump,
+    )]
+    pub limit_group: Account<'info, LimitGroup>,
+
+    /// The system program
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetLimitGroup<'info> {
+    /// Set the shared rate limit of the group, keeping the capacity already used
+    /// # Arguments
+    /// * `group_id` - The ID of the group, unique per mint
+    /// * `rate_limit` - The maximum amount the group can mint, and separately redeem, in the limit window
+    /// * `limit_window` - The limit window in seconds (must be > 0)
+    /// * `bump` - The bump of the `LimitGroup` PDA
+    /// # Returns
+    /// * `Result<()>` - Ok if the limit group is successfully set, Err otherwise
+    pub fn set_limit_group(
+        &mut self,
+        group_id: u64,
+        rate_limit: u64,
+        limit_window: u64,
+        bump: u8,
+    ) -> Result<()> {
+        require_gt!(limit_window, 0, OndoError::InvalidRateLimit);
+
+        let limit_group = &mut self.limit_group;
+        limit_group.group_id = group_id;
+        limit_group.mint = self.mint.key();
+        limit_group.rate_limit = rate_limit;
+        limit_group.limit_window = limit_window;
+        limit_group.bump = bump;
+
+        emit!(LimitGroupSet {
+            limit_group: limit_group.key(),
+            mint: self.mint.key(),
+            group_id,
+            rate_limit,
+            limit_window,
+        });
+
+        Ok(())
+    }
+}
+
+/// Attach an `OndoUser` to a limit group, or detach it
+/// Requires `ADMIN_ROLE_GMTOKEN_MANAGER` role
+#[derive(Accounts)]
+pub struct SetOndoUserLimitGroup<'info> {
+    /// The account with the authority to attach the user
+    pub authority: Signer<'info>,
+
+    /// The `Roles` account verifying the authority has the `ADMIN_ROLE_GMTOKEN_MANAGER` role
+    /// # PDA Seeds
+    /// - `ADMIN_ROLE_GMTOKEN_MANAGER`
+    /// - The authority's address
+    #[account(
+        seeds = [RoleType::ADMIN_ROLE_GMTOKEN_MANAGER, authority.key().as_ref()],
+        bump = authority_role_account.bump,
+    )]
+    pub authority_role_account: Account<'info, Roles>,
+
+    /// The GM Token mint
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// The `OndoUser` account to update
+    /// # PDA Seeds
+    /// - `ONDO_USER_SEED`
+    /// - User's owner address
+    /// - Mint address
+    #[account(
+        mut,
+        seeds = [ONDO_USER_SEED, ondo_user.owner.as_ref(), mint.key().as_ref()],
+        bump = ondo_user.bump,
+    )]
+    pub ondo_user: Account<'info, OndoUser>,
+
+    /// The `LimitGroup` to attach the user to, None to detach the user
+    #[account(
+        has_one = mint @ OndoError::InvalidLimitGroup,
+    )]
+    pub limit_group: Option<Account<'info, LimitGroup>>,
+}
+
+impl<'info> SetOndoUserLimitGroup<'info> {
+    /// Attach the user to the provided limit group, or detach it if none is provided
+    /// # Returns
+    /// * `Result<()>` - Ok if the limit group is successfully set, Err otherwise
+    pub fn set_ondo_user_limit_group(&mut self) -> Result<()> {
+        let limit_group = self.limit_group.as_ref().map(|group| group.key());
+        self.ondo_user.limit_group = limit_group;
+
+        emit!(OndoUserLimitGroupSet {
+            user: self.ondo_user.owner,
+            mint: self.mint.key(),
+            limit_group,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,766 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions::ID as INSTRUCTIONS_ID;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::Token,
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+        TransferChecked,
+    },
+};
+
+use super::{validate_order_deposit, TokenManager};
+use crate::{
+    constants::{
+        ATTESTATION_ID_SEED, GMTOKEN_MANAGER_STATE_SEED, LIMIT_ORDER_SEED, MINT_AUTHORITY_SEED,
+        ONDO_USER_SEED, ORACLE_SANITY_CHECK_SEED, REDEMPTION_QUEUE_SEED, TOKEN_LIMIT_ACCOUNT_SEED,
+        USDON_MANAGER_STATE_SEED, USER_NOTIONAL_LIMIT_SEED, WHITELIST_SEED,
+    },
+    errors::OndoError,
+    events::{LimitOrderCancelled, LimitOrderCreated, LimitOrderFilled},
+    state::{
+        GMTokenManagerState, LimitGroup, LimitOrder, LimitProfile, OndoUser, OracleSanityCheck,
+        RedemptionQueue, RoleType, Roles, TokenLimit, USDonManagerState, UserNotionalLimit,
+        Whitelist,
+    },
+    utils::release_escrow,
+};
+
+/// Place a limit order to mint or redeem GM Tokens.
+/// The user's USDon, USDC or GM Tokens are escrowed in a token account owned by the order PDA.
+/// Unpermissioned - the user must be whitelisted.
+#[derive(Accounts)]
+#[instruction(order_id: u64)]
+pub struct CreateLimitOrder<'info> {
+    /// The user placing the order, pays for account creation
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// The GM Token mint the order is for
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The TokenLimit account of the GM Token, checked for pause flags
+    /// # PDA Seeds
+    /// - TOKEN_LIMIT_ACCOUNT_SEED
+    /// - Mint address
+    #[account(
+        seeds = [TOKEN_LIMIT_ACCOUNT_SEED, mint.key().as_ref()],
+        bump = token_limit_account.bump,
+    )]
+    pub token_limit_account: Box<Account<'info, TokenLimit>>,
+
+    /// The Whitelist account verifying the user is authorized
+    /// # PDA Seeds
+    /// - WHITELIST_SEED
+    /// - User's address
+    ///
+    /// CHECK: Seeds constraint validates PDA address.
+    /// Validated in instruction handler - returns UserNotWhitelisted if not initialized.
+    #[account(
+        seeds = [WHITELIST_SEED, user.key().as_ref()],
+        bump,
+    )]
+    pub whitelist: UncheckedAccount<'info>,
+
+    /// The LimitOrder account being created
+    /// # PDA Seeds
+    /// - LIMIT_ORDER_SEED
+    /// - User's address
+    /// - Order ID (little endian)
+    #[account(
+        init,
+        payer = user,
+        space = 8 + LimitOrder::INIT_SPACE,
+        seeds = [LIMIT_ORDER_SEED, user.key().as_ref(), order_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub order: Box<Account<'info, LimitOrder>>,
+
+    /// The mint of the tokens being escrowed
+    /// USDon or USDC for mints, the GM Token for redemptions
+    #[account(mint::token_program = deposit_token_program)]
+    pub deposit_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The user's token account the deposit is taken from
+    #[account(
+        mut,
+        token::mint = deposit_mint,
+        token::authority = user,
+        token::token_program = deposit_token_program,
+    )]
+    pub user_deposit_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The escrow token account owned by the order
+    #[account(
+        init,
+        payer = user,
+        associated_token::mint = deposit_mint,
+        associated_token::authority = order,
+        associated_token::token_program = deposit_token_program,
+    )]
+    pub escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The USDonManagerState account used to validate the deposit mint
+    /// # PDA Seeds
+    /// - USDON_MANAGER_STATE_SEED
+    #[account(
+        seeds = [USDON_MANAGER_STATE_SEED],
+        bump = usdon_manager_state.bump,
+    )]
+    pub usdon_manager_state: Box<Account<'info, USDonManagerState>>,
+
+    /// The GMTokenManagerState account, checked for pause flags
+    /// # PDA Seeds
+    /// - GMTOKEN_MANAGER_STATE_SEED
+    #[account(
+        seeds = [GMTOKEN_MANAGER_STATE_SEED],
+        bump = gmtoken_manager_state.bump,
+    )]
+    pub gmtoken_manager_state: Box<Account<'info, GMTokenManagerState>>,
+
+    /// The token program of the deposit mint
+    /// Token-2022 for USDon and GM Tokens, the legacy SPL Token program for USDC
+    pub deposit_token_program: Interface<'info, TokenInterface>,
+
+    /// The associated token program
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// The system program
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateLimitOrder<'info> {
+    /// Place a limit order and escrow the user's deposit
+    /// # Arguments
+    /// * `order_id` - User supplied identifier for the order
+    /// * `is_buy` - True to mint GM Tokens, false to redeem them
+    /// * `is_usdon` - True to settle in USDon, false to settle in USDC
+    /// * `amount` - The amount to escrow: USDon or USDC for mints, GM Tokens for redemptions
+    /// * `limit_price` - The worst acceptable price, scaled by PRICE_SCALING_FACTOR
+    /// * `expires_at` - Unix timestamp after which the order can no longer be filled
+    /// * `bump` - The bump of the order PDA
+    /// # Returns
+    /// * `Result<()>` - Ok if the order is placed, Err otherwise
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_limit_order(
+        &mut self,
+        order_id: u64,
+        is_buy: bool,
+        is_usdon: bool,
+        amount: u64,
+        limit_price: u64,
+        expires_at: i64,
+        bump: u8,
+    ) -> Result<()> {
+        require_gt!(amount, 0, OndoError::InvalidAmount);
+        require_gt!(limit_price, 0, OndoError::InvalidPrice);
+
+        Whitelist::verify(&self.whitelist)?;
+
+        let current_timestamp = Clock::get()?.unix_timestamp;
+        require_gt!(expires_at, current_timestamp, OndoError::OrderExpired);
+
+        validate_order_deposit(
+            &self.gmtoken_manager_state,
+            &self.token_limit_account,
+            &self.usdon_manager_state,
+            &self.mint.key(),
+            &self.deposit_mint.key(),
+            &self.deposit_token_program.key(),
+            is_buy,
+            is_usdon,
+        )?;
+
+        // Move the deposit into the order's escrow
+        transfer_checked(
+            CpiContext::new(
+                self.deposit_token_program.to_account_info(),
+                TransferChecked {
+                    from: self.user_deposit_token_account.to_account_info(),
+                    mint: self.deposit_mint.to_account_info(),
+                    to: self.escrow.to_account_info(),
+                    authority: self.user.to_account_info(),
+                },
+            ),
+            amount,
+            self.deposit_mint.decimals,
+        )?;
+
+        self.order.set_inner(LimitOrder {
+            user: self.user.key(),
+            order_id,
+            mint: self.mint.key(),
+            deposit_mint: self.deposit_mint.key(),
+            escrow: self.escrow.key(),
+            is_buy,
+            is_usdon,
+            limit_price,
+            expires_at,
+            deposit_amount: amount,
+            filled_amount: 0,
+            created_at: current_timestamp,
+            bump,
+        });
+
+        emit!(LimitOrderCreated {
+            order: self.order.key(),
+            user: self.user.key(),
+            mint: self.mint.key(),
+            is_buy,
+            is_usdon,
+            deposit_amount: amount,
+            limit_price,
+            expires_at,
+        });
+
+        Ok(())
+    }
+}
+
+/// Cancel a limit order and return the remaining escrow to the user.
+/// The user can cancel at any time; once the order has expired anyone can close it.
+#[derive(Accounts)]
+pub struct CancelLimitOrder<'info> {
+    /// The account closing the order
+    pub caller: Signer<'info>,
+
+    /// The user who placed the order, receives the refund and the rent of the closed accounts
+    /// CHECK: Address constraint validates it is the order's user.
+    #[account(mut, address = order.user)]
+    pub user: UncheckedAccount<'info>,
+
+    /// The LimitOrder account being cancelled
+    /// # PDA Seeds
+    /// - LIMIT_ORDER_SEED
+    /// - User's address
+    /// - Order ID (little endian)
+    #[account(
+        mut,
+        close = user,
+        has_one = deposit_mint,
+        has_one = escrow,
+        seeds = [LIMIT_ORDER_SEED, order.user.as_ref(), order.order_id.to_le_bytes().as_ref()],
+        bump = order.bump,
+    )]
+    pub order: Box<Account<'info, LimitOrder>>,
+
+    /// The escrow token account owned by the order
+    #[account(mut)]
+    pub escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The mint of the escrowed tokens
+    #[account(mint::token_program = deposit_token_program)]
+    pub deposit_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The user's token account receiving the refund
+    #[account(
+        mut,
+        token::mint = deposit_mint,
+        token::authority = user,
+        token::token_program = deposit_token_program,
+    )]
+    pub user_deposit_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The token program of the deposit mint
+    pub deposit_token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> CancelLimitOrder<'info> {
+    /// Cancel the order and refund the remaining escrow
+    /// # Returns
+    /// * `Result<()>` - Ok if the order is cancelled, Err otherwise
+    pub fn cancel_limit_order(&mut self) -> Result<()> {
+        if self.caller.key() != self.order.user {
+            require!(
+                Clock::get()?.unix_timestamp >= self.order.expires_at,
+                OndoError::OrderNotExpired
+            );
+        }
+
+        let order_id = self.order.order_id.to_le_bytes();
+        let user = self.user.key();
+        let order_seeds: &[&[u8]] = &[
+            LIMIT_ORDER_SEED,
+            user.as_ref(),
+            &order_id,
+            &[self.order.bump],
+        ];
+
+        let refunded = release_escrow(
+            &self.deposit_token_program.to_account_info(),
+            &self.escrow.to_account_info(),
+            &self.deposit_mint.to_account_info(),
+            self.deposit_mint.decimals,
+            &self.user_deposit_token_account.to_account_info(),
+            &self.user.to_account_info(),
+            &self.order.to_account_info(),
+            &[order_seeds],
+        )?;
+
+        emit!(LimitOrderCancelled {
+            order: self.order.key(),
+            user,
+            refunded,
+            closed_by: self.caller.key(),
+        });
+
+        Ok(())
+    }
+}
+
+/// Fill all or part of a limit order with an attestation priced at or better than the limit.
+/// Runs the same checks as a direct mint or redemption on behalf of the order's user.
+/// Requires `ORDER_OPERATOR_ROLE` role.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(attestation_id: [u8; 16])]
+pub struct FillLimitOrder<'info> {
+    /// The operator filling the order, pays for account creation if needed
+    #[account(mut)]
+    pub operator: Signer<'info>,
+
+    /// The `Roles` account verifying the operator has the `ORDER_OPERATOR_ROLE` role
+    /// # PDA Seeds
+    /// - ORDER_OPERATOR_ROLE
+    /// - The operator's address
+    #[account(
+        seeds = [RoleType::ORDER_OPERATOR_ROLE, operator.key().as_ref()],
+        bump = operator_role_account.bump,
+    )]
+    pub operator_role_account: Box<Account<'info, Roles>>,
+
+    /// The user who placed the order, receives the rent once the order is fully filled
+    /// CHECK: Address constraint validates it is the order's user.
+    #[account(mut, address = order.user)]
+    pub user: UncheckedAccount<'info>,
+
+    /// The LimitOrder account being filled
+    /// # PDA Seeds
+    /// - LIMIT_ORDER_SEED
+    /// - User's address
+    /// - Order ID (little endian)
+    #[account(
+        mut,
+        has_one = mint,
+        has_one = escrow,
+        seeds = [LIMIT_ORDER_SEED, order.user.as_ref(), order.order_id.to_le_bytes().as_ref()],
+        bump = order.bump,
+    )]
+    pub order: Box<Account<'info, LimitOrder>>,
+
+    /// The escrow token account owned by the order
+    #[account(mut, token::authority = order)]
+    pub escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The GM Token mint the order is for
+    #[account(
+        mut,
+        mint::authority = mint_authority,
+        mint::token_program = token_program
+    )]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The mint authority PDA
+    /// # PDA Seeds
+    /// - MINT_AUTHORITY_SEED
+    /// CHECK: This account is used to verify the mint authority.
+    /// Does not need to be checked for correctness as it is uninitialized.
+    #[account(
+        seeds = [MINT_AUTHORITY_SEED],
+        bump,
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    /// The OndoUser account tracking user-specific state for this mint
+    /// # PDA Seeds
+    /// - ONDO_USER_SEED
+    /// - User's address
+    /// - Mint address
+    #[account(
+        init_if_needed,
+        payer = operator,
+        space = 8 + OndoUser::INIT_SPACE,
+        seeds = [ONDO_USER_SEED, user.key().as_ref(), mint.key().as_ref()],
+        bump,
+    )]
+    pub ondo_user: Box<Account<'info, OndoUser>>,
+
+    /// The user's `RateLimitExemptRole` account, skipping the per-user rate limit
+    /// Only provided for designated market makers
+    /// # PDA Seeds
+    /// - RATE_LIMIT_EXEMPT_ROLE
+    /// - User's address
+    #[account(
+        seeds = [RoleType::RATE_LIMIT_EXEMPT_ROLE, user.key().as_ref()],
+        bump = rate_limit_exemption.bump,
+    )]
+    pub rate_limit_exemption: Option<Box<Account<'info, Roles>>>,
+
+    /// The LimitProfile the OndoUser is linked to
+    /// Required when the OndoUser is linked to a limit profile
+    pub limit_profile: Option<Box<Account<'info, LimitProfile>>>,
+
+    /// The limit group the OndoUser is attached to
+    /// Required when the OndoUser is attached to a limit group
+    #[account(mut)]
+    pub limit_group: Option<Box<Account<'info, LimitGroup>>>,
+
+    /// The user's notional across all GM Tokens
+    /// Required while the per-user notional limit is enabled
+    /// # PDA Seeds
+    /// - USER_NOTIONAL_LIMIT_SEED
+    /// - User's address
+    #[account(
+        mut,
+        seeds = [USER_NOTIONAL_LIMIT_SEED, user.key().as_ref()],
+        bump = user_notional_limit.bump,
+    )]
+    pub user_notional_limit: Option<Box<Account<'info, UserNotionalLimit>>>,
+
+    /// The TokenLimit account enforcing mint/burn limits for the GM Token
+    /// # PDA Seeds
+    /// - TOKEN_LIMIT_ACCOUNT_SEED
+    /// - Mint address
+    #[account(
+        mut,
+        seeds = [TOKEN_LIMIT_ACCOUNT_SEED, mint.key().as_ref()],
+        bump = token_limit_account.bump,
+    )]
+    pub token_limit_account: Box<Account<'info, TokenLimit>>,
+
+    /// The OracleSanityCheck account validating oracle price updates
+    /// # PDA Seeds
+    /// - ORACLE_SANITY_CHECK_SEED
+    /// - Mint address
+    #[account(
+        mut,
+        seeds = [ORACLE_SANITY_CHECK_SEED, mint.key().as_ref()],
+        bump = sanity_check_account.bump,
+    )]
+    pub sanity_check_account: Box<Account<'info, OracleSanityCheck>>,
+
+    /// The user's associated token account for the GM Token
+    #[account(
+        init_if_needed,
+        payer = operator,
+        associated_token::mint = mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    pub user_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The attestation ID account preventing attestation reuse
+    /// # PDA Seeds
+    /// - ATTESTATION_ID_SEED
+    /// - Attestation ID (16-byte array)
+    /// CHECK: Seeds constraint validates PDA address.
+    /// Existence means the attestation has been used.
+    #[account(
+        mut,
+        seeds = [ATTESTATION_ID_SEED, attestation_id.as_ref()],
+        bump,
+    )]
+    pub attestation_id_account: UncheckedAccount<'info>,
+
+    /// The Whitelist account verifying the user is authorized
+    /// # PDA Seeds
+    /// - WHITELIST_SEED
+    /// - User's address
+    /// CHECK: Seeds constraint validates PDA address.
+    /// Validated in instruction handler - returns UserNotWhitelisted if not initialized.
+    #[account(
+        seeds = [WHITELIST_SEED, user.key().as_ref()],
+        bump,
+    )]
+    pub whitelist: UncheckedAccount<'info>,
+
+    /// The token program (Token-2022)
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The system program
+    pub system_program: Program<'info, System>,
+
+    /// The associated token program
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// The legacy SPL Token program
+    /// Required for orders settled in USDC
+    pub spl_token_program: Option<Program<'info, Token>>,
+
+    /// The oracle price update account for USDC/USD price feed
+    /// Required for orders settled in USDC
+    /// CHECK: Address constraint against the account stored in `usdon_manager_state`.
+    #[account(address = usdon_manager_state.usdc_price_update)]
+    pub usdc_price_update: Option<UncheckedAccount<'info>>,
+
+    /// The secondary oracle price update account for USDC/USD price feed
+    /// Required for orders settled in USDC when a secondary oracle is configured
+    /// CHECK: Address constraint against the account stored in `usdon_manager_state`.
+    #[account(address = usdon_manager_state.usdc_secondary_price_update)]
+    pub usdc_secondary_price_update: Option<UncheckedAccount<'info>>,
+
+    /// The redemption queue holding USDC owed to redemptions the USDC vault could not cover
+    /// Required for USDC payouts when the USDC vault shortfall mode is Queue
+    /// # PDA Seeds
+    /// - REDEMPTION_QUEUE_SEED
+    #[account(
+        mut,
+        seeds = [REDEMPTION_QUEUE_SEED],
+        bump = redemption_queue.bump,
+    )]
+    pub redemption_queue: Option<Box<Account<'info, RedemptionQueue>>>,
+
+    /// The USDC vault storing USDC tokens
+    /// Required for orders settled in USDC
+    #[account(mut, address = usdon_manager_state.usdc_vault)]
+    pub usdc_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// The USDon vault storing USDon tokens
+    #[account(
+        mut,
+        associated_token::mint = usdon_mint,
+        associated_token::authority = usdon_manager_state,
+        associated_token::token_program = token_program,
+        constraint = usdon_vault.key() == usdon_manager_state.usdon_vault
+    )]
+    pub usdon_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The USDC mint (SPL Token)
+    /// Required for orders settled in USDC
+    pub usdc_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    /// The user's USDC token account
+    /// Required for orders settled in USDC
+    #[account(mut)]
+    pub user_usdc_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// The USDon mint (Token-2022)
+    #[account(
+        mut,
+        mint::token_program = token_program,
+        constraint = usdon_mint.key() == usdon_manager_state.usdon_mint
+    )]
+    pub usdon_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The user's associated token account for USDon
+    #[account(
+        init_if_needed,
+        payer = operator,
+        associated_token::mint = usdon_mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    pub user_usdon_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The USDonManagerState account used as authority for vault operations
+    /// Tracks the USDC reserved for queued redemption claims
+    /// # PDA Seeds
+    /// - USDON_MANAGER_STATE_SEED
+    #[account(
+        mut,
+        seeds = [USDON_MANAGER_STATE_SEED],
+        bump = usdon_manager_state.bump,
+    )]
+    pub usdon_manager_state: Box<Account<'info, USDonManagerState>>,
+
+    /// The GmTokenManagerState account managing GM Token operations
+    /// # PDA Seeds
+    /// - GMTOKEN_MANAGER_STATE_SEED
+    #[account(
+        mut,
+        seeds = [GMTOKEN_MANAGER_STATE_SEED],
+        bump = gmtoken_manager_state.bump,
+    )]
+    pub gmtoken_manager_state: Box<Account<'info, GMTokenManagerState>>,
+
+    /// CHECK: Sysvar account for instruction introspection
+    #[account(address = INSTRUCTIONS_ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+impl<'info> FillLimitOrder<'info> {
+    /// Creates a TokenManager instance acting on behalf of the order's user.
+    /// The operator pays for any accounts created during the fill.
+    /// # Returns
+    /// * `TokenManager` - A TokenManager instance with references to the relevant accounts.
+    #[allow(clippy::wrong_self_convention)]
+    pub fn into_token_manager(&mut self) -> TokenManager<'_, 'info> {
+        TokenManager {
+            user: &self.user,
+            payer: &self.operator,
+            mint: &mut self.mint,
+            mint_authority: &self.mint_authority,
+            ondo_user: &mut self.ondo_user,
+            rate_limit_exemption: self.rate_limit_exemption.as_deref(),
+            limit_profile: self.limit_profile.as_deref(),
+            limit_group: self.limit_group.as_deref_mut(),
+            user_notional_limit: self.user_notional_limit.as_deref_mut(),
+            token_limit_account: &mut self.token_limit_account,
+            sanity_check_account: &mut self.sanity_check_account,
+            user_token_account: &mut self.user_token_account,
+            attestation_id_account: &mut self.attestation_id_account,
+            whitelist: &self.whitelist,
+            token_program: &self.token_program,
+            system_program: &self.system_program,
+            associated_token_program: &self.associated_token_program,
+            spl_token_program: self.spl_token_program.as_deref(),
+            usdc_price_update: self.usdc_price_update.as_ref(),
+            usdc_secondary_price_update: self.usdc_secondary_price_update.as_ref(),
+            redemption_queue: self.redemption_queue.as_deref_mut(),
+            usdc_vault: self.usdc_vault.as_deref_mut(),
+            usdc_mint: self.usdc_mint.as_deref(),
+            user_usdc_token_account: self.user_usdc_token_account.as_deref_mut(),
+            // Escrowed flows only settle in USDon or USDC, not in registered settlement assets
+            settlement_asset: None,
+            settlement_token_program: None,
+            settlement_price_update: None,
+            settlement_vault: None,
+            settlement_mint: None,
+            user_settlement_token_account: None,
+            usdon_vault: &mut self.usdon_vault,
+            usdon_mint: &self.usdon_mint,
+            user_usdon_token_account: &mut self.user_usdon_token_account,
+            usdon_manager_state: &mut self.usdon_manager_state,
+            gmtoken_manager_state: &mut self.gmtoken_manager_state,
+            instructions: &self.instructions,
+        }
+    }
+
+    /// Fill the order, in full or in part, against an attestation
+    /// Mints spend from the escrow, redemptions burn escrowed GM Tokens. The order and its
+    /// escrow are closed once the escrow is empty.
+    /// # Arguments
+    /// * `attestation_id` - A unique 16-byte identifier for the attestation
+    /// * `price` - The attested price, must satisfy the order's limit price
+    /// * `amount` - The attested amount of GM Tokens
+    /// * `expiration` - The expiration timestamp of the attestation
+    /// * `bumps` - The PDA bumps for account derivation
+    /// # Returns
+    /// * `Result<u64>` - The conversion rate of the settlement leg, see `TokenManager::conversion_rate`
+    pub fn fill_limit_order(
+        &mut self,
+        attestation_id: [u8; 16],
+        price: u64,
+        amount: u64,
+        expiration: i64,
+        bumps: &FillLimitOrderBumps,
+    ) -> Result<u64> {
+        self.order
+            .validate_fill(price, Clock::get()?.unix_timestamp)?;
+
+        let order = (*self.order).clone().into_inner();
+        let order_id = order.order_id.to_le_bytes();
+        let order_seeds: &[&[u8]] = &[
+            LIMIT_ORDER_SEED,
+            order.user.as_ref(),
+            &order_id,
+            &[order.bump],
+        ];
+        let escrow = self.escrow.to_account_info();
+        let escrow_authority = self.order.to_account_info();
+        let escrow_balance = self.escrow.amount;
+
+        if !order.is_usdon {
+            let usdc_mint = self.usdc_mint.as_ref().ok_or(OndoError::MintNotProvided)?;
+            let usdc_vault = self
+                .usdc_vault
+                .as_ref()
+                .ok_or(OndoError::InvalidTokenAccount)?;
+            require_keys_eq!(
+                usdc_vault.mint,
+                usdc_mint.key(),
+                OndoError::InvalidInputMint
+            );
+        }
+
+        let mut token_manager = self.into_token_manager();
+
+        // Validate token accounts
+        token_manager.validate(order.is_usdon)?;
+
+        // Run the same checks as a direct mint or redemption
+        token_manager.validate_trade(
+            attestation_id,
+            price,
+            amount,
+            expiration,
+            order.is_buy,
+            bumps.ondo_user,
+            bumps.attestation_id_account,
+        )?;
+
+        let conversion_rate = token_manager.conversion_rate(order.is_usdon)?;
+
+        if order.is_buy {
+            token_manager.mint_from_escrow(
+                &escrow,
+                &escrow_authority,
+                &[order_seeds],
+                escrow_balance,
+                price,
+                amount,
+                order.is_usdon,
+                conversion_rate,
+                bumps.mint_authority,
+            )?;
+        } else {
+            require_gte!(escrow_balance, amount, OndoError::InsufficientEscrowBalance);
+
+            token_manager.redeem_from_escrow(
+                &escrow,
+                &escrow_authority,
+                &[order_seeds],
+                price,
+                amount,
+                order.is_usdon,
+                conversion_rate,
+                bumps.mint_authority,
+            )?;
+        }
+
+        self.order.filled_amount = self
+            .order
+            .filled_amount
+            .checked_add(amount)
+            .ok_or(OndoError::MathOverflow)?;
+
+        self.escrow.reload()?;
+        let remaining_deposit = self.escrow.amount;
+
+        emit!(LimitOrderFilled {
+            order: self.order.key(),
+            user: order.user,
+            price,
+            amount,
+            filled_amount: self.order.filled_amount,
+            remaining_deposit,
+            operator: self.operator.key(),
+        });
+
+        // Close the order once nothing is left to fill
+        if remaining_deposit == 0 {
+            let token_program = if order.is_buy && !order.is_usdon {
+                self.spl_token_program
+                    .as_ref()
+                    .ok_or(OndoError::TokenProgramNotProvided)?
+                    .to_account_info()
+            } else {
+                self.token_program.to_account_info()
+            };
+
+            close_account(CpiContext::new_with_signer(
+                token_program,
+                CloseAccount {
+                    account: escrow,
+                    destination: self.user.to_account_info(),
+                    authority: escrow_authority,
+                },
+                &[order_seeds],
+            ))?;
+
+            self.order.close(self.user.to_account_info())?;
+        }
+
+        Ok(conversion_rate)
+    }
+}
@@ -0,0 +1,207 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    constants::{LIMIT_PROFILE_SEED, ONDO_USER_SEED, TOKEN_LIMIT_ACCOUNT_SEED},
+    errors::OndoError,
+    events::{LimitProfileSet, OndoUserLimitProfileSet, TokenDefaultLimitProfileSet},
+    state::{LimitProfile, OndoUser, RoleType, Roles, TokenLimit},
+};
+
+/// Set the limits of a limit profile, creating the profile if needed
+/// Requires `ADMIN_ROLE_GMTOKEN_MANAGER` role
+#[derive(Accounts)]
+#[instruction(profile_id: u64)]
+pub struct SetLimitProfile<'info> {
+    /// The account with the authority to set the limit profile, pays for account creation
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The `Roles` account verifying the authority has the `ADMIN_ROLE_GMTOKEN_MANAGER` role
+    /// # PDA Seeds
+    /// - `ADMIN_ROLE_GMTOKEN_MANAGER`
+    /// - The authority's address
+    #[account(
+        seeds = [RoleType::ADMIN_ROLE_GMTOKEN_MANAGER, authority.key().as_ref()],
+        bump = authority_role_account.bump,
+    )]
+    pub authority_role_account: Account<'info, Roles>,
+
+    /// The `LimitProfile` account
+    /// # PDA Seeds
+    /// - `LIMIT_PROFILE_SEED`
+    /// - The profile ID (little endian)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + LimitProfile::INIT_SPACE,
+        seeds = [LIMIT_PROFILE_SEED, &profile_id.to_le_bytes()],
+        bump,
+    )]
+    pub limit_profile: Account<'info, LimitProfile>,
+
+    /// The system program
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetLimitProfile<'info> {
+    /// Set the limits of the profile, applying to every OndoUser linked to it
+    /// # Arguments
+    /// * `profile_id` - The ID of the profile
+    /// * `rate_limit` - The maximum amount each linked user can mint in the limit window
+    /// * `limit_window` - The mint limit window in seconds (must be > 0)
+    /// * `redeem_rate_limit` - The redeem rate limit, None to use the mint rate limit
+    /// * `redeem_limit_window` - The redeem limit window in seconds (must be > 0), None to use the mint window
+    /// * `bump` - The bump of the `LimitProfile` PDA
+    /// # Returns
+    /// * `Result<()>` - Ok if the limit profile is successfully set, Err otherwise
+    pub fn set_limit_profile(
+        &mut self,
+        profile_id: u64,
+        rate_limit: u64,
+        limit_window: u64,
+        redeem_rate_limit: Option<u64>,
+        redeem_limit_window: Option<u64>,
+        bump: u8,
+    ) -> Result<()> {
+        require_gt!(limit_window, 0, OndoError::InvalidRateLimit);
+        if let Some(window) = redeem_limit_window {
+            require_gt!(window, 0, OndoError::InvalidRateLimit);
+        }
+
+        self.limit_profile.set_inner(LimitProfile {
+            profile_id,
+            rate_limit,
+            limit_window,
+            redeem_rate_limit,
+            redeem_limit_window,
+            bump,
+        });
+
+        emit!(LimitProfileSet {
+            limit_profile: self.limit_profile.key(),
+            profile_id,
+            rate_limit,
+            limit_window,
+            redeem_rate_limit,
+            redeem_limit_window,
+        });
+
+        Ok(())
+    }
+}
+
+/// Link an `OndoUser` to a limit profile, or unlink it
+/// Requires `ADMIN_ROLE_GMTOKEN_MANAGER` role
+#[derive(Accounts)]
+pub struct SetOndoUserLimitProfile<'info> {
+    /// The account with the authority to link the user
+    pub authority: Signer<'info>,
+
+    /// The `Roles` account verifying the authority has the `ADMIN_ROLE_GMTOKEN_MANAGER` role
+    /// # PDA Seeds
+    /// - `ADMIN_ROLE_GMTOKEN_MANAGER`
+    /// - The authority's address
+    #[account(
+        seeds = [RoleType::ADMIN_ROLE_GMTOKEN_MANAGER, authority.key().as_ref()],
+        bump = authority_role_account.bump,
+    )]
+    pub authority_role_account: Account<'info, Roles>,
+
+    /// The GM Token mint
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// The `OndoUser` account to update
+    /// # PDA Seeds
+    /// - `ONDO_USER_SEED`
+    /// - User's owner address
+    /// - Mint address
+    #[account(
+        mut,
+        seeds = [ONDO_USER_SEED, ondo_user.owner.as_ref(), mint.key().as_ref()],
+        bump = ondo_user.bump,
+    )]
+    pub ondo_user: Account<'info, OndoUser>,
+
+    /// The `LimitProfile` to link the user to, None to unlink the user
+    pub limit_profile: Option<Account<'info, LimitProfile>>,
+}
+
+impl<'info> SetOndoUserLimitProfile<'info> {
+    /// Link the user to the provided limit profile, or unlink it if none is provided.
+    /// An unlinked user falls back to its own limits
+    /// # Returns
+    /// * `Result<()>` - Ok if the limit profile is successfully set, Err otherwise
+    pub fn set_ondo_user_limit_profile(&mut self) -> Result<()> {
+        let limit_profile = self.limit_profile.as_ref().map(|profile| profile.key());
+        self.ondo_user.limit_profile = limit_profile;
+
+        // Initialize rate_used fields if not already set
+        if self.ondo_user.mint_capacity_used.is_none() {
+            self.ondo_user.mint_capacity_used = Some(0);
+        }
+        if self.ondo_user.redeem_capacity_used.is_none() {
+            self.ondo_user.redeem_capacity_used = Some(0);
+        }
+
+        emit!(OndoUserLimitProfileSet {
+            user: self.ondo_user.owner,
+            mint: self.mint.key(),
+            limit_profile,
+        });
+
+        Ok(())
+    }
+}
+
+/// Set the limit profile new OndoUser accounts of a GM Token are linked to
+/// Requires `ADMIN_ROLE_GMTOKEN_MANAGER` role
+#[derive(Accounts)]
+pub struct SetTokenDefaultLimitProfile<'info> {
+    /// The account with the authority to set the default limit profile
+    pub authority: Signer<'info>,
+
+    /// The `Roles` account verifying the authority has the `ADMIN_ROLE_GMTOKEN_MANAGER` role
+    /// # PDA Seeds
+    /// - `ADMIN_ROLE_GMTOKEN_MANAGER`
+    /// - The authority's address
+    #[account(
+        seeds = [RoleType::ADMIN_ROLE_GMTOKEN_MANAGER, authority.key().as_ref()],
+        bump = authority_role_account.bump,
+    )]
+    pub authority_role_account: Account<'info, Roles>,
+
+    /// The GM Token mint
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// The `TokenLimit` account to update
+    /// # PDA Seeds
+    /// - `TOKEN_LIMIT_ACCOUNT_SEED`
+    /// - Mint address
+    #[account(
+        mut,
+        seeds = [TOKEN_LIMIT_ACCOUNT_SEED, mint.key().as_ref()],
+        bump = token_limit.bump,
+    )]
+    pub token_limit: Account<'info, TokenLimit>,
+
+    /// The default `LimitProfile`, None to copy the default user limits into new users instead
+    pub limit_profile: Option<Account<'info, LimitProfile>>,
+}
+
+impl<'info> SetTokenDefaultLimitProfile<'info> {
+    /// Set the default limit profile of the token. Existing OndoUser accounts are not affected
+    /// # Returns
+    /// * `Result<()>` - Ok if the default limit profile is successfully set, Err otherwise
+    pub fn set_token_default_limit_profile(&mut self) -> Result<()> {
+        let limit_profile = self.limit_profile.as_ref().map(|profile| profile.key());
+        self.token_limit.default_limit_profile = limit_profile;
+
+        emit!(TokenDefaultLimitProfileSet {
+            token: self.mint.key(),
+            limit_profile,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,735 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions::ID as INSTRUCTIONS_ID;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::Token,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use super::{validate_order_deposit, TokenManager};
+use crate::{
+    constants::{
+        ATTESTATION_ID_SEED, GMTOKEN_MANAGER_STATE_SEED, MINT_AUTHORITY_SEED, MINT_REQUEST_SEED,
+        MINT_REQUEST_TIMEOUT, ONDO_USER_SEED, ORACLE_SANITY_CHECK_SEED, TOKEN_LIMIT_ACCOUNT_SEED,
+        USDON_MANAGER_STATE_SEED, USER_NOTIONAL_LIMIT_SEED, WHITELIST_SEED,
+    },
+    errors::OndoError,
+    events::{MintRequestCreated, MintRequestRefunded, MintRequestSettled},
+    state::{
+        GMTokenManagerState, LimitGroup, LimitProfile, MintRequest, OndoUser, OracleSanityCheck,
+        RoleType, Roles, TokenLimit, USDonManagerState, UserNotionalLimit, Whitelist,
+    },
+    utils::release_escrow,
+};
+
+/// Request a GM Token mint to be settled at the final execution price.
+/// The user's USDon or USDC is locked in a token account owned by the request PDA.
+/// Unpermissioned - the user must be whitelisted.
+#[derive(Accounts)]
+#[instruction(request_id: u64)]
+pub struct CreateMintRequest<'info> {
+    /// The user requesting the mint, pays for account creation
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// The GM Token mint being requested
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The TokenLimit account of the GM Token, checked for pause flags
+    /// # PDA Seeds
+    /// - TOKEN_LIMIT_ACCOUNT_SEED
+    /// - Mint address
+    #[account(
+        seeds = [TOKEN_LIMIT_ACCOUNT_SEED, mint.key().as_ref()],
+        bump = token_limit_account.bump,
+    )]
+    pub token_limit_account: Box<Account<'info, TokenLimit>>,
+
+    /// The Whitelist account verifying the user is authorized
+    /// # PDA Seeds
+    /// - WHITELIST_SEED
+    /// - User's address
+    ///
+    /// CHECK: Seeds constraint validates PDA address.
+    /// Validated in instruction handler - returns UserNotWhitelisted if not initialized.
+    #[account(
+        seeds = [WHITELIST_SEED, user.key().as_ref()],
+        bump,
+    )]
+    pub whitelist: UncheckedAccount<'info>,
+
+    /// The MintRequest account being created
+    /// # PDA Seeds
+    /// - MINT_REQUEST_SEED
+    /// - User's address
+    /// - Request ID (little endian)
+    #[account(
+        init,
+        payer = user,
+        space = 8 + MintRequest::INIT_SPACE,
+        seeds = [MINT_REQUEST_SEED, user.key().as_ref(), request_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub request: Box<Account<'info, MintRequest>>,
+
+    /// The mint of the quote asset being locked, USDon or USDC
+    #[account(mint::token_program = deposit_token_program)]
+    pub deposit_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The user's token account the quote asset is taken from
+    #[account(
+        mut,
+        token::mint = deposit_mint,
+        token::authority = user,
+        token::token_program = deposit_token_program,
+    )]
+    pub user_deposit_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The escrow token account owned by the request
+    #[account(
+        init,
+        payer = user,
+        associated_token::mint = deposit_mint,
+        associated_token::authority = request,
+        associated_token::token_program = deposit_token_program,
+    )]
+    pub escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The USDonManagerState account used to validate the deposit mint
+    /// # PDA Seeds
+    /// - USDON_MANAGER_STATE_SEED
+    #[account(
+        seeds = [USDON_MANAGER_STATE_SEED],
+        bump = usdon_manager_state.bump,
+    )]
+    pub usdon_manager_state: Box<Account<'info, USDonManagerState>>,
+
+    /// The GMTokenManagerState account, checked for pause flags
+    /// # PDA Seeds
+    /// - GMTOKEN_MANAGER_STATE_SEED
+    #[account(
+        seeds = [GMTOKEN_MANAGER_STATE_SEED],
+        bump = gmtoken_manager_state.bump,
+    )]
+    pub gmtoken_manager_state: Box<Account<'info, GMTokenManagerState>>,
+
+    /// The token program of the deposit mint
+    /// Token-2022 for USDon, the legacy SPL Token program for USDC
+    pub deposit_token_program: Interface<'info, TokenInterface>,
+
+    /// The associated token program
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// The system program
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateMintRequest<'info> {
+    /// Create the request and lock the user's quote asset
+    /// # Arguments
+    /// * `request_id` - User supplied identifier for the request
+    /// * `is_usdon` - True to pay in USDon, false to pay in USDC
+    /// * `amount` - The amount of the quote asset to lock
+    /// * `max_price` - The highest execution price the user accepts
+    /// * `bump` - The bump of the request PDA
+    /// # Returns
+    /// * `Result<()>` - Ok if the request is created, Err otherwise
+    pub fn create_mint_request(
+        &mut self,
+        request_id: u64,
+        is_usdon: bool,
+        amount: u64,
+        max_price: u64,
+        bump: u8,
+    ) -> Result<()> {
+        require_gt!(amount, 0, OndoError::InvalidAmount);
+        require_gt!(max_price, 0, OndoError::InvalidPrice);
+
+        Whitelist::verify(&self.whitelist)?;
+
+        validate_order_deposit(
+            &self.gmtoken_manager_state,
+            &self.token_limit_account,
+            &self.usdon_manager_state,
+            &self.mint.key(),
+            &self.deposit_mint.key(),
+            &self.deposit_token_program.key(),
+            true,
+            is_usdon,
+        )?;
+
+        // Lock the quote asset in the request's escrow
+        transfer_checked(
+            CpiContext::new(
+                self.deposit_token_program.to_account_info(),
+                TransferChecked {
+                    from: self.user_deposit_token_account.to_account_info(),
+                    mint: self.deposit_mint.to_account_info(),
+                    to: self.escrow.to_account_info(),
+                    authority: self.user.to_account_info(),
+                },
+            ),
+            amount,
+            self.deposit_mint.decimals,
+        )?;
+
+        // The quantity is fixed now, settlement refunds what a lower price leaves over
+        let requested_amount = MintRequest::requested_amount(
+            amount,
+            max_price,
+            self.deposit_mint.decimals,
+            self.mint.decimals,
+        )?;
+        require_gt!(requested_amount, 0, OndoError::InvalidAmount);
+
+        let current_timestamp = Clock::get()?.unix_timestamp;
+        let expires_at = current_timestamp
+            .checked_add(MINT_REQUEST_TIMEOUT)
+            .ok_or(OndoError::MathOverflow)?;
+
+        self.request.set_inner(MintRequest {
+            user: self.user.key(),
+            request_id,
+            mint: self.mint.key(),
+            deposit_mint: self.deposit_mint.key(),
+            escrow: self.escrow.key(),
+            is_usdon,
+            deposit_amount: amount,
+            max_price,
+            amount: requested_amount,
+            created_at: current_timestamp,
+            expires_at,
+            bump,
+        });
+
+        emit!(MintRequestCreated {
+            request: self.request.key(),
+            user: self.user.key(),
+            mint: self.mint.key(),
+            is_usdon,
+            deposit_amount: amount,
+            max_price,
+            amount: requested_amount,
+            expires_at,
+        });
+
+        Ok(())
+    }
+}
+
+/// Refund a mint request that was not settled before it timed out.
+/// Unpermissioned - the locked funds always go back to the request's user.
+#[derive(Accounts)]
+pub struct RefundMintRequest<'info> {
+    /// The account closing the request
+    pub caller: Signer<'info>,
+
+    /// The user who requested the mint, receives the refund and the rent of the closed accounts
+    /// CHECK: Address constraint validates it is the request's user.
+    #[account(mut, address = request.user)]
+    pub user: UncheckedAccount<'info>,
+
+    /// The MintRequest account being refunded
+    /// # PDA Seeds
+    /// - MINT_REQUEST_SEED
+    /// - User's address
+    /// - Request ID (little endian)
+    #[account(
+        mut,
+        close = user,
+        has_one = deposit_mint,
+        has_one = escrow,
+        seeds = [MINT_REQUEST_SEED, request.user.as_ref(), request.request_id.to_le_bytes().as_ref()],
+        bump = request.bump,
+    )]
+    pub request: Box<Account<'info, MintRequest>>,
+
+    /// The escrow token account owned by the request
+    #[account(mut)]
+    pub escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The mint of the locked quote asset
+    #[account(mint::token_program = deposit_token_program)]
+    pub deposit_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The user's token account receiving the refund
+    #[account(
+        mut,
+        token::mint = deposit_mint,
+        token::authority = user,
+        token::token_program = deposit_token_program,
+    )]
+    pub user_deposit_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The token program of the deposit mint
+    pub deposit_token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> RefundMintRequest<'info> {
+    /// Refund the locked quote asset once the request has timed out
+    /// # Returns
+    /// * `Result<()>` - Ok if the request is refunded, Err otherwise
+    pub fn refund_mint_request(&mut self) -> Result<()> {
+        self.request.validate_refund(Clock::get()?.unix_timestamp)?;
+
+        let request_id = self.request.request_id.to_le_bytes();
+        let user = self.user.key();
+        let request_seeds: &[&[u8]] = &[
+            MINT_REQUEST_SEED,
+            user.as_ref(),
+            &request_id,
+            &[self.request.bump],
+        ];
+
+        let refunded = release_escrow(
+            &self.deposit_token_program.to_account_info(),
+            &self.escrow.to_account_info(),
+            &self.deposit_mint.to_account_info(),
+            self.deposit_mint.decimals,
+            &self.user_deposit_token_account.to_account_info(),
+            &self.user.to_account_info(),
+            &self.request.to_account_info(),
+            &[request_seeds],
+        )?;
+
+        emit!(MintRequestRefunded {
+            request: self.request.key(),
+            user,
+            refunded,
+            closed_by: self.caller.key(),
+        });
+
+        Ok(())
+    }
+}
+
+/// Settle a mint request at the final execution price.
+/// Runs the same checks as a direct mint on behalf of the request's user.
+/// Requires `ORDER_OPERATOR_ROLE` role.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(attestation_id: [u8; 16])]
+pub struct SettleMintRequest<'info> {
+    /// The operator settling the request, pays for account creation if needed
+    #[account(mut)]
+    pub operator: Signer<'info>,
+
+    /// The `Roles` account verifying the operator has the `ORDER_OPERATOR_ROLE` role
+    /// # PDA Seeds
+    /// - ORDER_OPERATOR_ROLE
+    /// - The operator's address
+    #[account(
+        seeds = [RoleType::ORDER_OPERATOR_ROLE, operator.key().as_ref()],
+        bump = operator_role_account.bump,
+    )]
+    pub operator_role_account: Box<Account<'info, Roles>>,
+
+    /// The user who requested the mint, receives the rent of the closed accounts
+    /// CHECK: Address constraint validates it is the request's user.
+    #[account(mut, address = request.user)]
+    pub user: UncheckedAccount<'info>,
+
+    /// The MintRequest account being settled
+    /// # PDA Seeds
+    /// - MINT_REQUEST_SEED
+    /// - User's address
+    /// - Request ID (little endian)
+    #[account(
+        mut,
+        close = user,
+        has_one = mint,
+        has_one = escrow,
+        seeds = [MINT_REQUEST_SEED, request.user.as_ref(), request.request_id.to_le_bytes().as_ref()],
+        bump = request.bump,
+    )]
+    pub request: Box<Account<'info, MintRequest>>,
+
+    /// The escrow token account owned by the request
+    #[account(mut, token::authority = request)]
+    pub escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The GM Token mint being minted
+    #[account(
+        mut,
+        mint::authority = mint_authority,
+        mint::token_program = token_program
+    )]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The mint authority PDA
+    /// # PDA Seeds
+    /// - MINT_AUTHORITY_SEED
+    /// CHECK: This account is used to verify the mint authority.
+    /// Does not need to be checked for correctness as it is uninitialized.
+    #[account(
+        seeds = [MINT_AUTHORITY_SEED],
+        bump,
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    /// The OndoUser account tracking user-specific state for this mint
+    /// # PDA Seeds
+    /// - ONDO_USER_SEED
+    /// - User's address
+    /// - Mint address
+    #[account(
+        init_if_needed,
+        payer = operator,
+        space = 8 + OndoUser::INIT_SPACE,
+        seeds = [ONDO_USER_SEED, user.key().as_ref(), mint.key().as_ref()],
+        bump,
+    )]
+    pub ondo_user: Box<Account<'info, OndoUser>>,
+
+    /// The user's `RateLimitExemptRole` account, skipping the per-user rate limit
+    /// Only provided for designated market makers
+    /// # PDA Seeds
+    /// - RATE_LIMIT_EXEMPT_ROLE
+    /// - User's address
+    #[account(
+        seeds = [RoleType::RATE_LIMIT_EXEMPT_ROLE, user.key().as_ref()],
+        bump = rate_limit_exemption.bump,
+    )]
+    pub rate_limit_exemption: Option<Box<Account<'info, Roles>>>,
+
+    /// The LimitProfile the OndoUser is linked to
+    /// Required when the OndoUser is linked to a limit profile
+    pub limit_profile: Option<Box<Account<'info, LimitProfile>>>,
+
+    /// The limit group the OndoUser is attached to
+    /// Required when the OndoUser is attached to a limit group
+    #[account(mut)]
+    pub limit_group: Option<Box<Account<'info, LimitGroup>>>,
+
+    /// The user's notional across all GM Tokens
+    /// Required while the per-user notional limit is enabled
+    /// # PDA Seeds
+    /// - USER_NOTIONAL_LIMIT_SEED
+    /// - User's address
+    #[account(
+        mut,
+        seeds = [USER_NOTIONAL_LIMIT_SEED, user.key().as_ref()],
+        bump = user_notional_limit.bump,
+    )]
+    pub user_notional_limit: Option<Box<Account<'info, UserNotionalLimit>>>,
+
+    /// The TokenLimit account enforcing mint/burn limits for the GM Token
+    /// # PDA Seeds
+    /// - TOKEN_LIMIT_ACCOUNT_SEED
+    /// - Mint address
+    #[account(
+        mut,
+        seeds = [TOKEN_LIMIT_ACCOUNT_SEED, mint.key().as_ref()],
+        bump = token_limit_account.bump,
+    )]
+    pub token_limit_account: Box<Account<'info, TokenLimit>>,
+
+    /// The OracleSanityCheck account validating oracle price updates
+    /// # PDA Seeds
+    /// - ORACLE_SANITY_CHECK_SEED
+    /// - Mint address
+    #[account(
+        mut,
+        seeds = [ORACLE_SANITY_CHECK_SEED, mint.key().as_ref()],
+        bump = sanity_check_account.bump,
+    )]
+    pub sanity_check_account: Box<Account<'info, OracleSanityCheck>>,
+
+    /// The user's associated token account for the GM Token
+    #[account(
+        init_if_needed,
+        payer = operator,
+        associated_token::mint = mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    pub user_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The attestation ID account preventing attestation reuse
+    /// # PDA Seeds
+    /// - ATTESTATION_ID_SEED
+    /// - Attestation ID (16-byte array)
+    /// CHECK: Seeds constraint validates PDA address.
+    /// Existence means the attestation has been used.
+    #[account(
+        mut,
+        seeds = [ATTESTATION_ID_SEED, attestation_id.as_ref()],
+        bump,
+    )]
+    pub attestation_id_account: UncheckedAccount<'info>,
+
+    /// The Whitelist account verifying the user is authorized
+    /// # PDA Seeds
+    /// - WHITELIST_SEED
+    /// - User's address
+    /// CHECK: Seeds constraint validates PDA address.
+    /// Validated in instruction handler - returns UserNotWhitelisted if not initialized.
+    #[account(
+        seeds = [WHITELIST_SEED, user.key().as_ref()],
+        bump,
+    )]
+    pub whitelist: UncheckedAccount<'info>,
+
+    /// The token program (Token-2022)
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The system program
+    pub system_program: Program<'info, System>,
+
+    /// The associated token program
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// The legacy SPL Token program
+    /// Required for requests paid in USDC
+    pub spl_token_program: Option<Program<'info, Token>>,
+
+    /// The oracle price update account for USDC/USD price feed
+    /// Required for requests paid in USDC
+    /// CHECK: Address constraint against the account stored in `usdon_manager_state`.
+    #[account(address = usdon_manager_state.usdc_price_update)]
+    pub usdc_price_update: Option<UncheckedAccount<'info>>,
+
+    /// The secondary oracle price update account for USDC/USD price feed
+    /// Required for orders settled in USDC when a secondary oracle is configured
+    /// CHECK: Address constraint against the account stored in `usdon_manager_state`.
+    #[account(address = usdon_manager_state.usdc_secondary_price_update)]
+    pub usdc_secondary_price_update: Option<UncheckedAccount<'info>>,
+
+    /// The USDC vault storing USDC tokens
+    /// Required for requests paid in USDC
+    #[account(mut, address = usdon_manager_state.usdc_vault)]
+    pub usdc_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// The USDon vault storing USDon tokens
+    #[account(
+        mut,
+        associated_token::mint = usdon_mint,
+        associated_token::authority = usdon_manager_state,
+        associated_token::token_program = token_program,
+        constraint = usdon_vault.key() == usdon_manager_state.usdon_vault
+    )]
+    pub usdon_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The USDC mint (SPL Token)
+    /// Required for requests paid in USDC
+    pub usdc_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    /// The user's USDC token account
+    /// Required for requests paid in USDC
+    #[account(mut)]
+    pub user_usdc_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// The USDon mint (Token-2022)
+    #[account(
+        mut,
+        mint::token_program = token_program,
+        constraint = usdon_mint.key() == usdon_manager_state.usdon_mint
+    )]
+    pub usdon_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The user's associated token account for USDon
+    #[account(
+        init_if_needed,
+        payer = operator,
+        associated_token::mint = usdon_mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    pub user_usdon_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The USDonManagerState account used as authority for vault operations
+    /// Tracks the USDC reserved for queued redemption claims
+    /// # PDA Seeds
+    /// - USDON_MANAGER_STATE_SEED
+    #[account(
+        mut,
+        seeds = [USDON_MANAGER_STATE_SEED],
+        bump = usdon_manager_state.bump,
+    )]
+    pub usdon_manager_state: Box<Account<'info, USDonManagerState>>,
+
+    /// The GmTokenManagerState account managing GM Token operations
+    /// # PDA Seeds
+    /// - GMTOKEN_MANAGER_STATE_SEED
+    #[account(
+        mut,
+        seeds = [GMTOKEN_MANAGER_STATE_SEED],
+        bump = gmtoken_manager_state.bump,
+    )]
+    pub gmtoken_manager_state: Box<Account<'info, GMTokenManagerState>>,
+
+    /// CHECK: Sysvar account for instruction introspection
+    #[account(address = INSTRUCTIONS_ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+impl<'info> SettleMintRequest<'info> {
+    /// Creates a TokenManager instance acting on behalf of the request's user.
+    /// The operator pays for any accounts created during settlement.
+    /// # Returns
+    /// * `TokenManager` - A TokenManager instance with references to the relevant accounts.
+    #[allow(clippy::wrong_self_convention)]
+    pub fn into_token_manager(&mut self) -> TokenManager<'_, 'info> {
+        TokenManager {
+            user: &self.user,
+            payer: &self.operator,
+            mint: &mut self.mint,
+            mint_authority: &self.mint_authority,
+            ondo_user: &mut self.ondo_user,
+            rate_limit_exemption: self.rate_limit_exemption.as_deref(),
+            limit_profile: self.limit_profile.as_deref(),
+            limit_group: self.limit_group.as_deref_mut(),
+            user_notional_limit: self.user_notional_limit.as_deref_mut(),
+            token_limit_account: &mut self.token_limit_account,
+            sanity_check_account: &mut self.sanity_check_account,
+            user_token_account: &mut self.user_token_account,
+            attestation_id_account: &mut self.attestation_id_account,
+            whitelist: &self.whitelist,
+            token_program: &self.token_program,
+            system_program: &self.system_program,
+            associated_token_program: &self.associated_token_program,
+            spl_token_program: self.spl_token_program.as_deref(),
+            usdc_price_update: self.usdc_price_update.as_ref(),
+            usdc_secondary_price_update: self.usdc_secondary_price_update.as_ref(),
+            redemption_queue: None,
+            usdc_vault: self.usdc_vault.as_deref_mut(),
+            usdc_mint: self.usdc_mint.as_deref(),
+            user_usdc_token_account: self.user_usdc_token_account.as_deref_mut(),
+            // Escrowed flows only settle in USDon or USDC, not in registered settlement assets
+            settlement_asset: None,
+            settlement_token_program: None,
+            settlement_price_update: None,
+            settlement_vault: None,
+            settlement_mint: None,
+            user_settlement_token_account: None,
+            usdon_vault: &mut self.usdon_vault,
+            usdon_mint: &self.usdon_mint,
+            user_usdon_token_account: &mut self.user_usdon_token_account,
+            usdon_manager_state: &mut self.usdon_manager_state,
+            gmtoken_manager_state: &mut self.gmtoken_manager_state,
+            instructions: &self.instructions,
+        }
+    }
+
+    /// Settle the request against an attestation of the final execution price
+    /// The cost is taken from the locked funds and the difference is refunded.
+    /// # Arguments
+    /// * `attestation_id` - A unique 16-byte identifier for the attestation
+    /// * `price` - The attested execution price
+    /// * `amount` - The attested amount of GM Tokens, the amount requested
+    /// * `expiration` - The expiration timestamp of the attestation
+    /// * `bumps` - The PDA bumps for account derivation
+    /// # Returns
+    /// * `Result<u64>` - The conversion rate of the settlement leg, see `TokenManager::conversion_rate`
+    pub fn settle_mint_request(
+        &mut self,
+        attestation_id: [u8; 16],
+        price: u64,
+        amount: u64,
+        expiration: i64,
+        bumps: &SettleMintRequestBumps,
+    ) -> Result<u64> {
+        let request = (*self.request).clone().into_inner();
+
+        request.validate_settlement(price, amount, Clock::get()?.unix_timestamp)?;
+
+        let request_id = request.request_id.to_le_bytes();
+        let request_seeds: &[&[u8]] = &[
+            MINT_REQUEST_SEED,
+            request.user.as_ref(),
+            &request_id,
+            &[request.bump],
+        ];
+        let escrow = self.escrow.to_account_info();
+        let escrow_authority = self.request.to_account_info();
+
+        if !request.is_usdon {
+            let usdc_mint = self.usdc_mint.as_ref().ok_or(OndoError::MintNotProvided)?;
+            let usdc_vault = self
+                .usdc_vault
+                .as_ref()
+                .ok_or(OndoError::InvalidTokenAccount)?;
+            require_keys_eq!(
+                usdc_vault.mint,
+                usdc_mint.key(),
+                OndoError::InvalidInputMint
+            );
+        }
+
+        let mut token_manager = self.into_token_manager();
+
+        // Validate token accounts
+        token_manager.validate(request.is_usdon)?;
+
+        // Run the same checks as a direct mint
+        token_manager.validate_trade(
+            attestation_id,
+            price,
+            amount,
+            expiration,
+            true,
+            bumps.ondo_user,
+            bumps.attestation_id_account,
+        )?;
+
+        let conversion_rate = token_manager.conversion_rate(request.is_usdon)?;
+
+        token_manager.mint_from_escrow(
+            &escrow,
+            &escrow_authority,
+            &[request_seeds],
+            request.deposit_amount,
+            price,
+            amount,
+            request.is_usdon,
+            conversion_rate,
+            bumps.mint_authority,
+        )?;
+
+        // Refund the difference between the locked amount and the cost
+        let (token_program, deposit_mint, decimals, destination) = if request.is_usdon {
+            (
+                self.token_program.to_account_info(),
+                self.usdon_mint.to_account_info(),
+                self.usdon_mint.decimals,
+                self.user_usdon_token_account.to_account_info(),
+            )
+        } else {
+            let usdc_mint = self.usdc_mint.as_ref().ok_or(OndoError::MintNotProvided)?;
+            (
+                self.spl_token_program
+                    .as_ref()
+                    .ok_or(OndoError::TokenProgramNotProvided)?
+                    .to_account_info(),
+                usdc_mint.to_account_info(),
+                usdc_mint.decimals,
+                self.user_usdc_token_account
+                    .as_ref()
+                    .ok_or(OndoError::InvalidTokenAccount)?
+                    .to_account_info(),
+            )
+        };
+
+        let refunded = release_escrow(
+            &token_program,
+            &escrow,
+            &deposit_mint,
+            decimals,
+            &destination,
+            &self.user.to_account_info(),
+            &escrow_authority,
+            &[request_seeds],
+        )?;
+
+        emit!(MintRequestSettled {
+            request: self.request.key(),
+            user: request.user,
+            price,
+            amount,
+            refunded,
+            operator: self.operator.key(),
+        });
+
+        Ok(conversion_rate)
+    }
+}
@@ -3,17 +3,31 @@ pub mod gm_token_admin_operations;
 pub mod gm_token_factory_admin_operations;
 pub mod gm_token_manager_admin_operations;
 pub mod initialize_user;
+pub mod limit_group_operations;
+pub mod limit_order_operations;
+pub mod limit_profile_operations;
+pub mod mint_request_operations;
+pub mod order_operations;
+pub mod preview_operations;
+pub mod psm_operations;
+pub mod pushed_price_operations;
+pub mod recurring_order_operations;
+pub mod redemption_queue_operations;
+pub mod retrieval_operations;
 pub mod role_operations;
 pub mod sanity_checker_admin_operations;
+pub mod settlement_asset_admin_operations;
+pub mod settlement_swap_context;
 pub mod token_factory;
 pub mod token_limit_admin_operations;
 pub mod token_manager;
 pub mod update_scaled_ui_multiplier;
 pub mod update_token_metadata;
-pub mod usdc_swap_context;
 pub mod usdon_admin_operations;
 pub mod usdon_manager_admin_operations;
-pub mod usdon_swap_context;
+pub mod usdon_minter_operations;
+pub mod usdon_reserves_operations;
+pub mod user_notional_limit_operations;
 pub mod whitelist_operations;
 
 pub use close_attestation_account::*;
@@ -21,15 +35,29 @@ pub use gm_token_admin_operations::*;
 pub use gm_token_factory_admin_operations::*;
 pub use gm_token_manager_admin_operations::*;
 pub use initialize_user::*;
+pub use limit_group_operations::*;
+pub use limit_order_operations::*;
+pub use limit_profile_operations::*;
+pub use mint_request_operations::*;
+pub use order_operations::*;
+pub use preview_operations::*;
+pub use psm_operations::*;
+pub use pushed_price_operations::*;
+pub use recurring_order_operations::*;
+pub use redemption_queue_operations::*;
+pub use retrieval_operations::*;
 pub use role_operations::*;
 pub use sanity_checker_admin_operations::*;
+pub use settlement_asset_admin_operations::*;
+pub use settlement_swap_context::*;
 pub use token_factory::*;
 pub use token_limit_admin_operations::*;
 pub use token_manager::*;
 pub use update_scaled_ui_multiplier::*;
 pub use update_token_metadata::*;
-pub use usdc_swap_context::*;
 pub use usdon_admin_operations::*;
 pub use usdon_manager_admin_operations::*;
-pub use usdon_swap_context::*;
+pub use usdon_minter_operations::*;
+pub use usdon_reserves_operations::*;
+pub use user_notional_limit_operations::*;
 pub use whitelist_operations::*;
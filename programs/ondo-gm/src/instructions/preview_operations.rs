@@ -0,0 +1,358 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::get_associated_token_address_with_program_id,
+    token::ID as SPL_TOKEN_PROGRAM_ID, token_interface::Mint,
+};
+
+use crate::{
+    constants::{
+        GMTOKEN_MANAGER_STATE_SEED, ONDO_USER_SEED, ORACLE_SANITY_CHECK_SEED, PRICE_SCALING_FACTOR,
+        TOKEN_LIMIT_ACCOUNT_SEED, USDON_MANAGER_STATE_SEED, USER_NOTIONAL_LIMIT_SEED,
+    },
+    errors::OndoError,
+    state::{
+        GMTokenManagerState, LimitGroup, LimitProfile, OndoUser, OracleSanityCheck, RoleType,
+        Roles, TokenLimit, USDonManagerState, UserNotionalLimit,
+    },
+    utils::{mul_div, normalize_decimals, usd_to_asset_amount, usdc_conversion_rate},
+};
+
+/// Result of `preview_trade`, returned through the transaction return data
+#[derive(Clone, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct TradePreview {
+    /// Whether the market is open at the current timestamp
+    pub market_open: bool,
+    /// The lowest price accepted by the sanity check
+    pub sanity_min_price: u64,
+    /// The highest price accepted by the sanity check
+    pub sanity_max_price: u64,
+    /// Whether the last sanity check price is recent enough for trades to pass
+    pub sanity_price_fresh: bool,
+    /// The amount charged to the rate limits for the trade
+    pub rate_limit_amount: u64,
+    /// The largest amount the token-level limits accept, None if the limit is not configured
+    pub token_capacity_available: Option<u64>,
+    /// The largest amount the user-level limit accepts, None if the limit is not configured
+    /// or the user is exempt. Uses the token's default profile or default limits when the user
+    /// has no OndoUser account yet
+    pub user_capacity_available: Option<u64>,
+    /// Whether the user holds the `RateLimitExemptRole` and skips the user-level limit
+    pub rate_limit_exempt: bool,
+    /// The largest amount the user's limit group accepts, None if the user is not in a group
+    pub limit_group_capacity_available: Option<u64>,
+    /// The largest amount the global notional limit accepts, None if it is disabled
+    pub notional_capacity_available: Option<u64>,
+    /// The largest amount the per-user notional limit accepts, None if it is disabled.
+    /// Starts with no usage when the user has no UserNotionalLimit account yet
+    pub user_notional_capacity_available: Option<u64>,
+    /// The largest amount all the rate limits above accept together, 0 if the trade would fail
+    /// because a required limit is not configured
+    pub capacity_available: u64,
+    /// The USDon paid for a mint, or received for a redemption
+    pub usdon_amount: u64,
+    /// The USDC price used for USDC settlement, None if it cannot be read
+    pub usdc_conversion_rate: Option<u64>,
+    /// The USDC paid for a mint, or owed for a redemption, None if the USDC price cannot be read
+    pub usdc_amount: Option<u64>,
+}
+
+/// Preview a mint or redemption without changing any state
+/// Unpermissioned
+#[derive(Accounts)]
+pub struct PreviewTrade<'info> {
+    /// The user the trade would be executed for
+    /// CHECK: Only used to derive the `OndoUser` address
+    pub user: UncheckedAccount<'info>,
+
+    /// The GM Token mint
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The user's OndoUser account, if it exists
+    /// # PDA Seeds
+    /// - ONDO_USER_SEED
+    /// - User's address
+    /// - Mint address
+    #[account(
+        seeds = [ONDO_USER_SEED, user.key().as_ref(), mint.key().as_ref()],
+        bump = ondo_user.bump,
+    )]
+    pub ondo_user: Option<Box<Account<'info, OndoUser>>>,
+
+    /// The user's `RateLimitExemptRole` account, if the user is exempt
+    /// # PDA Seeds
+    /// - RATE_LIMIT_EXEMPT_ROLE
+    /// - User's address
+    #[account(
+        seeds = [RoleType::RATE_LIMIT_EXEMPT_ROLE, user.key().as_ref()],
+        bump = rate_limit_exemption.bump,
+    )]
+    pub rate_limit_exemption: Option<Box<Account<'info, Roles>>>,
+
+    /// The LimitProfile the user is linked to, or the token's default profile for a new user
+    /// Required when the user is, or would be, linked to a limit profile
+    pub limit_profile: Option<Box<Account<'info, LimitProfile>>>,
+
+    /// The limit group the OndoUser is attached to
+    /// Required when the OndoUser is attached to a limit group
+    pub limit_group: Option<Box<Account<'info, LimitGroup>>>,
+
+    /// The user's notional across all GM Tokens, if it exists
+    /// # PDA Seeds
+    /// - USER_NOTIONAL_LIMIT_SEED
+    /// - User's address
+    #[account(
+        seeds = [USER_NOTIONAL_LIMIT_SEED, user.key().as_ref()],
+        bump = user_notional_limit.bump,
+    )]
+    pub user_notional_limit: Option<Box<Account<'info, UserNotionalLimit>>>,
+
+    /// The TokenLimit account of the GM Token
+    /// # PDA Seeds
+    /// - TOKEN_LIMIT_ACCOUNT_SEED
+    /// - Mint address
+    #[account(
+        seeds = [TOKEN_LIMIT_ACCOUNT_SEED, mint.key().as_ref()],
+        bump = token_limit_account.bump,
+    )]
+    pub token_limit_account: Box<Account<'info, TokenLimit>>,
+
+    /// The OracleSanityCheck account of the GM Token
+    /// # PDA Seeds
+    /// - ORACLE_SANITY_CHECK_SEED
+    /// - Mint address
+    #[account(
+        seeds = [ORACLE_SANITY_CHECK_SEED, mint.key().as_ref()],
+        bump = sanity_check_account.bump,
+    )]
+    pub sanity_check_account: Box<Account<'info, OracleSanityCheck>>,
+
+    /// The GMTokenManagerState account
+    /// # PDA Seeds
+    /// - GMTOKEN_MANAGER_STATE_SEED
+    #[account(
+        seeds = [GMTOKEN_MANAGER_STATE_SEED],
+        bump = gmtoken_manager_state.bump,
+    )]
+    pub gmtoken_manager_state: Box<Account<'info, GMTokenManagerState>>,
+
+    /// The USDonManagerState account
+    /// # PDA Seeds
+    /// - USDON_MANAGER_STATE_SEED
+    #[account(
+        seeds = [USDON_MANAGER_STATE_SEED],
+        bump = usdon_manager_state.bump,
+    )]
+    pub usdon_manager_state: Box<Account<'info, USDonManagerState>>,
+
+    /// The USDon mint
+    #[account(address = usdon_manager_state.usdon_mint)]
+    pub usdon_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The USDC mint, required for the USDC amount
+    /// Must be the mint of the USDC vault, the USDonManagerState's associated token account for USDC
+    #[account(
+        constraint = get_associated_token_address_with_program_id(
+            &usdon_manager_state.key(),
+            &usdc_mint.key(),
+            &SPL_TOKEN_PROGRAM_ID,
+        ) == usdon_manager_state.usdc_vault @ OndoError::InvalidInputMint
+    )]
+    pub usdc_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    /// The oracle price update account for the USDC/USD price feed
+    /// CHECK: Address constraint against the account stored in `usdon_manager_state`.
+    #[account(address = usdon_manager_state.usdc_price_update)]
+    pub usdc_price_update: Option<UncheckedAccount<'info>>,
+
+    /// The secondary oracle price update account for the USDC/USD price feed
+    /// CHECK: Address constraint against the account stored in `usdon_manager_state`.
+    #[account(address = usdon_manager_state.usdc_secondary_price_update)]
+    pub usdc_secondary_price_update: Option<UncheckedAccount<'info>>,
+}
+
+impl<'info> PreviewTrade<'info> {
+    /// Compute what a mint or redemption at `price` would be charged and paid,
+    /// using the same rounding as the trade instructions
+    /// # Arguments
+    /// * `price` - The GM Token price
+    /// * `amount` - The amount of GM Tokens to mint or redeem
+    /// * `is_buy` - Whether the trade is a mint (true) or a redemption (false)
+    /// # Returns
+    /// * `Result<TradePreview>` - The preview of the trade
+    pub fn preview_trade(&self, price: u64, amount: u64, is_buy: bool) -> Result<TradePreview> {
+        let current_timestamp = Clock::get()?.unix_timestamp;
+
+        let (sanity_min_price, sanity_max_price) = self.sanity_check_account.price_band()?;
+        let sanity_price_fresh = current_timestamp
+            .saturating_sub(self.sanity_check_account.price_last_updated)
+            <= self.sanity_check_account.max_time_delay;
+
+        // Round up: Conservative - counts more toward the rate limit
+        let rate_limit_amount = mul_div(price, amount, PRICE_SCALING_FACTOR as u64, true)?;
+
+        let token_capacity_available = self
+            .token_limit_account
+            .available_capacity(is_buy, current_timestamp)?;
+        let rate_limit_exempt = self.rate_limit_exemption.is_some();
+        let user_capacity_available = match self.ondo_user.as_ref() {
+            _ if rate_limit_exempt => None,
+            Some(ondo_user) => ondo_user.available_capacity(
+                is_buy,
+                current_timestamp,
+                self.limit_profile.as_deref(),
+            )?,
+            // A new OndoUser is linked to the token's default profile and has no capacity used
+            None if self.token_limit_account.default_limit_profile.is_some() => {
+                let limit_profile = self
+                    .limit_profile
+                    .as_ref()
+                    .ok_or(OndoError::LimitProfileNotProvided)?;
+                require!(
+                    self.token_limit_account.default_limit_profile == Some(limit_profile.key()),
+                    OndoError::InvalidLimitProfile
+                );
+                Some(limit_profile.limit(is_buy).0)
+            }
+            // Otherwise it starts with the token defaults and no capacity used
+            None => {
+                let token_limit = &self.token_limit_account;
+                let (rate_limit, limit_window) = if is_buy {
+                    (
+                        token_limit.default_user_rate_limit,
+                        token_limit.default_user_limit_window,
+                    )
+                } else {
+                    (
+                        token_limit
+                            .default_user_redeem_rate_limit
+                            .or(token_limit.default_user_rate_limit),
+                        token_limit
+                            .default_user_redeem_limit_window
+                            .or(token_limit.default_user_limit_window),
+                    )
+                };
+                rate_limit
+                    .zip(limit_window)
+                    .map(|(rate_limit, _)| rate_limit)
+            }
+        };
+        let limit_group_capacity_available =
+            self.limit_group_capacity_available(current_timestamp, is_buy)?;
+        let notional_capacity_available = self
+            .gmtoken_manager_state
+            .available_notional(current_timestamp, is_buy)?;
+        let user_notional_capacity_available =
+            self.user_notional_capacity_available(current_timestamp, is_buy)?;
+
+        // Limits that are not configured fail the trade, optional limits that are disabled do not apply
+        let capacity_available = [
+            Some(token_capacity_available.unwrap_or(0)),
+            (!rate_limit_exempt).then(|| user_capacity_available.unwrap_or(0)),
+            limit_group_capacity_available,
+            notional_capacity_available,
+            user_notional_capacity_available,
+        ]
+        .into_iter()
+        .flatten()
+        .min()
+        .unwrap_or(0);
+
+        // Round up for mints and down for redemptions: Favours the protocol
+        let usdon_amount = mul_div(price, amount, PRICE_SCALING_FACTOR as u64, is_buy)?;
+
+        let usdc_conversion_rate = usdc_conversion_rate(
+            &self.usdon_manager_state,
+            self.usdc_price_update.as_ref().map(|a| a.as_ref()),
+            self.usdc_secondary_price_update
+                .as_ref()
+                .map(|a| a.as_ref()),
+        )
+        .ok();
+        let usdc_amount = match (self.usdc_mint.as_ref(), usdc_conversion_rate) {
+            (Some(usdc_mint), Some(conversion_rate)) => {
+                let usd_amount = if is_buy {
+                    normalize_decimals(usdon_amount, self.mint.decimals, usdc_mint.decimals, true)?
+                } else {
+                    normalize_decimals(
+                        usdon_amount,
+                        self.usdon_mint.decimals,
+                        usdc_mint.decimals,
+                        false,
+                    )?
+                };
+                Some(usd_to_asset_amount(usd_amount, conversion_rate, is_buy)?)
+            }
+            _ => None,
+        };
+
+        Ok(TradePreview {
+            market_open: self.gmtoken_manager_state.is_market_open(current_timestamp),
+            sanity_min_price,
+            sanity_max_price,
+            sanity_price_fresh,
+            rate_limit_amount,
+            token_capacity_available,
+            user_capacity_available,
+            rate_limit_exempt,
+            limit_group_capacity_available,
+            notional_capacity_available,
+            user_notional_capacity_available,
+            capacity_available,
+            usdon_amount,
+            usdc_conversion_rate,
+            usdc_amount,
+        })
+    }
+
+    /// Returns the capacity of the limit group the user is attached to, None if there is none
+    fn limit_group_capacity_available(
+        &self,
+        current_timestamp: i64,
+        is_buy: bool,
+    ) -> Result<Option<u64>> {
+        let Some(limit_group_key) = self.ondo_user.as_ref().and_then(|user| user.limit_group)
+        else {
+            return Ok(None);
+        };
+
+        let limit_group = self
+            .limit_group
+            .as_ref()
+            .ok_or(OndoError::LimitGroupNotProvided)?;
+        require_keys_eq!(
+            limit_group.key(),
+            limit_group_key,
+            OndoError::InvalidLimitGroup
+        );
+
+        limit_group
+            .available_capacity(current_timestamp, is_buy)
+            .map(Some)
+    }
+
+    /// Returns the capacity of the per-user notional limit, None if it is disabled
+    fn user_notional_capacity_available(
+        &self,
+        current_timestamp: i64,
+        is_buy: bool,
+    ) -> Result<Option<u64>> {
+        let state = &self.gmtoken_manager_state;
+        if !state.user_notional_limit_enabled() {
+            return Ok(None);
+        }
+
+        let usage = match self.user_notional_limit.as_ref() {
+            Some(user_notional_limit) if is_buy => user_notional_limit.mint_usage,
+            Some(user_notional_limit) => user_notional_limit.redeem_usage,
+            None => Default::default(),
+        };
+
+        usage
+            .available(
+                current_timestamp,
+                state.user_notional_rate_limit,
+                state.user_notional_limit_window,
+            )
+            .map(Some)
+    }
+}
@@ -0,0 +1,339 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::Token,
+    token_interface::{
+        burn_checked, transfer_checked, BurnChecked, Mint, TokenAccount, TokenInterface,
+        TransferChecked,
+    },
+};
+
+#[cfg(any(feature = "mainnet", feature = "testnet"))]
+use crate::constants::USDC_MINT;
+use crate::{
+    constants::{
+        BASIS_POINTS_DIVISOR, MINT_AUTHORITY_SEED, USDON_MANAGER_STATE_SEED, WHITELIST_SEED,
+    },
+    errors::OndoError,
+    events::{PSMDeposit, PSMRedemption},
+    state::{USDonManagerState, Whitelist},
+    utils::{
+        asset_to_usd_amount, mint_usdon_to, mul_div, normalize_decimals, usd_to_asset_amount,
+        usdc_conversion_rate,
+    },
+};
+
+/// Exchange USDC for USDon, or USDon for USDC, directly through the peg stability module (PSM)
+/// USDC is held in the USDC vault and USDon is minted to or burned from the user.
+/// Unpermissioned - the user must be whitelisted.
+#[derive(Accounts)]
+pub struct PSMContext<'info> {
+    /// The user exchanging USDC or USDon
+    pub user: Signer<'info>,
+
+    /// The Whitelist account verifying the user is authorized
+    /// # PDA Seeds
+    /// - WHITELIST_SEED
+    /// - User's address
+    ///
+    /// CHECK: Seeds constraint validates PDA address.
+    /// Validated in instruction handler - returns UserNotWhitelisted if not initialized.
+    #[account(
+        seeds = [WHITELIST_SEED, user.key().as_ref()],
+        bump,
+    )]
+    pub whitelist: UncheckedAccount<'info>,
+
+    /// The USDonManagerState account holding the PSM configuration and rate limit state
+    /// # PDA Seeds
+    /// - USDON_MANAGER_STATE_SEED
+    #[account(
+        mut,
+        seeds = [USDON_MANAGER_STATE_SEED],
+        bump = usdon_manager_state.bump,
+    )]
+    pub usdon_manager_state: Box<Account<'info, USDonManagerState>>,
+
+    /// The oracle price update account for USDC/USD price feed
+    /// Required only when oracle pricing is enabled
+    /// CHECK: Address constraint against the account stored in `usdon_manager_state`.
+    #[account(address = usdon_manager_state.usdc_price_update)]
+    pub usdc_price_update: Option<UncheckedAccount<'info>>,
+
+    /// The secondary oracle price update account for USDC/USD price feed
+    /// Required only when a secondary oracle is configured
+    /// CHECK: Address constraint against the account stored in `usdon_manager_state`.
+    #[account(address = usdon_manager_state.usdc_secondary_price_update)]
+    pub usdc_secondary_price_update: Option<UncheckedAccount<'info>>,
+
+    /// The USDC vault holding the USDC backing PSM deposits
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = usdon_manager_state,
+        associated_token::token_program = spl_token_program,
+        constraint = usdc_vault.key() == usdon_manager_state.usdc_vault
+    )]
+    pub usdc_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The USDC mint (SPL Token)
+    #[cfg(not(any(feature = "mainnet", feature = "testnet")))]
+    #[account(
+        mint::token_program = spl_token_program,
+    )]
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[cfg(any(feature = "mainnet", feature = "testnet"))]
+    #[account(
+        mint::token_program = spl_token_program,
+        constraint = usdc_mint.key() == USDC_MINT
+    )]
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The user's USDC token account
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = user,
+        token::token_program = spl_token_program,
+    )]
+    pub user_usdc_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The USDon mint (Token-2022)
+    #[account(
+        mut,
+        mint::authority = mint_authority,
+        mint::token_program = token_program,
+        constraint = usdon_mint.key() == usdon_manager_state.usdon_mint
+    )]
+    pub usdon_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The user's USDon token account
+    #[account(
+        mut,
+        token::mint = usdon_mint,
+        token::authority = user,
+        token::token_program = token_program,
+    )]
+    pub user_usdon_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The mint authority PDA
+    /// # PDA Seeds
+    /// - MINT_AUTHORITY_SEED
+    ///
+    /// CHECK: This account is used to verify the mint authority.
+    /// Does not need to be checked for correctness as it is uninitialized.
+    #[account(
+        seeds = [MINT_AUTHORITY_SEED],
+        bump,
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    /// The token program (Token-2022)
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The legacy SPL Token program
+    pub spl_token_program: Program<'info, Token>,
+}
+
+impl<'info> PSMContext<'info> {
+    /// Deposit USDC into the USDC vault and mint its USD value in USDon, minus the PSM fee
+    /// # Arguments
+    /// * `amount` - The amount of USDC to deposit
+    /// * `min_amount_out` - The minimum amount of USDon the user accepts
+    /// * `mint_authority_bump` - The bump of the mint authority PDA
+    /// # Returns
+    /// * `Result<()>` - Ok if the deposit succeeds, Err otherwise
+    /// # Errors
+    /// * `OndoError::PSMDepositPaused` - If PSM deposits are paused
+    /// * `OndoError::USDonMintingPaused` - If USDon minting is paused
+    /// * `OndoError::InvalidRateLimit` - If the PSM rate limit is not configured or exceeded
+    /// * `OndoError::SlippageExceeded` - If the USDon minted is below `min_amount_out`
+    /// * `OndoError::USDonSupplyCapExceeded` - If the mint would exceed the USDon supply cap
+    pub fn deposit_usdc_for_usdon(
+        &mut self,
+        amount: u64,
+        min_amount_out: u64,
+        mint_authority_bump: u8,
+    ) -> Result<()> {
+        require!(
+            !self.usdon_manager_state.psm_deposit_paused,
+            OndoError::PSMDepositPaused
+        );
+        require_gt!(amount, 0, OndoError::InvalidAmount);
+
+        Whitelist::verify(&self.whitelist)?;
+
+        let conversion_rate = self.conversion_rate()?;
+
+        // Round down: the user receives at most the USD value of the deposit
+        let usd_amount = asset_to_usd_amount(amount, conversion_rate, false)?;
+        let usdon_value = normalize_decimals(
+            usd_amount,
+            self.usdc_mint.decimals,
+            self.usdon_mint.decimals,
+            false,
+        )?;
+
+        let fee = self.fee(usdon_value)?;
+        let usdon_amount = usdon_value
+            .checked_sub(fee)
+            .ok_or(OndoError::MathOverflow)?;
+
+        require_gt!(usdon_amount, 0, OndoError::InvalidAmount);
+        require_gte!(usdon_amount, min_amount_out, OndoError::SlippageExceeded);
+
+        self.usdon_manager_state.consume_psm_capacity(
+            usdon_value,
+            Clock::get()?.unix_timestamp,
+            true,
+        )?;
+
+        transfer_checked(
+            CpiContext::new(
+                self.spl_token_program.to_account_info(),
+                TransferChecked {
+                    from: self.user_usdc_token_account.to_account_info(),
+                    mint: self.usdc_mint.to_account_info(),
+                    to: self.usdc_vault.to_account_info(),
+                    authority: self.user.to_account_info(),
+                },
+            ),
+            amount,
+            self.usdc_mint.decimals,
+        )?;
+
+        mint_usdon_to(
+            &self.usdon_manager_state,
+            &self.token_program.to_account_info(),
+            &self.usdon_mint.to_account_info(),
+            &self.user_usdon_token_account.to_account_info(),
+            &self.mint_authority.to_account_info(),
+            &[&[MINT_AUTHORITY_SEED, &[mint_authority_bump]]],
+            usdon_amount,
+        )?;
+
+        emit!(PSMDeposit {
+            user: self.user.key(),
+            usdc_amount: amount,
+            usdon_amount,
+            fee,
+            conversion_rate,
+        });
+
+        Ok(())
+    }
+
+    /// Burn USDon and pay its USD value out of the USDC vault, minus the PSM fee
+    /// # Arguments
+    /// * `amount` - The amount of USDon to redeem
+    /// * `min_amount_out` - The minimum amount of USDC the user accepts
+    /// # Returns
+    /// * `Result<()>` - Ok if the redemption succeeds, Err otherwise
+    /// # Errors
+    /// * `OndoError::PSMRedemptionPaused` - If PSM redemptions are paused
+    /// * `OndoError::InvalidRateLimit` - If the PSM rate limit is not configured or exceeded
+    /// * `OndoError::SlippageExceeded` - If the USDC paid out is below `min_amount_out`
+    /// * `OndoError::InsufficientVaultLiquidity` - If the unreserved USDC vault balance cannot cover the payout
+    pub fn redeem_usdon_for_usdc(&mut self, amount: u64, min_amount_out: u64) -> Result<()> {
+        require!(
+            !self.usdon_manager_state.psm_redemption_paused,
+            OndoError::PSMRedemptionPaused
+        );
+        require_gt!(amount, 0, OndoError::InvalidAmount);
+
+        Whitelist::verify(&self.whitelist)?;
+
+        let conversion_rate = self.conversion_rate()?;
+
+        let fee = self.fee(amount)?;
+        let usdon_value = amount.checked_sub(fee).ok_or(OndoError::MathOverflow)?;
+
+        // Round down: the user receives at most the USD value of the redemption
+        let usd_amount = normalize_decimals(
+            usdon_value,
+            self.usdon_mint.decimals,
+            self.usdc_mint.decimals,
+            false,
+        )?;
+        let usdc_amount = usd_to_asset_amount(usd_amount, conversion_rate, false)?;
+
+        require_gt!(usdc_amount, 0, OndoError::InvalidAmount);
+        require_gte!(usdc_amount, min_amount_out, OndoError::SlippageExceeded);
+
+        // USDC owed to queued claims is reserved for them
+        require_gte!(
+            self.usdon_manager_state
+                .unreserved_usdc(self.usdc_vault.amount),
+            usdc_amount,
+            OndoError::InsufficientVaultLiquidity
+        );
+
+        self.usdon_manager_state.consume_psm_capacity(
+            amount,
+            Clock::get()?.unix_timestamp,
+            false,
+        )?;
+
+        burn_checked(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                BurnChecked {
+                    mint: self.usdon_mint.to_account_info(),
+                    from: self.user_usdon_token_account.to_account_info(),
+                    authority: self.user.to_account_info(),
+                },
+            ),
+            amount,
+            self.usdon_mint.decimals,
+        )?;
+
+        transfer_checked(
+            CpiContext::new_with_signer(
+                self.spl_token_program.to_account_info(),
+                TransferChecked {
+                    from: self.usdc_vault.to_account_info(),
+                    mint: self.usdc_mint.to_account_info(),
+                    to: self.user_usdc_token_account.to_account_info(),
+                    authority: self.usdon_manager_state.to_account_info(),
+                },
+                &[&[USDON_MANAGER_STATE_SEED, &[self.usdon_manager_state.bump]]],
+            ),
+            usdc_amount,
+            self.usdc_mint.decimals,
+        )?;
+
+        emit!(PSMRedemption {
+            user: self.user.key(),
+            usdon_amount: amount,
+            usdc_amount,
+            fee,
+            conversion_rate,
+        });
+
+        Ok(())
+    }
+
+    /// Returns the rate USDC is exchanged at, after the same oracle checks as GM Token trades
+    fn conversion_rate(&self) -> Result<u64> {
+        usdc_conversion_rate(
+            &self.usdon_manager_state,
+            self.usdc_price_update
+                .as_ref()
+                .map(|account| account.as_ref()),
+            self.usdc_secondary_price_update
+                .as_ref()
+                .map(|account| account.as_ref()),
+        )
+    }
+
+    /// Returns the PSM fee on a USDon value
+    /// Round up: the fee is charged in full
+    fn fee(&self, usdon_value: u64) -> Result<u64> {
+        mul_div(
+            usdon_value,
+            self.usdon_manager_state.psm_fee_bps,
+            BASIS_POINTS_DIVISOR,
+            true,
+        )
+    }
+}
@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    constants::PUSHED_PRICE_SEED,
+    errors::OndoError,
+    events::PricePushed,
+    state::{PushedPrice, RoleType, Roles},
+};
+
+/// Create an admin-pushed price account for a mint
+/// Requires `ADMIN_ROLE_USDON_MANAGER` role
+#[derive(Accounts)]
+pub struct InitializePushedPrice<'info> {
+    /// The account with the authority to create the price account, pays for account creation
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The Roles account verifying the authority has the `ADMIN_ROLE_USDON_MANAGER` role
+    /// # PDA Seeds
+    /// - ADMIN_ROLE_USDON_MANAGER
+    /// - The authority's address
+    #[account(
+        seeds = [RoleType::ADMIN_ROLE_USDON_MANAGER, authority.key().as_ref()],
+        bump = authority_role_account.bump,
+    )]
+    pub authority_role_account: Account<'info, Roles>,
+
+    /// The mint the price is for
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// The PushedPrice account being created
+    /// # PDA Seeds
+    /// - PUSHED_PRICE_SEED
+    /// - Mint address
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PushedPrice::INIT_SPACE,
+        seeds = [PUSHED_PRICE_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub pushed_price: Account<'info, PushedPrice>,
+
+    /// The system program
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializePushedPrice<'info> {
+    /// Create the price account, it holds no price until the first push
+    /// # Arguments
+    /// * `bump` - The bump of the pushed price PDA
+    /// # Returns
+    /// * `Result<()>` - Ok if the account is created, Err otherwise
+    pub fn initialize_pushed_price(&mut self, bump: u8) -> Result<()> {
+        self.pushed_price.set_inner(PushedPrice {
+            mint: self.mint.key(),
+            price: 0,
+            conf: 0,
+            updated_at: 0,
+            bump,
+        });
+
+        Ok(())
+    }
+}
+
+/// Push a new price to an admin-pushed price account
+/// Requires `ADMIN_ROLE_USDON_MANAGER` role
+#[derive(Accounts)]
+pub struct PushPrice<'info> {
+    /// The account with the authority to push the price
+    pub authority: Signer<'info>,
+
+    /// The Roles account verifying the authority has the `ADMIN_ROLE_USDON_MANAGER` role
+    /// # PDA Seeds
+    /// - ADMIN_ROLE_USDON_MANAGER
+    /// - The authority's address
+    #[account(
+        seeds = [RoleType::ADMIN_ROLE_USDON_MANAGER, authority.key().as_ref()],
+        bump = authority_role_account.bump,
+    )]
+    pub authority_role_account: Account<'info, Roles>,
+
+    /// The PushedPrice account being updated
+    /// # PDA Seeds
+    /// - PUSHED_PRICE_SEED
+    /// - Mint address
+    #[account(
+        mut,
+        seeds = [PUSHED_PRICE_SEED, pushed_price.mint.as_ref()],
+        bump = pushed_price.bump,
+    )]
+    pub pushed_price: Account<'info, PushedPrice>,
+}
+
+impl<'info> PushPrice<'info> {
+    /// Store a new price
+    /// # Arguments
+    /// * `price` - The price, scaled by USDC_PRICE_DECIMALS
+    /// * `conf` - The confidence interval of the price, scaled by USDC_PRICE_DECIMALS
+    /// # Returns
+    /// * `Result<()>` - Ok if the price is stored, Err otherwise
+    pub fn push_price(&mut self, price: u64, conf: u64) -> Result<()> {
+        require_gt!(price, 0, OndoError::InvalidPrice);
+
+        self.pushed_price.price = price;
+        self.pushed_price.conf = conf;
+        self.pushed_price.updated_at = Clock::get()?.unix_timestamp;
+
+        emit!(PricePushed {
+            pushed_price: self.pushed_price.key(),
+            mint: self.pushed_price.mint,
+            price,
+            conf,
+            authority: self.authority.key(),
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,725 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions::ID as INSTRUCTIONS_ID;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use super::TokenManager;
+use crate::{
+    constants::{
+        ATTESTATION_ID_SEED, GMTOKEN_MANAGER_STATE_SEED, MINT_AUTHORITY_SEED, ONDO_USER_SEED,
+        ORACLE_SANITY_CHECK_SEED, RECURRING_ORDER_SEED, TOKEN_LIMIT_ACCOUNT_SEED,
+        USDON_MANAGER_STATE_SEED, USER_NOTIONAL_LIMIT_SEED, WHITELIST_SEED,
+    },
+    errors::OndoError,
+    events::{
+        RecurringOrderCancelled, RecurringOrderCreated, RecurringOrderExecuted,
+        RecurringOrderFunded,
+    },
+    state::{
+        GMTokenManagerState, LimitGroup, LimitProfile, OndoUser, OracleSanityCheck, RecurringOrder,
+        RoleType, Roles, TokenLimit, USDonManagerState, UserNotionalLimit, Whitelist,
+    },
+    utils::release_escrow,
+};
+
+/// Create a recurring GM Token purchase schedule paid for in USDon.
+/// The USDon allowance is escrowed in a token account owned by the schedule PDA.
+/// Unpermissioned - the user must be whitelisted.
+#[derive(Accounts)]
+#[instruction(order_id: u64)]
+pub struct CreateRecurringOrder<'info> {
+    /// The user creating the schedule, pays for account creation
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// The GM Token mint being purchased
+    #[account(mint::token_program = token_program)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The TokenLimit account proving the mint is a GM Token
+    /// # PDA Seeds
+    /// - TOKEN_LIMIT_ACCOUNT_SEED
+    /// - Mint address
+    #[account(
+        seeds = [TOKEN_LIMIT_ACCOUNT_SEED, mint.key().as_ref()],
+        bump = token_limit_account.bump,
+    )]
+    pub token_limit_account: Box<Account<'info, TokenLimit>>,
+
+    /// The Whitelist account verifying the user is authorized
+    /// # PDA Seeds
+    /// - WHITELIST_SEED
+    /// - User's address
+    ///
+    /// CHECK: Seeds constraint validates PDA address.
+    /// Validated in instruction handler - returns UserNotWhitelisted if not initialized.
+    #[account(
+        seeds = [WHITELIST_SEED, user.key().as_ref()],
+        bump,
+    )]
+    pub whitelist: UncheckedAccount<'info>,
+
+    /// The RecurringOrder account being created
+    /// # PDA Seeds
+    /// - RECURRING_ORDER_SEED
+    /// - User's address
+    /// - Order ID (little endian)
+    #[account(
+        init,
+        payer = user,
+        space = 8 + RecurringOrder::INIT_SPACE,
+        seeds = [RECURRING_ORDER_SEED, user.key().as_ref(), order_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub order: Box<Account<'info, RecurringOrder>>,
+
+    /// The USDon mint (Token-2022)
+    #[account(
+        mint::token_program = token_program,
+        address = usdon_manager_state.usdon_mint,
+    )]
+    pub usdon_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The user's USDon token account the allowance is taken from
+    #[account(
+        mut,
+        token::mint = usdon_mint,
+        token::authority = user,
+        token::token_program = token_program,
+    )]
+    pub user_usdon_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The escrow token account owned by the schedule
+    #[account(
+        init,
+        payer = user,
+        associated_token::mint = usdon_mint,
+        associated_token::authority = order,
+        associated_token::token_program = token_program,
+    )]
+    pub escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The USDonManagerState account storing the USDon mint
+    /// # PDA Seeds
+    /// - USDON_MANAGER_STATE_SEED
+    #[account(
+        seeds = [USDON_MANAGER_STATE_SEED],
+        bump = usdon_manager_state.bump,
+    )]
+    pub usdon_manager_state: Box<Account<'info, USDonManagerState>>,
+
+    /// The token program (Token-2022)
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The associated token program
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// The system program
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateRecurringOrder<'info> {
+    /// Create the schedule and escrow the initial USDon allowance
+    /// # Arguments
+    /// * `order_id` - User supplied identifier for the schedule
+    /// * `amount_per_execution` - The maximum USDon spent by a single installment
+    /// * `interval` - The number of seconds between installments
+    /// * `first_execution_at` - The timestamp from which the first installment can be executed
+    /// * `deposit_amount` - The USDon allowance to escrow, can be topped up later
+    /// * `bump` - The bump of the schedule PDA
+    /// # Returns
+    /// * `Result<()>` - Ok if the schedule is created, Err otherwise
+    pub fn create_recurring_order(
+        &mut self,
+        order_id: u64,
+        amount_per_execution: u64,
+        interval: i64,
+        first_execution_at: i64,
+        deposit_amount: u64,
+        bump: u8,
+    ) -> Result<()> {
+        require_gt!(amount_per_execution, 0, OndoError::InvalidAmount);
+        require_gt!(interval, 0, OndoError::InvalidInterval);
+
+        Whitelist::verify(&self.whitelist)?;
+
+        let current_timestamp = Clock::get()?.unix_timestamp;
+        let next_execution_at = first_execution_at.max(current_timestamp);
+
+        if deposit_amount > 0 {
+            transfer_checked(
+                CpiContext::new(
+                    self.token_program.to_account_info(),
+                    TransferChecked {
+                        from: self.user_usdon_token_account.to_account_info(),
+                        mint: self.usdon_mint.to_account_info(),
+                        to: self.escrow.to_account_info(),
+                        authority: self.user.to_account_info(),
+                    },
+                ),
+                deposit_amount,
+                self.usdon_mint.decimals,
+            )?;
+        }
+
+        self.order.set_inner(RecurringOrder {
+            user: self.user.key(),
+            order_id,
+            mint: self.mint.key(),
+            escrow: self.escrow.key(),
+            amount_per_execution,
+            interval,
+            next_execution_at,
+            executions: 0,
+            total_spent: 0,
+            created_at: current_timestamp,
+            bump,
+        });
+
+        emit!(RecurringOrderCreated {
+            order: self.order.key(),
+            user: self.user.key(),
+            mint: self.mint.key(),
+            amount_per_execution,
+            interval,
+            next_execution_at,
+            deposit_amount,
+        });
+
+        Ok(())
+    }
+}
+
+/// Top up the USDon allowance of a recurring purchase schedule.
+/// Only the user who created the schedule can fund it.
+#[derive(Accounts)]
+pub struct FundRecurringOrder<'info> {
+    /// The user who created the schedule
+    pub user: Signer<'info>,
+
+    /// The RecurringOrder account being funded
+    /// # PDA Seeds
+    /// - RECURRING_ORDER_SEED
+    /// - User's address
+    /// - Order ID (little endian)
+    #[account(
+        has_one = user,
+        has_one = escrow,
+        seeds = [RECURRING_ORDER_SEED, user.key().as_ref(), order.order_id.to_le_bytes().as_ref()],
+        bump = order.bump,
+    )]
+    pub order: Box<Account<'info, RecurringOrder>>,
+
+    /// The escrow token account owned by the schedule
+    #[account(mut)]
+    pub escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The USDon mint (Token-2022)
+    #[account(
+        mint::token_program = token_program,
+        address = usdon_manager_state.usdon_mint,
+    )]
+    pub usdon_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The user's USDon token account the allowance is taken from
+    #[account(
+        mut,
+        token::mint = usdon_mint,
+        token::authority = user,
+        token::token_program = token_program,
+    )]
+    pub user_usdon_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The USDonManagerState account storing the USDon mint
+    /// # PDA Seeds
+    /// - USDON_MANAGER_STATE_SEED
+    #[account(
+        seeds = [USDON_MANAGER_STATE_SEED],
+        bump = usdon_manager_state.bump,
+    )]
+    pub usdon_manager_state: Box<Account<'info, USDonManagerState>>,
+
+    /// The token program (Token-2022)
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> FundRecurringOrder<'info> {
+    /// Add USDon to the schedule's allowance
+    /// # Arguments
+    /// * `amount` - The amount of USDon to add
+    /// # Returns
+    /// * `Result<()>` - Ok if the allowance is topped up, Err otherwise
+    pub fn fund_recurring_order(&mut self, amount: u64) -> Result<()> {
+        require_gt!(amount, 0, OndoError::InvalidAmount);
+
+        transfer_checked(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    from: self.user_usdon_token_account.to_account_info(),
+                    mint: self.usdon_mint.to_account_info(),
+                    to: self.escrow.to_account_info(),
+                    authority: self.user.to_account_info(),
+                },
+            ),
+            amount,
+            self.usdon_mint.decimals,
+        )?;
+
+        emit!(RecurringOrderFunded {
+            order: self.order.key(),
+            user: self.user.key(),
+            amount,
+        });
+
+        Ok(())
+    }
+}
+
+/// Cancel a recurring purchase schedule and return the remaining allowance to the user.
+/// Only the user who created the schedule can cancel it.
+#[derive(Accounts)]
+pub struct CancelRecurringOrder<'info> {
+    /// The user who created the schedule, receives the rent of the closed accounts
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// The RecurringOrder account being cancelled
+    /// # PDA Seeds
+    /// - RECURRING_ORDER_SEED
+    /// - User's address
+    /// - Order ID (little endian)
+    #[account(
+        mut,
+        close = user,
+        has_one = user,
+        has_one = escrow,
+        seeds = [RECURRING_ORDER_SEED, user.key().as_ref(), order.order_id.to_le_bytes().as_ref()],
+        bump = order.bump,
+    )]
+    pub order: Box<Account<'info, RecurringOrder>>,
+
+    /// The escrow token account owned by the schedule
+    #[account(mut)]
+    pub escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The USDon mint (Token-2022)
+    #[account(
+        mint::token_program = token_program,
+        address = usdon_manager_state.usdon_mint,
+    )]
+    pub usdon_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The user's USDon token account receiving the refund
+    #[account(
+        mut,
+        token::mint = usdon_mint,
+        token::authority = user,
+        token::token_program = token_program,
+    )]
+    pub user_usdon_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The USDonManagerState account storing the USDon mint
+    /// # PDA Seeds
+    /// - USDON_MANAGER_STATE_SEED
+    #[account(
+        seeds = [USDON_MANAGER_STATE_SEED],
+        bump = usdon_manager_state.bump,
+    )]
+    pub usdon_manager_state: Box<Account<'info, USDonManagerState>>,
+
+    /// The token program (Token-2022)
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> CancelRecurringOrder<'info> {
+    /// Cancel the schedule and refund the remaining allowance
+    /// # Returns
+    /// * `Result<()>` - Ok if the schedule is cancelled, Err otherwise
+    pub fn cancel_recurring_order(&mut self) -> Result<()> {
+        let order_id = self.order.order_id.to_le_bytes();
+        let user = self.user.key();
+        let order_seeds: &[&[u8]] = &[
+            RECURRING_ORDER_SEED,
+            user.as_ref(),
+            &order_id,
+            &[self.order.bump],
+        ];
+
+        let refunded = release_escrow(
+            &self.token_program.to_account_info(),
+            &self.escrow.to_account_info(),
+            &self.usdon_mint.to_account_info(),
+            self.usdon_mint.decimals,
+            &self.user_usdon_token_account.to_account_info(),
+            &self.user.to_account_info(),
+            &self.order.to_account_info(),
+            &[order_seeds],
+        )?;
+
+        emit!(RecurringOrderCancelled {
+            order: self.order.key(),
+            user,
+            refunded,
+        });
+
+        Ok(())
+    }
+}
+
+/// Execute the next installment of a recurring purchase schedule.
+/// Runs the same checks as a direct mint on behalf of the schedule's user, including
+/// the token and user rate limits and trading hours.
+/// Unpermissioned - the attestation must be signed for the schedule's user.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(attestation_id: [u8; 16])]
+pub struct ExecuteRecurringOrder<'info> {
+    /// The account running the crank, pays for account creation if needed
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    /// The user who created the schedule
+    /// CHECK: Address constraint validates it is the schedule's user.
+    #[account(address = order.user)]
+    pub user: UncheckedAccount<'info>,
+
+    /// The RecurringOrder account being executed
+    /// # PDA Seeds
+    /// - RECURRING_ORDER_SEED
+    /// - User's address
+    /// - Order ID (little endian)
+    #[account(
+        mut,
+        has_one = mint,
+        has_one = escrow,
+        seeds = [RECURRING_ORDER_SEED, order.user.as_ref(), order.order_id.to_le_bytes().as_ref()],
+        bump = order.bump,
+    )]
+    pub order: Box<Account<'info, RecurringOrder>>,
+
+    /// The escrow token account owned by the schedule
+    #[account(mut, token::authority = order)]
+    pub escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The GM Token mint being purchased
+    #[account(
+        mut,
+        mint::authority = mint_authority,
+        mint::token_program = token_program
+    )]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The mint authority PDA
+    /// # PDA Seeds
+    /// - MINT_AUTHORITY_SEED
+    /// CHECK: This account is used to verify the mint authority.
+    /// Does not need to be checked for correctness as it is uninitialized.
+    #[account(
+        seeds = [MINT_AUTHORITY_SEED],
+        bump,
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    /// The OndoUser account tracking user-specific state for this mint
+    /// # PDA Seeds
+    /// - ONDO_USER_SEED
+    /// - User's address
+    /// - Mint address
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        space = 8 + OndoUser::INIT_SPACE,
+        seeds = [ONDO_USER_SEED, user.key().as_ref(), mint.key().as_ref()],
+        bump,
+    )]
+    pub ondo_user: Box<Account<'info, OndoUser>>,
+
+    /// The user's `RateLimitExemptRole` account, skipping the per-user rate limit
+    /// Only provided for designated market makers
+    /// # PDA Seeds
+    /// - RATE_LIMIT_EXEMPT_ROLE
+    /// - User's address
+    #[account(
+        seeds = [RoleType::RATE_LIMIT_EXEMPT_ROLE, user.key().as_ref()],
+        bump = rate_limit_exemption.bump,
+    )]
+    pub rate_limit_exemption: Option<Box<Account<'info, Roles>>>,
+
+    /// The LimitProfile the OndoUser is linked to
+    /// Required when the OndoUser is linked to a limit profile
+    pub limit_profile: Option<Box<Account<'info, LimitProfile>>>,
+
+    /// The limit group the OndoUser is attached to
+    /// Required when the OndoUser is attached to a limit group
+    #[account(mut)]
+    pub limit_group: Option<Box<Account<'info, LimitGroup>>>,
+
+    /// The user's notional across all GM Tokens
+    /// Required while the per-user notional limit is enabled
+    /// # PDA Seeds
+    /// - USER_NOTIONAL_LIMIT_SEED
+    /// - User's address
+    #[account(
+        mut,
+        seeds = [USER_NOTIONAL_LIMIT_SEED, user.key().as_ref()],
+        bump = user_notional_limit.bump,
+    )]
+    pub user_notional_limit: Option<Box<Account<'info, UserNotionalLimit>>>,
+
+    /// The TokenLimit account enforcing mint/burn limits for the GM Token
+    /// # PDA Seeds
+    /// - TOKEN_LIMIT_ACCOUNT_SEED
+    /// - Mint address
+    #[account(
+        mut,
+        seeds = [TOKEN_LIMIT_ACCOUNT_SEED, mint.key().as_ref()],
+        bump = token_limit_account.bump,
+    )]
+    pub token_limit_account: Box<Account<'info, TokenLimit>>,
+
+    /// The OracleSanityCheck account validating oracle price updates
+    /// # PDA Seeds
+    /// - ORACLE_SANITY_CHECK_SEED
+    /// - Mint address
+    #[account(
+        mut,
+        seeds = [ORACLE_SANITY_CHECK_SEED, mint.key().as_ref()],
+        bump = sanity_check_account.bump,
+    )]
+    pub sanity_check_account: Box<Account<'info, OracleSanityCheck>>,
+
+    /// The user's associated token account for the GM Token
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        associated_token::mint = mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    pub user_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The attestation ID account preventing attestation reuse
+    /// # PDA Seeds
+    /// - ATTESTATION_ID_SEED
+    /// - Attestation ID (16-byte array)
+    /// CHECK: Seeds constraint validates PDA address.
+    /// Existence means the attestation has been used.
+    #[account(
+        mut,
+        seeds = [ATTESTATION_ID_SEED, attestation_id.as_ref()],
+        bump,
+    )]
+    pub attestation_id_account: UncheckedAccount<'info>,
+
+    /// The Whitelist account verifying the user is authorized
+    /// # PDA Seeds
+    /// - WHITELIST_SEED
+    /// - User's address
+    /// CHECK: Seeds constraint validates PDA address.
+    /// Validated in instruction handler - returns UserNotWhitelisted if not initialized.
+    #[account(
+        seeds = [WHITELIST_SEED, user.key().as_ref()],
+        bump,
+    )]
+    pub whitelist: UncheckedAccount<'info>,
+
+    /// The token program (Token-2022)
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The system program
+    pub system_program: Program<'info, System>,
+
+    /// The associated token program
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// The USDon vault storing USDon tokens
+    #[account(
+        mut,
+        associated_token::mint = usdon_mint,
+        associated_token::authority = usdon_manager_state,
+        associated_token::token_program = token_program,
+        constraint = usdon_vault.key() == usdon_manager_state.usdon_vault
+    )]
+    pub usdon_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The USDon mint (Token-2022)
+    #[account(
+        mut,
+        mint::token_program = token_program,
+        constraint = usdon_mint.key() == usdon_manager_state.usdon_mint
+    )]
+    pub usdon_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The user's associated token account for USDon
+    #[account(
+        mut,
+        associated_token::mint = usdon_mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    pub user_usdon_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The USDonManagerState account used as authority for vault operations
+    /// Tracks the USDC reserved for queued redemption claims
+    /// # PDA Seeds
+    /// - USDON_MANAGER_STATE_SEED
+    #[account(
+        mut,
+        seeds = [USDON_MANAGER_STATE_SEED],
+        bump = usdon_manager_state.bump,
+    )]
+    pub usdon_manager_state: Box<Account<'info, USDonManagerState>>,
+
+    /// The GmTokenManagerState account managing GM Token operations
+    /// # PDA Seeds
+    /// - GMTOKEN_MANAGER_STATE_SEED
+    #[account(
+        mut,
+        seeds = [GMTOKEN_MANAGER_STATE_SEED],
+        bump = gmtoken_manager_state.bump,
+    )]
+    pub gmtoken_manager_state: Box<Account<'info, GMTokenManagerState>>,
+
+    /// CHECK: Sysvar account for instruction introspection
+    #[account(address = INSTRUCTIONS_ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+impl<'info> ExecuteRecurringOrder<'info> {
+    /// Creates a TokenManager instance acting on behalf of the schedule's user.
+    /// The cranker pays for any accounts created during execution.
+    /// # Returns
+    /// * `TokenManager` - A TokenManager instance with references to the relevant accounts.
+    #[allow(clippy::wrong_self_convention)]
+    pub fn into_token_manager(&mut self) -> TokenManager<'_, 'info> {
+        TokenManager {
+            user: &self.user,
+            payer: &self.cranker,
+            mint: &mut self.mint,
+            mint_authority: &self.mint_authority,
+            ondo_user: &mut self.ondo_user,
+            rate_limit_exemption: self.rate_limit_exemption.as_deref(),
+            limit_profile: self.limit_profile.as_deref(),
+            limit_group: self.limit_group.as_deref_mut(),
+            user_notional_limit: self.user_notional_limit.as_deref_mut(),
+            token_limit_account: &mut self.token_limit_account,
+            sanity_check_account: &mut self.sanity_check_account,
+            user_token_account: &mut self.user_token_account,
+            attestation_id_account: &mut self.attestation_id_account,
+            whitelist: &self.whitelist,
+            token_program: &self.token_program,
+            system_program: &self.system_program,
+            associated_token_program: &self.associated_token_program,
+            spl_token_program: None,
+            usdc_price_update: None,
+            usdc_secondary_price_update: None,
+            redemption_queue: None,
+            usdc_vault: None,
+            usdc_mint: None,
+            user_usdc_token_account: None,
+            // Recurring orders only settle in USDon, not in registered settlement assets
+            settlement_asset: None,
+            settlement_token_program: None,
+            settlement_price_update: None,
+            settlement_vault: None,
+            settlement_mint: None,
+            user_settlement_token_account: None,
+            usdon_vault: &mut self.usdon_vault,
+            usdon_mint: &self.usdon_mint,
+            user_usdon_token_account: &mut self.user_usdon_token_account,
+            usdon_manager_state: &mut self.usdon_manager_state,
+            gmtoken_manager_state: &mut self.gmtoken_manager_state,
+            instructions: &self.instructions,
+        }
+    }
+
+    /// Execute the installment that is due
+    /// The cost of the attested amount must fit within both the per-installment amount
+    /// and the remaining allowance, and reach the lower of the two within one unit.
+    /// # Arguments
+    /// * `attestation_id` - A unique 16-byte identifier for the attestation
+    /// * `price` - The attested price
+    /// * `amount` - The attested amount of GM Tokens
+    /// * `expiration` - The expiration timestamp of the attestation
+    /// * `bumps` - The PDA bumps for account derivation
+    /// # Returns
+    /// * `Result<u64>` - The conversion rate of the settlement leg, see `TokenManager::conversion_rate`
+    pub fn execute_recurring_order(
+        &mut self,
+        attestation_id: [u8; 16],
+        price: u64,
+        amount: u64,
+        expiration: i64,
+        bumps: &ExecuteRecurringOrderBumps,
+    ) -> Result<u64> {
+        let current_timestamp = Clock::get()?.unix_timestamp;
+        self.order.validate_due(current_timestamp)?;
+
+        let order_key = self.order.key();
+        let user = self.order.user;
+        let order_id = self.order.order_id.to_le_bytes();
+        let bump = self.order.bump;
+        let order_seeds: &[&[u8]] = &[RECURRING_ORDER_SEED, user.as_ref(), &order_id, &[bump]];
+        let escrow = self.escrow.to_account_info();
+        let escrow_authority = self.order.to_account_info();
+        let budget = self.order.amount_per_execution.min(self.escrow.amount);
+
+        let mut token_manager = self.into_token_manager();
+
+        // Validate token accounts
+        token_manager.validate(true)?;
+
+        // Run the same checks as a direct mint
+        token_manager.validate_trade(
+            attestation_id,
+            price,
+            amount,
+            expiration,
+            true,
+            bumps.ondo_user,
+            bumps.attestation_id_account,
+        )?;
+
+        let conversion_rate = token_manager.conversion_rate(true)?;
+
+        let spent = token_manager.mint_from_escrow(
+            &escrow,
+            &escrow_authority,
+            &[order_seeds],
+            budget,
+            price,
+            amount,
+            true,
+            conversion_rate,
+            bumps.mint_authority,
+        )?;
+        self.order.validate_spend(budget, spent)?;
+
+        self.order.executions = self
+            .order
+            .executions
+            .checked_add(1)
+            .ok_or(OndoError::MathOverflow)?;
+        self.order.total_spent = self
+            .order
+            .total_spent
+            .checked_add(spent)
+            .ok_or(OndoError::MathOverflow)?;
+        self.order.advance_schedule(current_timestamp)?;
+
+        emit!(RecurringOrderExecuted {
+            order: order_key,
+            user,
+            price,
+            amount,
+            spent,
+            next_execution_at: self.order.next_execution_at,
+            cranker: self.cranker.key(),
+        });
+
+        Ok(conversion_rate)
+    }
+}
@@ -0,0 +1,300 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::Token,
+    token_interface::{transfer_checked, Mint, TokenAccount, TransferChecked},
+};
+
+use crate::{
+    constants::{REDEMPTION_QUEUE_SEED, USDON_MANAGER_STATE_SEED},
+    errors::OndoError,
+    events::{RedemptionClaimDeferred, RedemptionClaimPaid, RedemptionClaimRerouted},
+    state::{RedemptionQueue, RoleType, Roles, USDonManagerState},
+};
+
+/// Create the redemption queue
+/// Requires `ADMIN_ROLE_USDON_MANAGER` role
+#[derive(Accounts)]
+pub struct InitializeRedemptionQueue<'info> {
+    /// The account with the authority to create the queue, pays for account creation
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The Roles account verifying the authority has the `ADMIN_ROLE_USDON_MANAGER` role
+    /// # PDA Seeds
+    /// - ADMIN_ROLE_USDON_MANAGER
+    /// - The authority's address
+    #[account(
+        seeds = [RoleType::ADMIN_ROLE_USDON_MANAGER, authority.key().as_ref()],
+        bump = authority_role_account.bump,
+    )]
+    pub authority_role_account: Account<'info, Roles>,
+
+    /// The RedemptionQueue account being created
+    /// # PDA Seeds
+    /// - REDEMPTION_QUEUE_SEED
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + RedemptionQueue::INIT_SPACE,
+        seeds = [REDEMPTION_QUEUE_SEED],
+        bump,
+    )]
+    pub redemption_queue: Account<'info, RedemptionQueue>,
+
+    /// The system program
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializeRedemptionQueue<'info> {
+    /// Create an empty redemption queue
+    /// # Arguments
+    /// * `bump` - The bump of the redemption queue PDA
+    /// # Returns
+    /// * `Result<()>` - Ok if the queue is created, Err otherwise
+    pub fn initialize_redemption_queue(&mut self, bump: u8) -> Result<()> {
+        self.redemption_queue.set_inner(RedemptionQueue {
+            next_claim_id: 0,
+            total_owed: 0,
+            claims: vec![],
+            bump,
+        });
+
+        Ok(())
+    }
+}
+
+/// Pay queued redemption claims out of the USDC vault, oldest first
+///
+/// The USDC token accounts of the claims are passed via remaining_accounts, constraints:
+/// 1. Accounts must be marked writable
+/// 2. Accounts must be in queue order, starting with the oldest claim
+///
+/// A claim whose token account cannot receive the payment blocks the claims behind it
+/// until the guardian re-routes it with `reroute_redemption_claim` or moves it to the back
+/// of the queue with `defer_redemption_claim`.
+#[derive(Accounts)]
+pub struct ProcessRedemptionQueue<'info> {
+    /// The account processing the queue
+    pub cranker: Signer<'info>,
+
+    /// The USDonManagerState account used as authority of the USDC vault
+    /// Releases the USDC reserved for the claims it pays
+    /// # PDA Seeds
+    /// - USDON_MANAGER_STATE_SEED
+    #[account(
+        mut,
+        seeds = [USDON_MANAGER_STATE_SEED],
+        bump = usdon_manager_state.bump,
+    )]
+    pub usdon_manager_state: Box<Account<'info, USDonManagerState>>,
+
+    /// The redemption queue
+    /// # PDA Seeds
+    /// - REDEMPTION_QUEUE_SEED
+    #[account(
+        mut,
+        seeds = [REDEMPTION_QUEUE_SEED],
+        bump = redemption_queue.bump,
+    )]
+    pub redemption_queue: Box<Account<'info, RedemptionQueue>>,
+
+    /// The USDC vault the claims are paid from
+    #[account(
+        mut,
+        address = usdon_manager_state.usdc_vault,
+        token::mint = usdc_mint,
+        token::token_program = spl_token_program,
+    )]
+    pub usdc_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The USDC mint (SPL Token)
+    #[account(
+        mint::token_program = spl_token_program,
+    )]
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The legacy SPL Token program
+    pub spl_token_program: Program<'info, Token>,
+}
+
+impl<'info> ProcessRedemptionQueue<'info> {
+    /// Pay as many queued claims as the USDC vault and the provided accounts allow.
+    /// The oldest claim is paid in part if the vault cannot cover it in full.
+    /// # Arguments
+    /// * `remaining_accounts` - The USDC token accounts of the claims, in queue order
+    /// # Returns
+    /// * `Result<()>` - Ok if the claims are paid, Err otherwise
+    /// # Errors
+    /// * `OndoError::InvalidTokenAccount` - If an account does not match the claim it pays
+    pub fn process_redemption_queue(
+        &mut self,
+        remaining_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        let mut available = self.usdc_vault.amount;
+
+        for destination in remaining_accounts.iter() {
+            let Some((claim, paid)) = self
+                .redemption_queue
+                .pay_front(&mut self.usdon_manager_state.queued_usdc, available)?
+            else {
+                break;
+            };
+
+            require_keys_eq!(
+                destination.key(),
+                claim.destination,
+                OndoError::InvalidTokenAccount
+            );
+
+            transfer_checked(
+                CpiContext::new_with_signer(
+                    self.spl_token_program.to_account_info(),
+                    TransferChecked {
+                        from: self.usdc_vault.to_account_info(),
+                        mint: self.usdc_mint.to_account_info(),
+                        to: destination.clone(),
+                        authority: self.usdon_manager_state.to_account_info(),
+                    },
+                    &[&[USDON_MANAGER_STATE_SEED, &[self.usdon_manager_state.bump]]],
+                ),
+                paid,
+                self.usdc_mint.decimals,
+            )?;
+
+            available -= paid;
+
+            emit!(RedemptionClaimPaid {
+                claim_id: claim.id,
+                user: claim.user,
+                amount: paid,
+                remaining: claim.amount - paid,
+                cranker: self.cranker.key(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Re-route a queued redemption claim to another USDC token account of its user
+/// Requires `GUARDIAN_USDON` role
+#[derive(Accounts)]
+pub struct RerouteRedemptionClaim<'info> {
+    /// The account with the authority to re-route the claim
+    pub guardian: Signer<'info>,
+
+    /// The Roles account verifying the guardian has the `GUARDIAN_USDON` role
+    /// # PDA Seeds
+    /// - GUARDIAN_USDON
+    /// - The guardian's address
+    #[account(
+        seeds = [RoleType::GUARDIAN_USDON, guardian.key().as_ref()],
+        bump = guardian_role_account.bump,
+    )]
+    pub guardian_role_account: Account<'info, Roles>,
+
+    /// The USDonManagerState account holding the USDC vault address
+    /// # PDA Seeds
+    /// - USDON_MANAGER_STATE_SEED
+    #[account(
+        seeds = [USDON_MANAGER_STATE_SEED],
+        bump = usdon_manager_state.bump,
+    )]
+    pub usdon_manager_state: Box<Account<'info, USDonManagerState>>,
+
+    /// The redemption queue
+    /// # PDA Seeds
+    /// - REDEMPTION_QUEUE_SEED
+    #[account(
+        mut,
+        seeds = [REDEMPTION_QUEUE_SEED],
+        bump = redemption_queue.bump,
+    )]
+    pub redemption_queue: Box<Account<'info, RedemptionQueue>>,
+
+    /// The USDC vault, only used to check the mint of the new destination
+    #[account(address = usdon_manager_state.usdc_vault)]
+    pub usdc_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The USDC token account the claim is paid to from now on
+    /// Must be owned by the user of the claim
+    #[account(
+        constraint = destination.mint == usdc_vault.mint @ OndoError::InvalidTokenAccount,
+    )]
+    pub destination: Box<InterfaceAccount<'info, TokenAccount>>,
+}
+
+impl<'info> RerouteRedemptionClaim<'info> {
+    /// Change the token account a claim is paid to, keeping its place in the queue
+    /// # Arguments
+    /// * `claim_id` - The ID of the claim
+    /// # Returns
+    /// * `Result<()>` - Ok if the claim is re-routed, Err otherwise
+    pub fn reroute_redemption_claim(&mut self, claim_id: u64) -> Result<()> {
+        let destination = self.destination.key();
+        let claim = self.redemption_queue.reroute(claim_id, destination)?;
+
+        // The claim can only be paid to its own user
+        require_keys_eq!(
+            self.destination.owner,
+            claim.user,
+            OndoError::InvalidTokenAccount
+        );
+
+        emit!(RedemptionClaimRerouted {
+            claim_id,
+            user: claim.user,
+            previous_destination: claim.destination,
+            destination,
+            guardian: self.guardian.key(),
+        });
+
+        Ok(())
+    }
+}
+
+/// Move the claim at the front of the redemption queue to the back
+/// Requires `GUARDIAN_USDON` role
+#[derive(Accounts)]
+pub struct DeferRedemptionClaim<'info> {
+    /// The account with the authority to defer the claim
+    pub guardian: Signer<'info>,
+
+    /// The Roles account verifying the guardian has the `GUARDIAN_USDON` role
+    /// # PDA Seeds
+    /// - GUARDIAN_USDON
+    /// - The guardian's address
+    #[account(
+        seeds = [RoleType::GUARDIAN_USDON, guardian.key().as_ref()],
+        bump = guardian_role_account.bump,
+    )]
+    pub guardian_role_account: Account<'info, Roles>,
+
+    /// The redemption queue
+    /// # PDA Seeds
+    /// - REDEMPTION_QUEUE_SEED
+    #[account(
+        mut,
+        seeds = [REDEMPTION_QUEUE_SEED],
+        bump = redemption_queue.bump,
+    )]
+    pub redemption_queue: Box<Account<'info, RedemptionQueue>>,
+}
+
+impl<'info> DeferRedemptionClaim<'info> {
+    /// Move the front claim to the back of the queue so the claims behind it can be paid.
+    /// The claim keeps the USDC it is owed
+    /// # Returns
+    /// * `Result<()>` - Ok if the claim is deferred, Err otherwise
+    pub fn defer_redemption_claim(&mut self) -> Result<()> {
+        let claim = self.redemption_queue.defer_front()?;
+
+        emit!(RedemptionClaimDeferred {
+            claim_id: claim.id,
+            user: claim.user,
+            guardian: self.guardian.key(),
+        });
+
+        Ok(())
+    }
+}
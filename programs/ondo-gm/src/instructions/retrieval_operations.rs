@@ -0,0 +1,558 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+use crate::{
+    constants::{
+        PENDING_RETRIEVAL_SEED, RETRIEVAL_DESTINATION_SEED, RETRIEVAL_LIMIT_SEED,
+        USDON_MANAGER_STATE_SEED,
+    },
+    errors::OndoError,
+    events::{
+        RetrievalCancelled, RetrievalDestinationSet, RetrievalLimitSet, RetrievalRequested,
+        TokensRetrieved,
+    },
+    state::{
+        PendingRetrieval, RetrievalDestination, RetrievalLimit, RoleType, Roles, USDonManagerState,
+    },
+};
+
+/// Allowlist a token account as a destination for `retrieve_tokens`
+/// Requires the `GUARDIAN_USDON` role
+#[derive(Accounts)]
+#[instruction(destination: Pubkey)]
+pub struct AddRetrievalDestination<'info> {
+    /// The account with the authority to allowlist the destination, pays for account creation
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The Roles account verifying the authority has the `GUARDIAN_USDON` role
+    /// # PDA Seeds
+    /// - GUARDIAN_USDON
+    /// - The authority's address
+    #[account(
+        seeds = [RoleType::GUARDIAN_USDON, authority.key().as_ref()],
+        bump = authority_role_account.bump,
+    )]
+    pub authority_role_account: Account<'info, Roles>,
+
+    /// The RetrievalDestination account being created
+    /// # PDA Seeds
+    /// - RETRIEVAL_DESTINATION_SEED
+    /// - The destination's address
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + RetrievalDestination::INIT_SPACE,
+        seeds = [RETRIEVAL_DESTINATION_SEED, destination.as_ref()],
+        bump,
+    )]
+    pub retrieval_destination: Account<'info, RetrievalDestination>,
+
+    /// The system program
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AddRetrievalDestination<'info> {
+    /// Allowlist a destination
+    /// # Arguments
+    /// * `destination` - The token account to allowlist
+    /// * `bump` - The bump of the RetrievalDestination PDA
+    /// # Returns
+    /// * `Result<()>` - Ok if the destination is allowlisted, Err otherwise
+    pub fn add_retrieval_destination(&mut self, destination: Pubkey, bump: u8) -> Result<()> {
+        self.retrieval_destination
+            .set_inner(RetrievalDestination { destination, bump });
+
+        emit!(RetrievalDestinationSet {
+            destination,
+            is_allowed: true,
+            authority: self.authority.key(),
+        });
+
+        Ok(())
+    }
+}
+
+/// Remove a token account from the `retrieve_tokens` destination allowlist
+/// Requires the `GUARDIAN_USDON` role
+#[derive(Accounts)]
+pub struct RemoveRetrievalDestination<'info> {
+    /// The account with the authority to remove the destination, receives the rent
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The Roles account verifying the authority has the `GUARDIAN_USDON` role
+    /// # PDA Seeds
+    /// - GUARDIAN_USDON
+    /// - The authority's address
+    #[account(
+        seeds = [RoleType::GUARDIAN_USDON, authority.key().as_ref()],
+        bump = authority_role_account.bump,
+    )]
+    pub authority_role_account: Account<'info, Roles>,
+
+    /// The RetrievalDestination account being closed
+    /// # PDA Seeds
+    /// - RETRIEVAL_DESTINATION_SEED
+    /// - The destination's address
+    #[account(
+        mut,
+        close = authority,
+        seeds = [RETRIEVAL_DESTINATION_SEED, retrieval_destination.destination.as_ref()],
+        bump = retrieval_destination.bump,
+    )]
+    pub retrieval_destination: Account<'info, RetrievalDestination>,
+}
+
+impl<'info> RemoveRetrievalDestination<'info> {
+    /// Remove a destination from the allowlist
+    /// # Returns
+    /// * `Result<()>` - Ok if the destination is removed, Err otherwise
+    pub fn remove_retrieval_destination(&mut self) -> Result<()> {
+        emit!(RetrievalDestinationSet {
+            destination: self.retrieval_destination.destination,
+            is_allowed: false,
+            authority: self.authority.key(),
+        });
+
+        Ok(())
+    }
+}
+
+/// Set the rolling withdrawal limit and timelock policy for retrieving a mint
+/// Requires the `GUARDIAN_USDON` role
+#[derive(Accounts)]
+pub struct SetRetrievalLimit<'info> {
+    /// The account with the authority to set the limit, pays for account creation
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The Roles account verifying the authority has the `GUARDIAN_USDON` role
+    /// # PDA Seeds
+    /// - GUARDIAN_USDON
+    /// - The authority's address
+    #[account(
+        seeds = [RoleType::GUARDIAN_USDON, authority.key().as_ref()],
+        bump = authority_role_account.bump,
+    )]
+    pub authority_role_account: Account<'info, Roles>,
+
+    /// The mint the limit applies to
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    /// The RetrievalLimit account of the mint
+    /// # PDA Seeds
+    /// - RETRIEVAL_LIMIT_SEED
+    /// - The mint's address
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + RetrievalLimit::INIT_SPACE,
+        seeds = [RETRIEVAL_LIMIT_SEED, token_mint.key().as_ref()],
+        bump,
+    )]
+    pub retrieval_limit: Account<'info, RetrievalLimit>,
+
+    /// The system program
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetRetrievalLimit<'info> {
+    /// Set the withdrawal limit of the mint, keeping the amount already retrieved in the window
+    /// # Arguments
+    /// * `rate_limit` - The maximum amount that can be retrieved in the limit window
+    /// * `limit_window` - The limit window in seconds (must be > 0)
+    /// * `timelock_threshold` - Retrievals above this amount must be timelocked, 0 disables the timelock
+    /// * `timelock_delay` - The timelock delay in seconds (must be > 0 when the timelock is enabled)
+    /// * `bump` - The bump of the RetrievalLimit PDA
+    /// # Returns
+    /// * `Result<()>` - Ok if the limit is successfully set, Err otherwise
+    pub fn set_retrieval_limit(
+        &mut self,
+        rate_limit: u64,
+        limit_window: u64,
+        timelock_threshold: u64,
+        timelock_delay: u64,
+        bump: u8,
+    ) -> Result<()> {
+        require_gt!(limit_window, 0, OndoError::InvalidRateLimit);
+
+        if timelock_threshold != 0 {
+            require_gt!(timelock_delay, 0, OndoError::InvalidRetrievalTimelock);
+        }
+
+        let limit = &mut self.retrieval_limit;
+        limit.mint = self.token_mint.key();
+        limit.rate_limit = rate_limit;
+        limit.limit_window = limit_window;
+        limit.timelock_threshold = timelock_threshold;
+        limit.timelock_delay = timelock_delay;
+        limit.bump = bump;
+
+        emit!(RetrievalLimitSet {
+            token: self.token_mint.key(),
+            rate_limit,
+            limit_window,
+            timelock_threshold,
+            timelock_delay,
+            authority: self.authority.key(),
+        });
+
+        Ok(())
+    }
+}
+
+/// Request a retrieval above the timelock threshold of its mint
+/// Requires `ADMIN_ROLE_USDON_MANAGER` role
+#[derive(Accounts)]
+#[instruction(request_id: u64)]
+pub struct RequestRetrieval<'info> {
+    /// The account with the authority to request the retrieval, pays for account creation
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The Roles account verifying the authority has the `ADMIN_ROLE_USDON_MANAGER` role
+    /// # PDA Seeds
+    /// - ADMIN_ROLE_USDON_MANAGER
+    /// - The authority's address
+    #[account(
+        seeds = [RoleType::ADMIN_ROLE_USDON_MANAGER, authority.key().as_ref()],
+        bump = authority_role_account.bump,
+    )]
+    pub authority_role_account: Account<'info, Roles>,
+
+    /// The mint of the token being retrieved
+    #[account(
+        mint::token_program = token_program,
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    /// The destination token account to receive the tokens
+    #[account(
+        token::mint = token_mint,
+        token::token_program = token_program,
+    )]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+
+    /// The RetrievalDestination account allowlisting the destination
+    /// # PDA Seeds
+    /// - RETRIEVAL_DESTINATION_SEED
+    /// - The destination's address
+    #[account(
+        seeds = [RETRIEVAL_DESTINATION_SEED, destination.key().as_ref()],
+        bump = retrieval_destination.bump,
+    )]
+    pub retrieval_destination: Account<'info, RetrievalDestination>,
+
+    /// The RetrievalLimit account of the mint
+    /// # PDA Seeds
+    /// - RETRIEVAL_LIMIT_SEED
+    /// - The mint's address
+    #[account(
+        seeds = [RETRIEVAL_LIMIT_SEED, token_mint.key().as_ref()],
+        bump = retrieval_limit.bump,
+    )]
+    pub retrieval_limit: Account<'info, RetrievalLimit>,
+
+    /// The PendingRetrieval account being created
+    /// # PDA Seeds
+    /// - PENDING_RETRIEVAL_SEED
+    /// - The mint's address
+    /// - The destination's address
+    /// - Request ID (little endian)
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PendingRetrieval::INIT_SPACE,
+        seeds = [
+            PENDING_RETRIEVAL_SEED,
+            token_mint.key().as_ref(),
+            destination.key().as_ref(),
+            &request_id.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub pending_retrieval: Account<'info, PendingRetrieval>,
+
+    /// The token program (SPL Token or Token-2022)
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The system program
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RequestRetrieval<'info> {
+    /// Request a timelocked retrieval, executable once the timelock delay of the mint has passed
+    /// # Arguments
+    /// * `request_id` - The ID of the request, unique per mint and destination
+    /// * `amount` - The amount of tokens to retrieve
+    /// * `bump` - The bump of the PendingRetrieval PDA
+    /// # Returns
+    /// * `Result<()>` - Ok if the retrieval is requested, Err otherwise
+    /// # Errors
+    /// * `OndoError::InvalidRateLimit` - If the amount exceeds the withdrawal limit, so it could never execute
+    pub fn request_retrieval(&mut self, request_id: u64, amount: u64, bump: u8) -> Result<()> {
+        require_gt!(amount, 0, OndoError::InvalidAmount);
+        require_gte!(
+            self.retrieval_limit.rate_limit,
+            amount,
+            OndoError::InvalidRateLimit
+        );
+
+        let executable_at = Clock::get()?
+            .unix_timestamp
+            .checked_add(
+                i64::try_from(self.retrieval_limit.timelock_delay)
+                    .map_err(|_| OndoError::MathOverflow)?,
+            )
+            .ok_or(OndoError::MathOverflow)?;
+
+        self.pending_retrieval.set_inner(PendingRetrieval {
+            request_id,
+            mint: self.token_mint.key(),
+            destination: self.destination.key(),
+            amount,
+            requested_by: self.authority.key(),
+            executable_at,
+            bump,
+        });
+
+        emit!(RetrievalRequested {
+            request_id,
+            token: self.token_mint.key(),
+            to: self.destination.key(),
+            amount,
+            executable_at,
+            authority: self.authority.key(),
+        });
+
+        Ok(())
+    }
+}
+
+/// Execute a timelocked retrieval once its timelock delay has passed
+/// Requires `ADMIN_ROLE_USDON_MANAGER` role
+#[derive(Accounts)]
+pub struct ExecuteRetrieval<'info> {
+    /// The account with the authority to execute the retrieval, receives the rent
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The Roles account verifying the authority has the `ADMIN_ROLE_USDON_MANAGER` role
+    /// # PDA Seeds
+    /// - ADMIN_ROLE_USDON_MANAGER
+    /// - The authority's address
+    #[account(
+        seeds = [RoleType::ADMIN_ROLE_USDON_MANAGER, authority.key().as_ref()],
+        bump = authority_role_account.bump,
+    )]
+    pub authority_role_account: Account<'info, Roles>,
+
+    /// The USDonManagerState account used as authority for vault operations
+    /// # PDA Seeds
+    /// - USDON_MANAGER_STATE_SEED
+    #[account(
+        seeds = [USDON_MANAGER_STATE_SEED],
+        bump = usdon_manager_state.bump,
+    )]
+    pub usdon_manager_state: Account<'info, USDonManagerState>,
+
+    /// The mint of the token being retrieved
+    #[account(
+        address = pending_retrieval.mint,
+        mint::token_program = token_program,
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    /// The source vault token account (must be owned by usdon_manager_state)
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = usdon_manager_state,
+        associated_token::token_program = token_program,
+    )]
+    pub source_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// The destination token account to receive the tokens
+    #[account(
+        mut,
+        address = pending_retrieval.destination,
+        token::mint = token_mint,
+        token::token_program = token_program,
+    )]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+
+    /// The RetrievalDestination account, the destination must still be allowlisted
+    /// # PDA Seeds
+    /// - RETRIEVAL_DESTINATION_SEED
+    /// - The destination's address
+    #[account(
+        seeds = [RETRIEVAL_DESTINATION_SEED, destination.key().as_ref()],
+        bump = retrieval_destination.bump,
+    )]
+    pub retrieval_destination: Account<'info, RetrievalDestination>,
+
+    /// The RetrievalLimit account of the mint
+    /// # PDA Seeds
+    /// - RETRIEVAL_LIMIT_SEED
+    /// - The mint's address
+    #[account(
+        mut,
+        seeds = [RETRIEVAL_LIMIT_SEED, token_mint.key().as_ref()],
+        bump = retrieval_limit.bump,
+    )]
+    pub retrieval_limit: Account<'info, RetrievalLimit>,
+
+    /// The PendingRetrieval account being executed and closed
+    /// # PDA Seeds
+    /// - PENDING_RETRIEVAL_SEED
+    /// - The mint's address
+    /// - The destination's address
+    /// - Request ID (little endian)
+    #[account(
+        mut,
+        close = authority,
+        seeds = [
+            PENDING_RETRIEVAL_SEED,
+            pending_retrieval.mint.as_ref(),
+            pending_retrieval.destination.as_ref(),
+            &pending_retrieval.request_id.to_le_bytes(),
+        ],
+        bump = pending_retrieval.bump,
+    )]
+    pub pending_retrieval: Account<'info, PendingRetrieval>,
+
+    /// The token program (SPL Token or Token-2022)
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> ExecuteRetrieval<'info> {
+    /// Execute the retrieval, consuming the withdrawal limit of the mint
+    /// # Returns
+    /// * `Result<()>` - Ok if the tokens are successfully retrieved, Err otherwise
+    /// # Errors
+    /// * `OndoError::RetrievalNotExecutable` - If the timelock delay has not passed
+    /// * `OndoError::InvalidRateLimit` - If the amount exceeds the withdrawal limit
+    pub fn execute_retrieval(&mut self) -> Result<()> {
+        let current_timestamp = Clock::get()?.unix_timestamp;
+        let amount = self.pending_retrieval.amount;
+
+        require_gte!(
+            current_timestamp,
+            self.pending_retrieval.executable_at,
+            OndoError::RetrievalNotExecutable
+        );
+
+        self.retrieval_limit
+            .record_retrieval(amount, current_timestamp)?;
+
+        transfer_from_vault(
+            &self.token_program,
+            &self.usdon_manager_state,
+            &self.source_vault,
+            &self.token_mint,
+            &self.destination,
+            amount,
+        )?;
+
+        emit!(TokensRetrieved {
+            token: self.token_mint.key(),
+            to: self.destination.key(),
+            amount,
+            authority: self.authority.key(),
+        });
+
+        Ok(())
+    }
+}
+
+/// Cancel a timelocked retrieval
+/// Requires the `GUARDIAN_USDON` role
+#[derive(Accounts)]
+pub struct CancelRetrieval<'info> {
+    /// The account with the authority to cancel the retrieval, receives the rent
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The Roles account verifying the authority has the `GUARDIAN_USDON` role
+    /// # PDA Seeds
+    /// - GUARDIAN_USDON
+    /// - The authority's address
+    #[account(
+        seeds = [RoleType::GUARDIAN_USDON, authority.key().as_ref()],
+        bump = authority_role_account.bump,
+    )]
+    pub authority_role_account: Account<'info, Roles>,
+
+    /// The PendingRetrieval account being cancelled and closed
+    /// # PDA Seeds
+    /// - PENDING_RETRIEVAL_SEED
+    /// - The mint's address
+    /// - The destination's address
+    /// - Request ID (little endian)
+    #[account(
+        mut,
+        close = authority,
+        seeds = [
+            PENDING_RETRIEVAL_SEED,
+            pending_retrieval.mint.as_ref(),
+            pending_retrieval.destination.as_ref(),
+            &pending_retrieval.request_id.to_le_bytes(),
+        ],
+        bump = pending_retrieval.bump,
+    )]
+    pub pending_retrieval: Account<'info, PendingRetrieval>,
+}
+
+impl<'info> CancelRetrieval<'info> {
+    /// Cancel the retrieval
+    /// # Returns
+    /// * `Result<()>` - Ok if the retrieval is cancelled, Err otherwise
+    pub fn cancel_retrieval(&mut self) -> Result<()> {
+        emit!(RetrievalCancelled {
+            request_id: self.pending_retrieval.request_id,
+            token: self.pending_retrieval.mint,
+            to: self.pending_retrieval.destination,
+            amount: self.pending_retrieval.amount,
+            authority: self.authority.key(),
+        });
+
+        Ok(())
+    }
+}
+
+/// Transfers `amount` from a vault owned by the USDon manager, signing as the USDon manager.
+/// USDC owed to queued redemption claims cannot be retrieved from the USDC vault
+pub(crate) fn transfer_from_vault<'info>(
+    token_program: &Interface<'info, TokenInterface>,
+    usdon_manager_state: &Account<'info, USDonManagerState>,
+    source_vault: &InterfaceAccount<'info, TokenAccount>,
+    token_mint: &InterfaceAccount<'info, Mint>,
+    destination: &InterfaceAccount<'info, TokenAccount>,
+    amount: u64,
+) -> Result<()> {
+    if source_vault.key() == usdon_manager_state.usdc_vault {
+        require_gte!(
+            usdon_manager_state.unreserved_usdc(source_vault.amount),
+            amount,
+            OndoError::InsufficientVaultLiquidity
+        );
+    }
+
+    transfer_checked(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            TransferChecked {
+                from: source_vault.to_account_info(),
+                mint: token_mint.to_account_info(),
+                to: destination.to_account_info(),
+                authority: usdon_manager_state.to_account_info(),
+            },
+            &[&[USDON_MANAGER_STATE_SEED, &[usdon_manager_state.bump]]],
+        ),
+        amount,
+        token_mint.decimals,
+    )
+}
@@ -0,0 +1,354 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use spl_token_2022::{
+    extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions},
+    state::Mint as Token2022Mint,
+};
+
+use crate::{
+    constants::{SETTLEMENT_ASSET_SEED, USDON_MANAGER_STATE_SEED},
+    errors::OndoError,
+    events::SettlementAssetSet,
+    state::{OracleAdapter, RoleType, Roles, SettlementAsset, USDonManagerState},
+};
+
+/// Register a stablecoin that mints and redemptions can settle in
+/// Requires `ADMIN_ROLE_USDON_MANAGER` role
+#[derive(Accounts)]
+pub struct AddSettlementAsset<'info> {
+    /// The account with the authority to register the asset, pays for account creation
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The Roles account verifying the authority has the `ADMIN_ROLE_USDON_MANAGER` role
+    /// # PDA Seeds
+    /// - ADMIN_ROLE_USDON_MANAGER
+    /// - The authority's address
+    #[account(
+        seeds = [RoleType::ADMIN_ROLE_USDON_MANAGER, authority.key().as_ref()],
+        bump = authority_role_account.bump,
+    )]
+    pub authority_role_account: Account<'info, Roles>,
+
+    /// The USDonManagerState account owning the asset's vault
+    /// # PDA Seeds
+    /// - USDON_MANAGER_STATE_SEED
+    #[account(
+        seeds = [USDON_MANAGER_STATE_SEED],
+        bump = usdon_manager_state.bump,
+    )]
+    pub usdon_manager_state: Account<'info, USDonManagerState>,
+
+    /// The USDC vault, identifying the USDC mint
+    #[account(address = usdon_manager_state.usdc_vault @ OndoError::InvalidVault)]
+    pub usdc_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// The mint of the settlement asset
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// The vault holding the asset
+    /// Must be the ATA for `mint` owned by `usdon_manager_state`
+    #[account(
+        associated_token::mint = mint,
+        associated_token::authority = usdon_manager_state,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// The SettlementAsset account being created
+    /// # PDA Seeds
+    /// - SETTLEMENT_ASSET_SEED
+    /// - Mint address
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + SettlementAsset::INIT_SPACE,
+        seeds = [SETTLEMENT_ASSET_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub settlement_asset: Account<'info, SettlementAsset>,
+
+    /// The token program of the asset (SPL Token or Token-2022)
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The system program
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AddSettlementAsset<'info> {
+    /// Register the settlement asset
+    /// # Arguments
+    /// * `priced_at_par` - Whether the asset is exchanged at par without an oracle check,
+    ///   the oracle configuration must then be left unset
+    /// * `price_update` - The oracle price account
+    /// * `oracle_adapter` - The kind of oracle account `price_update` is
+    /// * `price_update_owner` - The owner of the price update account, default pubkey for the adapter's default owner
+    /// * `feed_id` - The Pyth price feed ID, unused by other oracle adapters
+    /// * `oracle_max_age` - The maximum age of an oracle price in seconds
+    /// * `confidence_threshold_bps` - The maximum oracle confidence interval, in basis points of the price
+    /// * `min_price` - The depeg floor, scaled by USDC_PRICE_DECIMALS
+    /// * `max_price` - The depeg ceiling, scaled by USDC_PRICE_DECIMALS
+    /// * `bump` - The bump of the settlement asset PDA
+    /// # Returns
+    /// * `Result<()>` - Ok if the asset is registered, Err otherwise
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_settlement_asset(
+        &mut self,
+        priced_at_par: bool,
+        price_update: Pubkey,
+        oracle_adapter: OracleAdapter,
+        price_update_owner: Pubkey,
+        feed_id: [u8; 32],
+        oracle_max_age: u64,
+        confidence_threshold_bps: u64,
+        min_price: u64,
+        max_price: u64,
+        bump: u8,
+    ) -> Result<()> {
+        // USDon is always accepted and never goes through a vault swap
+        require_keys_neq!(
+            self.mint.key(),
+            self.usdon_manager_state.usdon_mint,
+            OndoError::InvalidSettlementAsset
+        );
+
+        // USDC settles through the USDC leg, with its redemption queue, shortfall mode
+        // and oracle conversion
+        require_keys_neq!(
+            self.mint.key(),
+            self.usdc_vault.mint,
+            OndoError::InvalidSettlementAsset
+        );
+
+        #[cfg(any(feature = "mainnet", feature = "testnet"))]
+        require_keys_neq!(
+            self.mint.key(),
+            crate::constants::USDC_MINT,
+            OndoError::InvalidSettlementAsset
+        );
+
+        validate_mint_extensions(&self.mint.to_account_info())?;
+
+        self.settlement_asset.set_inner(SettlementAsset {
+            mint: self.mint.key(),
+            token_program: self.token_program.key(),
+            vault: self.vault.key(),
+            priced_at_par,
+            price_update,
+            price_update_owner,
+            feed_id,
+            oracle_max_age,
+            confidence_threshold_bps,
+            min_price,
+            max_price,
+            oracle_adapter,
+            enabled: true,
+            bump,
+        });
+
+        self.settlement_asset.validate_oracle_config()?;
+
+        emit_settlement_asset_set(&self.settlement_asset, self.authority.key());
+
+        Ok(())
+    }
+}
+
+/// Token-2022 extensions a settlement asset mint may carry. Swaps credit and debit the vault
+/// by the transferred amount at the asset's price, so any extension changing what arrives in
+/// the vault (transfer fees, hooks, confidential transfers), what a unit is worth
+/// (scaled or interest-bearing amounts) or who can move vault funds (permanent delegate)
+/// is rejected.
+const ALLOWED_MINT_EXTENSIONS: [ExtensionType; 7] = [
+    ExtensionType::MetadataPointer,
+    ExtensionType::TokenMetadata,
+    ExtensionType::GroupPointer,
+    ExtensionType::TokenGroup,
+    ExtensionType::GroupMemberPointer,
+    ExtensionType::TokenGroupMember,
+    ExtensionType::Pausable,
+];
+
+/// Checks that a Token-2022 settlement asset mint only carries allowed extensions
+/// # Arguments
+/// * `mint` - The mint account of the settlement asset
+/// # Returns
+/// * `Result<()>` - Ok if the mint is an SPL Token mint or only has allowed extensions, Err otherwise
+fn validate_mint_extensions(mint: &AccountInfo) -> Result<()> {
+    if *mint.owner != spl_token_2022::ID {
+        return Ok(());
+    }
+
+    let data = mint.try_borrow_data()?;
+    let mint = StateWithExtensions::<Token2022Mint>::unpack(&data)?;
+    for extension in mint.get_extension_types()? {
+        require!(
+            ALLOWED_MINT_EXTENSIONS.contains(&extension),
+            OndoError::UnsupportedMintExtension
+        );
+    }
+
+    Ok(())
+}
+
+/// Admin operations for a registered settlement asset
+/// Requires `ADMIN_ROLE_USDON_MANAGER` role
+/// Allows updating the oracle configuration and enabling/disabling the asset
+#[derive(Accounts)]
+pub struct SettlementAssetAdmin<'info> {
+    /// The account with the authority to execute the operation
+    pub authority: Signer<'info>,
+
+    /// The Roles account verifying the authority has the `ADMIN_ROLE_USDON_MANAGER` role
+    /// # PDA Seeds
+    /// - ADMIN_ROLE_USDON_MANAGER
+    /// - The authority's address
+    #[account(
+        seeds = [RoleType::ADMIN_ROLE_USDON_MANAGER, authority.key().as_ref()],
+        bump = authority_role_account.bump,
+    )]
+    pub authority_role_account: Account<'info, Roles>,
+
+    /// The SettlementAsset account to be modified
+    /// # PDA Seeds
+    /// - SETTLEMENT_ASSET_SEED
+    /// - Mint address
+    #[account(
+        mut,
+        seeds = [SETTLEMENT_ASSET_SEED, settlement_asset.mint.as_ref()],
+        bump = settlement_asset.bump,
+    )]
+    pub settlement_asset: Account<'info, SettlementAsset>,
+}
+
+impl<'info> SettlementAssetAdmin<'info> {
+    /// Set the oracle configuration of the asset
+    /// # Arguments
+    /// * `priced_at_par` - Whether the asset is exchanged at par without an oracle check,
+    ///   the oracle configuration must then be left unset
+    /// * `price_update` - The oracle price account
+    /// * `oracle_adapter` - The kind of oracle account `price_update` is
+    /// * `price_update_owner` - The owner of the price update account, default pubkey for the adapter's default owner
+    /// * `feed_id` - The Pyth price feed ID, unused by other oracle adapters
+    /// * `oracle_max_age` - The maximum age of an oracle price in seconds
+    /// * `confidence_threshold_bps` - The maximum oracle confidence interval, in basis points of the price
+    /// * `min_price` - The depeg floor, scaled by USDC_PRICE_DECIMALS
+    /// * `max_price` - The depeg ceiling, scaled by USDC_PRICE_DECIMALS
+    /// # Returns
+    /// * `Result<()>` - Ok if the configuration is updated, Err otherwise
+    #[allow(clippy::too_many_arguments)]
+    pub fn set_settlement_asset_oracle(
+        &mut self,
+        priced_at_par: bool,
+        price_update: Pubkey,
+        oracle_adapter: OracleAdapter,
+        price_update_owner: Pubkey,
+        feed_id: [u8; 32],
+        oracle_max_age: u64,
+        confidence_threshold_bps: u64,
+        min_price: u64,
+        max_price: u64,
+    ) -> Result<()> {
+        self.settlement_asset.priced_at_par = priced_at_par;
+        self.settlement_asset.price_update = price_update;
+        self.settlement_asset.oracle_adapter = oracle_adapter;
+        self.settlement_asset.price_update_owner = price_update_owner;
+        self.settlement_asset.feed_id = feed_id;
+        self.settlement_asset.oracle_max_age = oracle_max_age;
+        self.settlement_asset.confidence_threshold_bps = confidence_threshold_bps;
+        self.settlement_asset.min_price = min_price;
+        self.settlement_asset.max_price = max_price;
+
+        self.settlement_asset.validate_oracle_config()?;
+
+        emit_settlement_asset_set(&self.settlement_asset, self.authority.key());
+
+        Ok(())
+    }
+
+    /// Enable or disable settlement in the asset
+    /// # Arguments
+    /// * `enabled` - Whether mints and redemptions may settle in the asset
+    /// # Returns
+    /// * `Result<()>` - Ok if the state is updated, Err otherwise
+    pub fn set_settlement_asset_enabled(&mut self, enabled: bool) -> Result<()> {
+        self.settlement_asset.enabled = enabled;
+
+        emit_settlement_asset_set(&self.settlement_asset, self.authority.key());
+
+        Ok(())
+    }
+}
+
+fn emit_settlement_asset_set(settlement_asset: &SettlementAsset, authority: Pubkey) {
+    emit!(SettlementAssetSet {
+        mint: settlement_asset.mint,
+        vault: settlement_asset.vault,
+        priced_at_par: settlement_asset.priced_at_par,
+        price_update: settlement_asset.price_update,
+        price_update_owner: settlement_asset.price_update_owner,
+        oracle_max_age: settlement_asset.oracle_max_age,
+        confidence_threshold_bps: settlement_asset.confidence_threshold_bps,
+        min_price: settlement_asset.min_price,
+        max_price: settlement_asset.max_price,
+        enabled: settlement_asset.enabled,
+        authority,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use spl_token_2022::{
+        extension::{
+            metadata_pointer::MetadataPointer, transfer_fee::TransferFeeConfig,
+            BaseStateWithExtensionsMut, StateWithExtensionsMut,
+        },
+        pod::PodMint,
+    };
+
+    /// Returns the data of an initialized Token-2022 mint carrying `extension`,
+    /// initialized by `init`
+    fn create_test_mint_data(
+        extension: ExtensionType,
+        init: impl FnOnce(&mut StateWithExtensionsMut<Token2022Mint>),
+    ) -> Vec<u8> {
+        let len = ExtensionType::try_calculate_account_len::<PodMint>(&[extension]).unwrap();
+        let mut data = vec![0u8; len];
+        let mut mint =
+            StateWithExtensionsMut::<Token2022Mint>::unpack_uninitialized(&mut data).unwrap();
+        init(&mut mint);
+        mint.base.decimals = 6;
+        mint.base.is_initialized = true;
+        mint.pack_base();
+        mint.init_account_type().unwrap();
+        data
+    }
+
+    fn validate(data: &mut [u8], owner: &Pubkey) -> Result<()> {
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mint = AccountInfo::new(&key, false, false, &mut lamports, data, owner, false, 0);
+        validate_mint_extensions(&mint)
+    }
+
+    #[test]
+    fn test_mint_extensions_must_be_allowed() {
+        let mut metadata_mint = create_test_mint_data(ExtensionType::MetadataPointer, |mint| {
+            mint.init_extension::<MetadataPointer>(true).unwrap();
+        });
+        assert!(validate(&mut metadata_mint, &spl_token_2022::ID).is_ok());
+
+        let mut fee_mint = create_test_mint_data(ExtensionType::TransferFeeConfig, |mint| {
+            mint.init_extension::<TransferFeeConfig>(true).unwrap();
+        });
+        assert_eq!(
+            validate(&mut fee_mint, &spl_token_2022::ID).unwrap_err(),
+            OndoError::UnsupportedMintExtension.into()
+        );
+
+        // SPL Token mints have no extensions
+        assert!(validate(&mut fee_mint, &anchor_spl::token::ID).is_ok());
+    }
+}
@@ -2,27 +2,33 @@ use anchor_lang::prelude::*;
 use anchor_lang::solana_program::sysvar::instructions::ID as INSTRUCTIONS_ID;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::Token,
     token_interface::{Mint, TokenAccount, TokenInterface},
 };
 
 use super::TokenManager;
-#[cfg(any(feature = "mainnet", feature = "testnet"))]
-use crate::constants::USDC_MINT;
 use crate::{
     constants::{
         ATTESTATION_ID_SEED, GMTOKEN_MANAGER_STATE_SEED, MINT_AUTHORITY_SEED, ONDO_USER_SEED,
-        ORACLE_SANITY_CHECK_SEED, TOKEN_LIMIT_ACCOUNT_SEED, USDON_MANAGER_STATE_SEED,
+        ORACLE_SANITY_CHECK_SEED, REDEMPTION_QUEUE_SEED, SETTLEMENT_ASSET_SEED,
+        TOKEN_LIMIT_ACCOUNT_SEED, USDON_MANAGER_STATE_SEED, USER_NOTIONAL_LIMIT_SEED,
         WHITELIST_SEED,
     },
-    state::{GMTokenManagerState, OndoUser, OracleSanityCheck, TokenLimit, USDonManagerState},
+    state::{
+        GMTokenManagerState, LimitGroup, LimitProfile, OndoUser, OracleSanityCheck,
+        RedemptionQueue, RoleType, Roles, SettlementAsset, TokenLimit, USDonManagerState,
+        UserNotionalLimit,
+    },
 };
 
+/// Mint or redeem GM Tokens settling in USDon, USDC or any registered settlement asset.
+/// The settlement accounts form the non-USDon leg of the swap: they must match the registered
+/// asset when `settlement_asset` is provided, and the USDC configuration of the USDonManagerState
+/// otherwise. The instruction decides whether the leg is used.
 #[event_cpi]
 #[derive(Accounts)]
 #[instruction(attestation_id: [u8; 16])]
-pub struct USDCSwapContext<'info> {
-    /// The user performing the USDC swap, pays for account creation if needed
+pub struct SettlementSwapContext<'info> {
+    /// The user performing the swap, pays for account creation if needed
     #[account(mut)]
     pub user: Signer<'info>,
 
@@ -59,6 +65,38 @@ pub struct USDCSwapContext<'info> {
     )]
     pub ondo_user: Box<Account<'info, OndoUser>>,
 
+    /// The user's `RateLimitExemptRole` account, skipping the per-user rate limit
+    /// Only provided for designated market makers
+    /// # PDA Seeds
+    /// - RATE_LIMIT_EXEMPT_ROLE
+    /// - User's address
+    #[account(
+        seeds = [RoleType::RATE_LIMIT_EXEMPT_ROLE, user.key().as_ref()],
+        bump = rate_limit_exemption.bump,
+    )]
+    pub rate_limit_exemption: Option<Box<Account<'info, Roles>>>,
+
+    /// The LimitProfile the OndoUser is linked to
+    /// Required when the OndoUser is linked to a limit profile
+    pub limit_profile: Option<Box<Account<'info, LimitProfile>>>,
+
+    /// The limit group the OndoUser is attached to
+    /// Required when the OndoUser is attached to a limit group
+    #[account(mut)]
+    pub limit_group: Option<Box<Account<'info, LimitGroup>>>,
+
+    /// The user's notional across all GM Tokens
+    /// Required while the per-user notional limit is enabled
+    /// # PDA Seeds
+    /// - USER_NOTIONAL_LIMIT_SEED
+    /// - User's address
+    #[account(
+        mut,
+        seeds = [USER_NOTIONAL_LIMIT_SEED, user.key().as_ref()],
+        bump = user_notional_limit.bump,
+    )]
+    pub user_notional_limit: Option<Box<Account<'info, UserNotionalLimit>>>,
+
     /// The TokenLimit account enforcing mint/burn limits for the GM Token
     /// # PDA Seeds
     /// - TOKEN_LIMIT_ACCOUNT_SEED
@@ -125,27 +163,47 @@ pub struct USDCSwapContext<'info> {
     /// The associated token program
     pub associated_token_program: Program<'info, AssociatedToken>,
 
-    /// The legacy SPL Token program
-    /// Required when performing swaps between USDC and USDon tokens
-    pub spl_token_program: Program<'info, Token>,
+    /// The registered settlement asset, the leg settles in USDC if not provided
+    /// # PDA Seeds
+    /// - SETTLEMENT_ASSET_SEED
+    /// - Settlement asset mint address
+    #[account(
+        seeds = [SETTLEMENT_ASSET_SEED, settlement_asset.mint.as_ref()],
+        bump = settlement_asset.bump,
+    )]
+    pub settlement_asset: Option<Box<Account<'info, SettlementAsset>>>,
+
+    /// The token program of the settlement asset, the legacy SPL Token program for USDC
+    /// Required when not settling in USDon
+    pub settlement_token_program: Option<Interface<'info, TokenInterface>>,
+
+    /// The oracle price update account of the settlement asset or the primary USDC oracle
+    /// Required when not settling in USDon and the asset is priced by an oracle
+    /// CHECK: Validated against the address stored in `settlement_asset` or
+    /// `usdon_manager_state` before use.
+    pub settlement_price_update: Option<UncheckedAccount<'info>>,
 
-    /// The oracle price update account for USDC/USD price feed
-    /// Provides real-time price data for USDC token
-    /// Used in swap operations to calculate exchange rates
-    /// Must be recent (within oracle_price_max_age) to ensure price accuracy
-    /// CHECK: Constraint using `has_one` in usdon_manager_state.
-    /// `has_one` ensures that this account matches the expected oracle account stored in the `usdon_manager_state`.
-    pub usdc_price_update: UncheckedAccount<'info>,
+    /// The secondary oracle price update account for USDC/USD price feed
+    /// Required for USDC settlement when a secondary oracle is configured
+    /// CHECK: Address constraint against the account stored in `usdon_manager_state`.
+    #[account(address = usdon_manager_state.usdc_secondary_price_update)]
+    pub usdc_secondary_price_update: Option<UncheckedAccount<'info>>,
 
-    /// The USDC vault storing USDC tokens received from users during swaps
+    /// The redemption queue holding USDC owed to redemptions the USDC vault could not cover
+    /// Required for USDC payouts when the USDC vault shortfall mode is Queue
+    /// # PDA Seeds
+    /// - REDEMPTION_QUEUE_SEED
     #[account(
         mut,
-        associated_token::mint = usdc_mint,
-        associated_token::authority = usdon_manager_state,
-        associated_token::token_program = spl_token_program,
-        constraint = usdc_vault.key() == usdon_manager_state.usdc_vault
+        seeds = [REDEMPTION_QUEUE_SEED],
+        bump = redemption_queue.bump,
     )]
-    pub usdc_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub redemption_queue: Option<Box<Account<'info, RedemptionQueue>>>,
+
+    /// The vault holding the settlement asset, the USDC vault for USDC
+    /// Required when not settling in USDon
+    #[account(mut)]
+    pub settlement_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 
     /// The USDon vault storing USDon tokens received from users during swaps
     #[account(
@@ -157,23 +215,14 @@ pub struct USDCSwapContext<'info> {
     )]
     pub usdon_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    /// The USDC mint (SPL Token)
-    #[cfg(not(any(feature = "mainnet", feature = "testnet")))]
-    #[account(
-        mint::token_program = spl_token_program,
-    )]
-    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+    /// The mint of the settlement asset, the USDC mint for USDC
+    /// Required when not settling in USDon
+    pub settlement_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
 
-    #[cfg(any(feature = "mainnet", feature = "testnet"))]
-    #[account(
-        mint::token_program = spl_token_program,
-        constraint = usdc_mint.key() == USDC_MINT
-    )]
-    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
-
-    /// The user's USDC token account
+    /// The user's token account for the settlement asset
+    /// Required when not settling in USDon
     #[account(mut)]
-    pub user_usdc_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub user_settlement_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 
     /// The USDon mint (Token-2022)
     #[account(
@@ -188,12 +237,13 @@ pub struct USDCSwapContext<'info> {
     pub user_usdon_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The USDonManagerState account used as authority for vault operations
+    /// Tracks the USDC reserved for queued redemption claims
     /// # PDA Seeds
     /// - USDON_MANAGER_STATE_SEED
     #[account(
+        mut,
         seeds = [USDON_MANAGER_STATE_SEED],
         bump = usdon_manager_state.bump,
-        has_one = usdc_price_update
     )]
     pub usdon_manager_state: Box<Account<'info, USDonManagerState>>,
 
@@ -213,9 +263,9 @@ pub struct USDCSwapContext<'info> {
     pub instructions: UncheckedAccount<'info>,
 }
 
-impl<'info> USDCSwapContext<'info> {
+impl<'info> SettlementSwapContext<'info> {
     /// Creates a TokenManager instance from the current context.
-    /// This TokenManager facilitates token operations within the USDC swap context.
+    /// This TokenManager facilitates token operations within the settlement swap context.
     /// # Returns
     /// * `TokenManager` - A TokenManager instance with references to the relevant accounts.
     /// # Safety
@@ -223,11 +273,28 @@ impl<'info> USDCSwapContext<'info> {
     /// ensuring that the TokenManager can perform necessary operations safely.
     #[allow(clippy::wrong_self_convention)]
     pub fn into_token_manager(&mut self) -> TokenManager<'_, 'info> {
+        // Without a registered settlement asset the settlement accounts form the USDC leg
+        let is_usdc = self.settlement_asset.is_none();
+        let (usdc_vault, settlement_vault) =
+            split_leg(is_usdc, self.settlement_vault.as_deref_mut());
+        let (user_usdc_token_account, user_settlement_token_account) =
+            split_leg(is_usdc, self.user_settlement_token_account.as_deref_mut());
+        let (usdc_mint, settlement_mint) = split_leg(is_usdc, self.settlement_mint.as_deref());
+        let (spl_token_program, settlement_token_program) =
+            split_leg(is_usdc, self.settlement_token_program.as_deref());
+        let (usdc_price_update, settlement_price_update) =
+            split_leg(is_usdc, self.settlement_price_update.as_ref());
+
         TokenManager {
-            user: &mut self.user,
+            user: &self.user,
+            payer: &self.user,
             mint: &mut self.mint,
             mint_authority: &self.mint_authority,
             ondo_user: &mut self.ondo_user,
+            rate_limit_exemption: self.rate_limit_exemption.as_deref(),
+            limit_profile: self.limit_profile.as_deref(),
+            limit_group: self.limit_group.as_deref_mut(),
+            user_notional_limit: self.user_notional_limit.as_deref_mut(),
             token_limit_account: &mut self.token_limit_account,
             sanity_check_account: &mut self.sanity_check_account,
             user_token_account: &mut self.user_token_account,
@@ -236,17 +303,34 @@ impl<'info> USDCSwapContext<'info> {
             token_program: &self.token_program,
             system_program: &self.system_program,
             associated_token_program: &self.associated_token_program,
-            spl_token_program: Some(&self.spl_token_program),
-            usdc_price_update: Some(&self.usdc_price_update),
-            usdc_vault: Some(&mut self.usdc_vault),
+            spl_token_program,
+            usdc_price_update,
+            usdc_secondary_price_update: self.usdc_secondary_price_update.as_ref(),
+            redemption_queue: self.redemption_queue.as_deref_mut(),
+            usdc_vault,
+            usdc_mint,
+            user_usdc_token_account,
+            settlement_asset: self.settlement_asset.as_deref(),
+            settlement_token_program,
+            settlement_price_update,
+            settlement_vault,
+            settlement_mint,
+            user_settlement_token_account,
             usdon_vault: &mut self.usdon_vault,
-            usdc_mint: Some(&self.usdc_mint),
-            user_usdc_token_account: Some(&mut self.user_usdc_token_account),
             usdon_mint: &self.usdon_mint,
             user_usdon_token_account: &mut self.user_usdon_token_account,
-            usdon_manager_state: &self.usdon_manager_state,
+            usdon_manager_state: &mut self.usdon_manager_state,
             gmtoken_manager_state: &mut self.gmtoken_manager_state,
             instructions: &self.instructions,
         }
     }
 }
+
+/// Hands a settlement account to the USDC leg, or to the registered settlement asset leg
+fn split_leg<T>(is_usdc: bool, account: Option<T>) -> (Option<T>, Option<T>) {
+    if is_usdc {
+        (account, None)
+    } else {
+        (None, account)
+    }
+}
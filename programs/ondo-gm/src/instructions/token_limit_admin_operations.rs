@@ -1,11 +1,12 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{prelude::*, Discriminator};
 use anchor_spl::token_interface::Mint;
 
 use crate::{
-    constants::TOKEN_LIMIT_ACCOUNT_SEED,
+    constants::{MAX_TOKEN_LIMIT_TIERS, ONDO_USER_SEED, TOKEN_LIMIT_ACCOUNT_SEED},
     errors::OndoError,
-    events::RateLimitTokenSet,
-    state::{RoleType, Roles, TokenLimit},
+    events::{RateLimitTokenSet, TokenLimitTierSet, TokenNetFlowModeSet},
+    state::{LimitTier, OndoUser, RoleType, Roles, TokenLimit},
+    utils::migrate_account_len,
 };
 
 /// Initialize a `TokenLimit` account for a GM Token/USDon
@@ -99,6 +100,16 @@ impl<'info> InitializeTokenLimit<'info> {
             default_user_rate_limit,
             default_user_limit_window,
             bump: bumps.token_limit,
+            // Redemptions use the mint limits until set with `set_token_limit`
+            redeem_rate_limit: None,
+            redeem_limit_window: None,
+            default_user_redeem_rate_limit: None,
+            default_user_redeem_limit_window: None,
+            net_flow_enabled: false,
+            net_flow_position: 0,
+            net_flow_last_updated: 0,
+            limit_tiers: [LimitTier::default(); MAX_TOKEN_LIMIT_TIERS],
+            default_limit_profile: None,
         });
 
         // Emit event for token limit initialization
@@ -106,6 +117,8 @@ impl<'info> InitializeTokenLimit<'info> {
             token: self.mint.key(),
             limit: self.token_limit.rate_limit,
             limit_window: self.token_limit.limit_window,
+            redeem_limit: self.token_limit.redeem_rate_limit,
+            redeem_limit_window: self.token_limit.redeem_limit_window,
         });
 
         Ok(())
@@ -114,7 +127,7 @@ impl<'info> InitializeTokenLimit<'info> {
 
 /// Set or update the token limit parameters for a GM Token/USDon
 /// Requires `ADMIN_ROLE_GMTOKEN_MANAGER` role
-/// Allows updating any combination of the four parameters
+/// Allows updating any combination of the mint and redeem parameters
 #[derive(Accounts)]
 pub struct SetTokenLimit<'info> {
     /// The account with the authority to update token limits
@@ -148,28 +161,41 @@ pub struct SetTokenLimit<'info> {
 
 impl<'info> SetTokenLimit<'info> {
     /// Set or update the token limit parameters for a GM Token/USDon
-    /// Allows updating any combination of the four parameters
+    /// Allows updating any combination of the mint and redeem parameters.
+    /// Redeem parameters that were never set fall back to the matching mint parameter.
     /// # Arguments
-    /// * `rate_limit` - The new global rate limit (maximum tokens per window), if provided
-    /// * `limit_window` - The new time window for the rate limit in seconds, if provided
-    /// * `default_user_rate_limit` - The new default per-user rate limit, if provided
-    /// * `default_user_limit_window` - The new default per-user time window in seconds, if provided
+    /// * `rate_limit` - The new global mint rate limit (maximum tokens per window), if provided
+    /// * `limit_window` - The new time window for the mint rate limit in seconds, if provided
+    /// * `default_user_rate_limit` - The new default per-user mint rate limit, if provided
+    /// * `default_user_limit_window` - The new default per-user mint time window in seconds, if provided
+    /// * `redeem_rate_limit` - The new global redeem rate limit (maximum tokens per window), if provided
+    /// * `redeem_limit_window` - The new time window for the redeem rate limit in seconds, if provided
+    /// * `default_user_redeem_rate_limit` - The new default per-user redeem rate limit, if provided
+    /// * `default_user_redeem_limit_window` - The new default per-user redeem time window in seconds, if provided
     /// # Returns
     /// * `Result<()>` - Ok if the TokenLimit account is successfully updated, Err otherwise
+    #[allow(clippy::too_many_arguments)]
     pub fn set_token_limit(
         &mut self,
         rate_limit: Option<u64>,
         limit_window: Option<u64>,
         default_user_rate_limit: Option<u64>,
         default_user_limit_window: Option<u64>,
+        redeem_rate_limit: Option<u64>,
+        redeem_limit_window: Option<u64>,
+        default_user_redeem_rate_limit: Option<u64>,
+        default_user_redeem_limit_window: Option<u64>,
     ) -> Result<()> {
-        // Validate limit_window is not zero if provided
-        if let Some(window) = limit_window {
-            require_gt!(window, 0, OndoError::InvalidRateLimit);
-        }
-
-        // Validate default_user_limit_window is not zero if provided
-        if let Some(window) = default_user_limit_window {
+        // Validate the windows are not zero if provided
+        for window in [
+            limit_window,
+            default_user_limit_window,
+            redeem_limit_window,
+            default_user_redeem_limit_window,
+        ]
+        .into_iter()
+        .flatten()
+        {
             require_gt!(window, 0, OndoError::InvalidRateLimit);
         }
 
@@ -193,14 +219,36 @@ impl<'info> SetTokenLimit<'info> {
             self.token_limit.default_user_limit_window = Some(new_default_user_limit_window);
         }
 
+        // Update redeem rate limit if provided
+        if let Some(new_redeem_rate_limit) = redeem_rate_limit {
+            self.token_limit.redeem_rate_limit = Some(new_redeem_rate_limit);
+        }
+
+        // Update redeem limit window if provided
+        if let Some(new_redeem_limit_window) = redeem_limit_window {
+            self.token_limit.redeem_limit_window = Some(new_redeem_limit_window);
+        }
+
+        // Update default user redeem rate limit if provided
+        if let Some(new_default_user_redeem_rate_limit) = default_user_redeem_rate_limit {
+            self.token_limit.default_user_redeem_rate_limit =
+                Some(new_default_user_redeem_rate_limit);
+        }
+
+        // Update default user redeem limit window if provided
+        if let Some(new_default_user_redeem_limit_window) = default_user_redeem_limit_window {
+            self.token_limit.default_user_redeem_limit_window =
+                Some(new_default_user_redeem_limit_window);
+        }
+
         // Initialize rate_used fields if they were previously None but limits are now set
-        if self.token_limit.rate_limit.is_some() && self.token_limit.limit_window.is_some() {
-            if self.token_limit.mint_capacity_used.is_none() {
-                self.token_limit.mint_capacity_used = Some(0);
-            }
-            if self.token_limit.redeem_capacity_used.is_none() {
-                self.token_limit.redeem_capacity_used = Some(0);
-            }
+        if self.token_limit.limit(true).is_some() && self.token_limit.mint_capacity_used.is_none() {
+            self.token_limit.mint_capacity_used = Some(0);
+        }
+        if self.token_limit.limit(false).is_some()
+            && self.token_limit.redeem_capacity_used.is_none()
+        {
+            self.token_limit.redeem_capacity_used = Some(0);
         }
 
         // Emit event for token limit update
@@ -208,8 +256,183 @@ impl<'info> SetTokenLimit<'info> {
             token: self.mint.key(),
             limit: self.token_limit.rate_limit,
             limit_window: self.token_limit.limit_window,
+            redeem_limit: self.token_limit.redeem_rate_limit,
+            redeem_limit_window: self.token_limit.redeem_limit_window,
+        });
+
+        Ok(())
+    }
+
+    /// Enable or disable net-flow mode for a GM Token/USDon
+    /// In net-flow mode mints and redemptions offset each other, and the mint rate limit
+    /// caps the absolute net flow in the limit window. The net flow restarts from zero.
+    /// # Arguments
+    /// * `enabled` - Whether net-flow mode is enabled
+    /// # Returns
+    /// * `Result<()>` - Ok if the mode is successfully set, Err otherwise
+    pub fn set_token_net_flow_mode(&mut self, enabled: bool) -> Result<()> {
+        if enabled {
+            require!(
+                self.token_limit.limit(true).is_some(),
+                OndoError::InvalidRateLimit
+            );
+        }
+
+        self.token_limit.net_flow_enabled = enabled;
+        self.token_limit.net_flow_position = 0;
+        self.token_limit.net_flow_last_updated = Clock::get()?.unix_timestamp;
+
+        emit!(TokenNetFlowModeSet {
+            token: self.mint.key(),
+            enabled,
         });
 
         Ok(())
     }
+
+    /// Set or clear an additional rate limit tier for a GM Token/USDon
+    /// Every configured tier caps the mint and the redeem volume in its window separately.
+    /// The usage of the tier is reset.
+    /// # Arguments
+    /// * `index` - The tier to set (must be < MAX_TOKEN_LIMIT_TIERS)
+    /// * `rate_limit` - The maximum amount of tokens in the limit window
+    /// * `limit_window` - The limit window in seconds, 0 clears the tier
+    /// # Returns
+    /// * `Result<()>` - Ok if the tier is successfully set, Err otherwise
+    pub fn set_token_limit_tier(
+        &mut self,
+        index: u8,
+        rate_limit: u64,
+        limit_window: u64,
+    ) -> Result<()> {
+        let tier = self
+            .token_limit
+            .limit_tiers
+            .get_mut(index as usize)
+            .ok_or(OndoError::InvalidLimitTier)?;
+
+        *tier = LimitTier {
+            rate_limit,
+            limit_window,
+            ..LimitTier::default()
+        };
+
+        emit!(TokenLimitTierSet {
+            token: self.mint.key(),
+            index,
+            rate_limit,
+            limit_window,
+        });
+
+        Ok(())
+    }
+}
+
+/// Grow a `TokenLimit` account to the current layout
+/// Requires `ADMIN_ROLE_GMTOKEN_MANAGER` role
+/// Fields added since the account was created read as None or zero, which selects their defaults
+#[derive(Accounts)]
+pub struct MigrateTokenLimit<'info> {
+    /// The account with the authority to execute the migration, pays for the extra rent
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The Roles account verifying the authority has the `ADMIN_ROLE_GMTOKEN_MANAGER` role
+    /// # PDA Seeds
+    /// - `ADMIN_ROLE_GMTOKEN_MANAGER`
+    /// - The authority's address
+    #[account(
+        seeds = [RoleType::ADMIN_ROLE_GMTOKEN_MANAGER, authority.key().as_ref()],
+        bump = authority_role_account.bump,
+    )]
+    pub authority_role_account: Account<'info, Roles>,
+
+    /// The GM Token or USDon mint
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// The `TokenLimit` account to be migrated
+    /// # PDA Seeds
+    /// - `TOKEN_LIMIT_ACCOUNT_SEED`
+    /// - Mint address
+    ///
+    /// CHECK: Seeds and owner constraints validate the account.
+    /// Not deserialized as it may be smaller than the current layout.
+    #[account(
+        mut,
+        seeds = [TOKEN_LIMIT_ACCOUNT_SEED, mint.key().as_ref()],
+        bump,
+        owner = crate::ID,
+    )]
+    pub token_limit: UncheckedAccount<'info>,
+
+    /// The system program
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateTokenLimit<'info> {
+    /// Resize the account to the current layout, topping up rent if needed
+    /// Does nothing if the account already has the current size
+    /// # Returns
+    /// * `Result<()>` - Ok if the account is migrated, Err otherwise
+    pub fn migrate_token_limit(&self) -> Result<()> {
+        migrate_account_len(
+            &self.token_limit.to_account_info(),
+            TokenLimit::DISCRIMINATOR,
+            8 + TokenLimit::INIT_SPACE,
+            &self.authority.to_account_info(),
+            &self.system_program.to_account_info(),
+        )
+    }
+}
+
+/// Grow an `OndoUser` account to the current layout
+/// Unpermissioned, the payer covers the extra rent
+/// Fields added since the account was created read as None, which selects their fallbacks
+#[derive(Accounts)]
+pub struct MigrateOndoUser<'info> {
+    /// Pays for the extra rent
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The owner of the `OndoUser` account
+    /// CHECK: Only used to derive the `OndoUser` address
+    pub user: UncheckedAccount<'info>,
+
+    /// The GM Token mint associated with the `OndoUser` account
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// The `OndoUser` account to be migrated
+    /// # PDA Seeds
+    /// - `ONDO_USER_SEED`
+    /// - User's address
+    /// - The GM Token's mint address
+    ///
+    /// CHECK: Seeds and owner constraints validate the account.
+    /// Not deserialized as it may be smaller than the current layout.
+    #[account(
+        mut,
+        seeds = [ONDO_USER_SEED, user.key().as_ref(), mint.key().as_ref()],
+        bump,
+        owner = crate::ID,
+    )]
+    pub ondo_user: UncheckedAccount<'info>,
+
+    /// The system program
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateOndoUser<'info> {
+    /// Resize the account to the current layout, topping up rent if needed
+    /// Does nothing if the account already has the current size
+    /// # Returns
+    /// * `Result<()>` - Ok if the account is migrated, Err otherwise
+    pub fn migrate_ondo_user(&self) -> Result<()> {
+        migrate_account_len(
+            &self.ondo_user.to_account_info(),
+            OndoUser::DISCRIMINATOR,
+            8 + OndoUser::INIT_SPACE,
+            &self.payer.to_account_info(),
+            &self.system_program.to_account_info(),
+        )
+    }
 }
@@ -7,7 +7,6 @@ use anchor_lang::solana_program::{
 };
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::Token,
     token_interface::{
         burn_checked, mint_to, transfer_checked, BurnChecked, Mint, MintTo, TokenAccount,
         TokenInterface, TransferChecked,
@@ -16,25 +15,38 @@ use anchor_spl::{
 use solana_keccak_hasher::hash;
 use solana_sdk_ids::secp256k1_program;
 
-// Import necessary dependencies from Pyth
-use pyth_solana_receiver_sdk::price_update::{get_feed_id_from_hex, PriceUpdateV2};
-
 use crate::{
     constants::*,
     errors::OndoError,
+    events::{RateLimitExemptTrade, RedemptionQueued, VaultShortfall},
     state::{
-        Attestation, GMTokenManagerState, OndoUser, OracleSanityCheck, TokenLimit,
-        USDonManagerState, Whitelist,
+        Attestation, GMTokenManagerState, LimitGroup, LimitProfile, OndoUser, OracleSanityCheck,
+        RedemptionQueue, Roles, SettlementAsset, TokenLimit, USDonManagerState, UserNotionalLimit,
+        VaultShortfallMode, Whitelist,
+    },
+    utils::{
+        apply_net_flow, calculate_capacity_used, mint_usdon_to, mul_div, normalize_decimals,
+        read_oracle_price, usd_to_asset_amount, usdc_conversion_rate,
     },
-    utils::{calculate_capacity_used, mul_div, normalize_decimals},
 };
 use anchor_lang::Discriminator;
 
 pub struct TokenManager<'a, 'info> {
-    pub user: &'a mut Signer<'info>,
+    // The user the trade is executed for
+    pub user: &'a AccountInfo<'info>,
+    // Funds accounts created during the trade, the user unless an operator executes on their behalf
+    pub payer: &'a AccountInfo<'info>,
     pub mint: &'a mut InterfaceAccount<'info, Mint>,
     pub mint_authority: &'a UncheckedAccount<'info>,
     pub ondo_user: &'a mut Account<'info, OndoUser>,
+    // Only provided when the user holds the RateLimitExemptRole
+    pub rate_limit_exemption: Option<&'a Account<'info, Roles>>,
+    // Only required when the OndoUser is linked to a limit profile
+    pub limit_profile: Option<&'a Account<'info, LimitProfile>>,
+    // Only required when the OndoUser is attached to a limit group
+    pub limit_group: Option<&'a mut Account<'info, LimitGroup>>,
+    // Only required while the per-user notional limit is enabled
+    pub user_notional_limit: Option<&'a mut Account<'info, UserNotionalLimit>>,
     pub token_limit_account: &'a mut Account<'info, TokenLimit>,
     pub sanity_check_account: &'a mut Account<'info, OracleSanityCheck>,
     pub user_token_account: &'a mut InterfaceAccount<'info, TokenAccount>,
@@ -43,15 +55,27 @@ pub struct TokenManager<'a, 'info> {
     pub token_program: &'a Interface<'info, TokenInterface>,
     pub system_program: &'a Program<'info, System>,
     pub associated_token_program: &'a Program<'info, AssociatedToken>,
-    pub spl_token_program: Option<&'a Program<'info, Token>>,
+    // The USDC settlement leg, only required for trades settled in USDC
+    pub spl_token_program: Option<&'a AccountInfo<'info>>,
     pub usdc_price_update: Option<&'a UncheckedAccount<'info>>,
+    // Only required when a secondary USDC oracle is configured
+    pub usdc_secondary_price_update: Option<&'a UncheckedAccount<'info>>,
+    // Only required for USDC payouts when the USDC vault shortfall mode is Queue
+    pub redemption_queue: Option<&'a mut Account<'info, RedemptionQueue>>,
     pub usdc_vault: Option<&'a mut InterfaceAccount<'info, TokenAccount>>,
-    pub usdon_vault: &'a mut InterfaceAccount<'info, TokenAccount>,
     pub usdc_mint: Option<&'a InterfaceAccount<'info, Mint>>,
     pub user_usdc_token_account: Option<&'a mut InterfaceAccount<'info, TokenAccount>>,
+    // The registered settlement asset leg, replaces the USDC leg when `settlement_asset` is provided
+    pub settlement_asset: Option<&'a Account<'info, SettlementAsset>>,
+    pub settlement_token_program: Option<&'a AccountInfo<'info>>,
+    pub settlement_price_update: Option<&'a UncheckedAccount<'info>>,
+    pub settlement_vault: Option<&'a mut InterfaceAccount<'info, TokenAccount>>,
+    pub settlement_mint: Option<&'a InterfaceAccount<'info, Mint>>,
+    pub user_settlement_token_account: Option<&'a mut InterfaceAccount<'info, TokenAccount>>,
+    pub usdon_vault: &'a mut InterfaceAccount<'info, TokenAccount>,
     pub usdon_mint: &'a InterfaceAccount<'info, Mint>,
     pub user_usdon_token_account: &'a mut InterfaceAccount<'info, TokenAccount>,
-    pub usdon_manager_state: &'a Account<'info, USDonManagerState>,
+    pub usdon_manager_state: &'a mut Account<'info, USDonManagerState>,
     pub gmtoken_manager_state: &'a mut Account<'info, GMTokenManagerState>,
     pub instructions: &'a UncheckedAccount<'info>,
 }
@@ -78,38 +102,93 @@ impl<'a, 'info> TokenManager<'a, 'info> {
         );
 
         if !is_usdon {
-            let spl_token = self
-                .spl_token_program
-                .ok_or(OndoError::TokenProgramNotProvided)?;
-
-            let usdc_mint = self.usdc_mint.ok_or(OndoError::MintNotProvided)?;
-
-            // SAFETY: is_usdon is false, so user_usdc_token_account must be Some
-            let user_usdc_token_account = self.user_usdc_token_account.as_ref().unwrap();
+            let token_program = self.leg_token_program()?;
+            let mint = self.leg_mint()?;
+            let vault = self.leg_vault()?;
+            let user_token_account = self.user_leg_token_account()?;
 
-            // Validate the user's USDC token account
+            // Validate the user's token account for the settlement leg
             require_keys_eq!(
-                user_usdc_token_account.mint,
-                usdc_mint.key(),
+                user_token_account.mint,
+                mint.key(),
                 OndoError::InvalidTokenAccount
             );
 
             require_keys_eq!(
-                user_usdc_token_account.owner,
+                user_token_account.owner,
                 self.user.key(),
                 OndoError::InvalidTokenAccount
             );
 
             require_keys_eq!(
-                *user_usdc_token_account.to_account_info().owner,
-                spl_token.key(),
+                *user_token_account.to_account_info().owner,
+                token_program.key(),
                 OndoError::InvalidTokenAccount
             );
+
+            match self.settlement_asset {
+                // A registered settlement asset must be enabled and match the provided accounts
+                Some(settlement_asset) => settlement_asset.validate_leg(
+                    &mint.key(),
+                    &token_program.key(),
+                    &vault.key(),
+                )?,
+                // USDC settles against the configured USDC vault
+                None => {
+                    require_keys_eq!(
+                        vault.key(),
+                        self.usdon_manager_state.usdc_vault,
+                        OndoError::InvalidVault
+                    );
+
+                    require_keys_eq!(vault.mint, mint.key(), OndoError::InvalidInputMint);
+
+                    #[cfg(any(feature = "mainnet", feature = "testnet"))]
+                    require_keys_eq!(mint.key(), USDC_MINT, OndoError::InvalidInputMint);
+                }
+            }
         }
 
         Ok(())
     }
 
+    /// Returns the token program of the non-USDon settlement leg: the registered
+    /// settlement asset's when one is provided, the legacy SPL Token program for USDC otherwise
+    fn leg_token_program(&self) -> Result<&'a AccountInfo<'info>> {
+        match self.settlement_asset {
+            Some(_) => self.settlement_token_program,
+            None => self.spl_token_program,
+        }
+        .ok_or(OndoError::TokenProgramNotProvided.into())
+    }
+
+    /// Returns the mint of the non-USDon settlement leg
+    fn leg_mint(&self) -> Result<&'a InterfaceAccount<'info, Mint>> {
+        match self.settlement_asset {
+            Some(_) => self.settlement_mint,
+            None => self.usdc_mint,
+        }
+        .ok_or(OndoError::MintNotProvided.into())
+    }
+
+    /// Returns the protocol vault of the non-USDon settlement leg
+    fn leg_vault(&self) -> Result<&InterfaceAccount<'info, TokenAccount>> {
+        match self.settlement_asset {
+            Some(_) => self.settlement_vault.as_deref(),
+            None => self.usdc_vault.as_deref(),
+        }
+        .ok_or(OndoError::InvalidTokenAccount.into())
+    }
+
+    /// Returns the user's token account for the non-USDon settlement leg
+    fn user_leg_token_account(&self) -> Result<&InterfaceAccount<'info, TokenAccount>> {
+        match self.settlement_asset {
+            Some(_) => self.user_settlement_token_account.as_deref(),
+            None => self.user_usdc_token_account.as_deref(),
+        }
+        .ok_or(OndoError::InvalidTokenAccount.into())
+    }
+
     /// Initializes a new attestation account with the provided attestation ID, timestamp, and bump.
     /// Marks the attestation ID as used to prevent replay attacks.
     /// # Arguments
@@ -138,14 +217,14 @@ impl<'a, 'info> TokenManager<'a, 'info> {
             // Fund the attestation account to be rent-exempt
             invoke(
                 &system_instruction::transfer(
-                    &self.user.key(),
+                    &self.payer.key(),
                     &self.attestation_id_account.key(),
                     Rent::get()?
                         .minimum_balance(space)
                         .saturating_sub(self.attestation_id_account.lamports()),
                 ),
                 &[
-                    self.user.to_account_info(),
+                    self.payer.to_account_info(),
                     self.attestation_id_account.to_account_info(),
                 ],
             )?;
@@ -166,7 +245,7 @@ impl<'a, 'info> TokenManager<'a, 'info> {
             // Create the attestation data
             let attestation = Attestation {
                 attestation_id,
-                creator: self.user.key(),
+                creator: self.payer.key(),
                 created_at: timestamp,
                 bump,
             };
@@ -385,39 +464,19 @@ impl<'a, 'info> TokenManager<'a, 'info> {
     pub fn sanity_check(&mut self, price: u64, current_timestamp: i64) -> Result<()> {
         // Perform sanity checks on the token
         // Ensure the price is within a reasonable range of the last price
-        let deviation = self
-            .sanity_check_account
-            .last_price
-            .checked_mul(self.sanity_check_account.allowed_deviation_bps)
-            .ok_or(OndoError::MathOverflow)?
-            .checked_div(BASIS_POINTS_DIVISOR)
-            .ok_or(OndoError::MathOverflow)?;
-
-        // Calculate maximum acceptable price
-        let max_price = self
-            .sanity_check_account
-            .last_price
-            .checked_add(deviation)
-            .ok_or(OndoError::MathOverflow)?;
-
-        // Calculate minimum acceptable price
-        let min_price = self
-            .sanity_check_account
-            .last_price
-            .checked_sub(deviation)
-            .ok_or(OndoError::MathOverflow)?;
+        let (min_price, max_price) = self.sanity_check_account.price_band()?;
 
         // Check if the price is within the allowed deviation range
         if price > max_price {
             msg!(
-                "Price sanity check failed: price {} exceeds max_price {}. last_price={}, percentage_bp={}, deviation={}",
-                price, max_price, self.sanity_check_account.last_price, self.sanity_check_account.allowed_deviation_bps, deviation
+                "Price sanity check failed: price {} exceeds max_price {}. last_price={}, percentage_bp={}",
+                price, max_price, self.sanity_check_account.last_price, self.sanity_check_account.allowed_deviation_bps
             );
             return Err(OndoError::PriceExceedsMaxDeviation.into());
         } else if price < min_price {
             msg!(
-                "Price sanity check failed: price {} below min_price {}. last_price={}, percentage_bp={}, deviation={}",
-                price, min_price, self.sanity_check_account.last_price, self.sanity_check_account.allowed_deviation_bps, deviation
+                "Price sanity check failed: price {} below min_price {}. last_price={}, percentage_bp={}",
+                price, min_price, self.sanity_check_account.last_price, self.sanity_check_account.allowed_deviation_bps
             );
             return Err(OndoError::PriceBelowMinDeviation.into());
         }
@@ -456,8 +515,23 @@ impl<'a, 'info> TokenManager<'a, 'info> {
         // Check token-level rate limit with linear decay
         self.check_token_rate_limit(amount, current_timestamp, is_buy)?;
 
-        // Check user-level rate limit with linear decay
-        self.check_user_rate_limit(amount, current_timestamp, is_buy)?;
+        // Check user-level rate limit with linear decay, unless the user is exempt
+        if self.rate_limit_exemption.is_some() {
+            emit!(RateLimitExemptTrade {
+                user: self.user.key(),
+                mint: self.mint.key(),
+                amount,
+                is_buy,
+            });
+        } else {
+            self.check_user_rate_limit(amount, current_timestamp, is_buy)?;
+        }
+
+        // Check the limit group shared by the user's wallets, if any
+        self.check_limit_group_rate_limit(amount, current_timestamp, is_buy)?;
+
+        // Check the notional limits across all GM Tokens
+        self.check_notional_rate_limit(amount, current_timestamp, is_buy)?;
 
         Ok(())
     }
@@ -476,11 +550,17 @@ impl<'a, 'info> TokenManager<'a, 'info> {
         current_timestamp: i64,
         is_buy: bool,
     ) -> Result<()> {
+        // Additional tiers apply in both modes
+        self.token_limit_account
+            .consume_limit_tiers(amount, is_buy, current_timestamp)?;
+
+        if self.token_limit_account.net_flow_enabled {
+            return self.check_token_net_flow(amount, current_timestamp, is_buy);
+        }
+
         // Check if token-level rate limits are configured
-        if let (Some(token_rate_limit), Some(token_limit_window)) = (
-            self.token_limit_account.rate_limit,
-            self.token_limit_account.limit_window,
-        ) {
+        if let Some((token_rate_limit, token_limit_window)) = self.token_limit_account.limit(is_buy)
+        {
             let (token_capacity_used, token_last_updated) = if is_buy {
                 (
                     self.token_limit_account
@@ -557,6 +637,116 @@ impl<'a, 'info> TokenManager<'a, 'info> {
         Ok(())
     }
 
+    /// Checks and updates the token-level net flow, used instead of the separate
+    /// mint and redeem capacities when net-flow mode is enabled.
+    /// The absolute net flow is capped by the mint rate limit and limit window.
+    /// # Arguments
+    /// * `amount` - The amount of tokens involved in the transaction.
+    /// * `current_timestamp` - The current timestamp.
+    /// * `is_buy` - A boolean indicating if the transaction is a buy (true) or sell (false).
+    /// # Returns
+    /// * `Result<()>` - Ok if the check passes, Err otherwise.
+    #[inline(always)]
+    fn check_token_net_flow(
+        &mut self,
+        amount: u64,
+        current_timestamp: i64,
+        is_buy: bool,
+    ) -> Result<()> {
+        let (token_rate_limit, token_limit_window) = self
+            .token_limit_account
+            .limit(true)
+            .ok_or(OndoError::InvalidRateLimit)?;
+
+        let time_since_last_update = current_timestamp
+            .checked_sub(self.token_limit_account.net_flow_last_updated)
+            .ok_or(OndoError::MathOverflow)?;
+
+        self.token_limit_account.net_flow_position = apply_net_flow(
+            amount,
+            is_buy,
+            time_since_last_update,
+            token_limit_window,
+            self.token_limit_account.net_flow_position,
+            token_rate_limit,
+        )?;
+        self.token_limit_account.net_flow_last_updated = current_timestamp;
+
+        Ok(())
+    }
+
+    /// Checks and updates the shared capacity of the user's limit group, if the user has one.
+    /// # Arguments
+    /// * `amount` - The amount of tokens involved in the transaction.
+    /// * `current_timestamp` - The current timestamp.
+    /// * `is_buy` - A boolean indicating if the transaction is a buy (true) or sell (false).
+    /// # Returns
+    /// * `Result<()>` - Ok if the check passes, Err otherwise.
+    #[inline(always)]
+    fn check_limit_group_rate_limit(
+        &mut self,
+        amount: u64,
+        current_timestamp: i64,
+        is_buy: bool,
+    ) -> Result<()> {
+        let Some(limit_group_key) = self.ondo_user.limit_group else {
+            return Ok(());
+        };
+
+        let limit_group = self
+            .limit_group
+            .as_deref_mut()
+            .ok_or(OndoError::LimitGroupNotProvided)?;
+        require_keys_eq!(
+            limit_group.key(),
+            limit_group_key,
+            OndoError::InvalidLimitGroup
+        );
+
+        limit_group.consume(amount, current_timestamp, is_buy)
+    }
+
+    /// Checks and updates the global and per-user notional limits across all GM Tokens.
+    /// # Arguments
+    /// * `amount` - The USD notional of the transaction.
+    /// * `current_timestamp` - The current timestamp.
+    /// * `is_buy` - A boolean indicating if the transaction is a buy (true) or sell (false).
+    /// # Returns
+    /// * `Result<()>` - Ok if the check passes, Err otherwise.
+    #[inline(always)]
+    fn check_notional_rate_limit(
+        &mut self,
+        amount: u64,
+        current_timestamp: i64,
+        is_buy: bool,
+    ) -> Result<()> {
+        self.gmtoken_manager_state
+            .consume_notional(amount, current_timestamp, is_buy)?;
+
+        if !self.gmtoken_manager_state.user_notional_limit_enabled() {
+            return Ok(());
+        }
+
+        let user_notional_limit = self
+            .user_notional_limit
+            .as_deref_mut()
+            .ok_or(OndoError::UserNotionalLimitNotProvided)?;
+        let usage = if is_buy {
+            &mut user_notional_limit.mint_usage
+        } else {
+            &mut user_notional_limit.redeem_usage
+        };
+
+        usage
+            .consume(
+                amount,
+                current_timestamp,
+                self.gmtoken_manager_state.user_notional_rate_limit,
+                self.gmtoken_manager_state.user_notional_limit_window,
+            )
+            .inspect_err(|_| msg!("User notional limit exceeded"))
+    }
+
     /// Checks and updates the user-level rate limit state.
     /// # Arguments
     /// * `amount` - The amount of tokens involved in the transaction.
@@ -571,8 +761,8 @@ impl<'a, 'info> TokenManager<'a, 'info> {
         current_timestamp: i64,
         is_buy: bool,
     ) -> Result<()> {
-        if let (Some(user_rate_limit), Some(user_limit_window)) =
-            (self.ondo_user.rate_limit, self.ondo_user.limit_window)
+        if let Some((user_rate_limit, user_limit_window)) =
+            self.ondo_user.effective_limit(is_buy, self.limit_profile)?
         {
             let (user_capacity_used, user_last_updated) = if is_buy {
                 (
@@ -650,53 +840,41 @@ impl<'a, 'info> TokenManager<'a, 'info> {
         Ok(())
     }
 
-    /// Swaps USDC tokens for USDon tokens (1:1 exchange).
+    /// Swaps USDC tokens for USDon tokens at the given conversion rate.
     ///
     /// This method handles the conversion of USDC to USDon tokens with the following steps:
-    /// 1. Validates input amount and retrieves current USDC price from the USDC price oracle
+    /// 1. Validates input amount and converts the USD value into USDC at the conversion rate
     /// 2. Transfers USDC from user to protocol vault
     /// 3. Returns the calculated USDon amount to be burned
     ///
     /// # Arguments
-    /// * `amount_in` - The amount of USDC tokens to swap (must be > 0)
+    /// * `amount_in` - The USD value to swap, in USDC decimals (must be > 0)
+    /// * `conversion_rate` - The USDC price returned by `conversion_rate`
     ///
     /// # Returns
     /// * `Result<u64>` - The amount of USDon tokens to be burned
-    pub fn swap_usdc_to_usdon(&mut self, amount_in: u64) -> Result<u64> {
+    pub fn swap_usdc_to_usdon(&mut self, amount_in: u64, conversion_rate: u64) -> Result<u64> {
         // Validate that input amount is greater than zero
         require_gt!(amount_in, 0);
 
-        // Perform sanity checks on the USDC token
-        if self.usdon_manager_state.oracle_price_enabled {
-            self.usdc_oracle_sanity_check()?;
-        }
+        let usdc_mint = self.leg_mint()?;
 
-        let usdc_mint = self.usdc_mint.as_ref().ok_or(OndoError::InvalidInputMint)?;
+        // Round up: the user pays at least the USD value
+        let usdc_amount_in = usd_to_asset_amount(amount_in, conversion_rate, true)?;
 
         // Transfer USDC tokens from user to protocol vault
         // This locks the user's USDC in the protocol's vault
         transfer_checked(
             CpiContext::new(
-                self.spl_token_program
-                    .as_ref()
-                    .ok_or(OndoError::TokenProgramNotProvided)?
-                    .to_account_info(),
+                self.leg_token_program()?.to_account_info(),
                 TransferChecked {
-                    from: self
-                        .user_usdc_token_account
-                        .as_ref()
-                        .ok_or(OndoError::InvalidTokenAccount)?
-                        .to_account_info(),
+                    from: self.user_leg_token_account()?.to_account_info(),
                     mint: usdc_mint.to_account_info(),
-                    to: self
-                        .usdc_vault
-                        .as_ref()
-                        .ok_or(OndoError::InvalidTokenAccount)?
-                        .to_account_info(),
+                    to: self.leg_vault()?.to_account_info(),
                     authority: self.user.to_account_info(),
                 },
             ),
-            amount_in,
+            usdc_amount_in,
             usdc_mint.decimals,
         )?;
 
@@ -713,28 +891,24 @@ impl<'a, 'info> TokenManager<'a, 'info> {
         Ok(normalized_amount_out)
     }
 
-    /// Swaps USDon tokens for USDC tokens (1:1 exchange).
+    /// Swaps USDon tokens for USDC tokens at the given conversion rate.
     ///
     /// This method handles the conversion of USDon to USDC tokens with the following steps:
-    /// 1. Validates input amount and retrieves current USDC price from a USDC price oracle
+    /// 1. Validates input amount and converts it into USDC at the conversion rate
     /// 2. Transfers USDon from user to protocol vault
     /// 3. Transfers USDC from protocol vault to user
     ///
     /// # Arguments
     /// * `amount_in` - The amount of USDon tokens to swap (must be > 0)
+    /// * `conversion_rate` - The USDC price returned by `conversion_rate`
     ///
     /// # Returns
     /// * `Result<()>` - Success if swap completes without errors
-    pub fn swap_usdon_to_usdc(&mut self, amount_in: u64) -> Result<()> {
+    pub fn swap_usdon_to_usdc(&mut self, amount_in: u64, conversion_rate: u64) -> Result<()> {
         // Validate that input amount is greater than zero
         require_gt!(amount_in, 0);
 
-        // Perform sanity checks on the USDC token
-        if self.usdon_manager_state.oracle_price_enabled {
-            self.usdc_oracle_sanity_check()?;
-        }
-
-        let usdc_mint = self.usdc_mint.as_ref().ok_or(OndoError::InvalidInputMint)?;
+        let usdc_mint = self.leg_mint()?;
 
         // Normalize decimals from USDon (9 decimals) to USDC (6 decimals)
         let normalized_amount_out = normalize_decimals(
@@ -754,48 +928,48 @@ impl<'a, 'info> TokenManager<'a, 'info> {
             false,
         )?;
 
+        // Round down: Protocol pays - protects the protocol
+        let usdc_amount_out = usd_to_asset_amount(normalized_amount_out, conversion_rate, false)?;
+
+        require!(usdc_amount_out > 0, OndoError::InvalidAmount);
+
+        // Only swap what the USDC vault can cover or queue, the user keeps the rest in USDon
+        let (usdc_amount_out, usdon_amount_to_transfer) =
+            self.vault_payout(usdc_amount_out, usdon_amount_to_transfer)?;
+
         // Step 1: Transfer USDon tokens from user to protocol vault
         // This reduces the user's USDon balance and increases the protocol's USDon vault
-        transfer_checked(
-            CpiContext::new(
-                self.token_program.to_account_info(),
-                TransferChecked {
-                    from: self.user_usdon_token_account.to_account_info(),
-                    mint: self.usdon_mint.to_account_info(),
-                    to: self.usdon_vault.to_account_info(),
-                    authority: self.user.to_account_info(),
-                },
-            ),
-            usdon_amount_to_transfer,
-            self.usdon_mint.decimals,
-        )?;
+        if usdon_amount_to_transfer != 0 {
+            transfer_checked(
+                CpiContext::new(
+                    self.token_program.to_account_info(),
+                    TransferChecked {
+                        from: self.user_usdon_token_account.to_account_info(),
+                        mint: self.usdon_mint.to_account_info(),
+                        to: self.usdon_vault.to_account_info(),
+                        authority: self.user.to_account_info(),
+                    },
+                ),
+                usdon_amount_to_transfer,
+                self.usdon_mint.decimals,
+            )?;
+        }
 
         // Step 2: Transfer USDC tokens from protocol vault to user
         // This releases USDC from the protocol's vault to the user's account
-        if normalized_amount_out != 0 {
+        if usdc_amount_out != 0 {
             transfer_checked(
                 CpiContext::new_with_signer(
-                    self.spl_token_program
-                        .as_ref()
-                        .ok_or(OndoError::TokenProgramNotProvided)?
-                        .to_account_info(),
+                    self.leg_token_program()?.to_account_info(),
                     TransferChecked {
-                        from: self
-                            .usdc_vault
-                            .as_ref()
-                            .ok_or(OndoError::InvalidTokenAccount)?
-                            .to_account_info(),
+                        from: self.leg_vault()?.to_account_info(),
                         mint: usdc_mint.to_account_info(),
-                        to: self
-                            .user_usdc_token_account
-                            .as_ref()
-                            .ok_or(OndoError::InvalidTokenAccount)?
-                            .to_account_info(),
+                        to: self.user_leg_token_account()?.to_account_info(),
                         authority: self.usdon_manager_state.to_account_info(),
                     },
                     &[&[USDON_MANAGER_STATE_SEED, &[self.usdon_manager_state.bump]]],
                 ),
-                normalized_amount_out,
+                usdc_amount_out,
                 usdc_mint.decimals,
             )?;
         }
@@ -803,73 +977,162 @@ impl<'a, 'info> TokenManager<'a, 'info> {
         Ok(())
     }
 
-    #[inline(always)]
-    fn usdc_oracle_sanity_check(&self) -> Result<()> {
-        // Retrieve the USDC price update account info
-        let usdc_price_update_info = self
-            .usdc_price_update
-            .as_ref()
-            .ok_or(OndoError::USDCOracleNotProvided)?
-            .to_account_info();
-
-        let usdc_price = match usdc_price_update_info.key() {
-            USDC_PYTH_ORACLE_ADDRESS => {
-                // Fetch the feed ID for the USDC token price from its hex representation.
-                let usdc_feed_id: [u8; 32] = get_feed_id_from_hex(USDC_PYTH_ID)?;
-
-                // Deserialize `usdc_price_update_info` account data into PriceUpdateV2 struct
-                let data = usdc_price_update_info.try_borrow_data()?;
-                let usdc_price_update_data = PriceUpdateV2::try_deserialize(&mut &data[..])?;
-
-                // Retrieve current USDC/USD price from Pyth oracle with freshness validation
-                // This ensures we're using recent price data to prevent stale price attacks
-                let price_update_data = usdc_price_update_data.get_price_no_older_than(
-                    &Clock::get()?,
-                    self.usdon_manager_state.oracle_price_max_age,
-                    &usdc_feed_id,
+    /// Caps a payout out of the settlement vault at its balance, less the USDC reserved for
+    /// queued claims when paying out of the USDC vault.
+    /// Fails with InsufficientVaultLiquidity if the vault is short, unless the shortfall mode
+    /// settles the remainder in USDon (emitting VaultShortfall) or queues it as a claim
+    /// (emitting RedemptionQueued). Only USDC payouts can be queued.
+    /// # Arguments
+    /// * `usdc_amount_out` - The amount of USDC owed to the user
+    /// * `usdon_amount` - The amount of USDon swapped for `usdc_amount_out`
+    /// # Returns
+    /// * `Result<(u64, u64)>` - The USDC amount to pay out now and the USDon amount swapped for it
+    fn vault_payout(&mut self, usdc_amount_out: u64, usdon_amount: u64) -> Result<(u64, u64)> {
+        let is_usdc = self.settlement_asset.is_none();
+        let vault = self.leg_vault()?;
+        let vault_key = vault.key();
+        // USDC owed to queued claims is reserved for them in the USDC vault, whether or not
+        // the queue is provided
+        let vault_liquidity = self
+            .usdon_manager_state
+            .vault_liquidity(vault.amount, is_usdc);
+        let destination = self.user_leg_token_account()?.key();
+
+        let mut mode = self.usdon_manager_state.usdc_vault_shortfall_mode;
+
+        // The redemption queue only holds USDC claims
+        let redemption_queue = if is_usdc {
+            self.redemption_queue.as_deref_mut()
+        } else {
+            if mode == VaultShortfallMode::Queue {
+                mode = VaultShortfallMode::Revert;
+            }
+            None
+        };
+
+        if mode == VaultShortfallMode::Queue {
+            // The queue must be provided so its claims keep their place in line
+            let queue = redemption_queue
+                .as_ref()
+                .ok_or(OndoError::RedemptionQueueNotProvided)?;
+
+            // A full queue cannot take the shortfall, which then reverts as in Revert mode
+            if queue.is_full() {
+                msg!("Redemption queue full, shortfalls revert until claims are paid");
+                mode = VaultShortfallMode::Revert;
+            }
+        }
+
+        let (usdc_paid, usdon_swapped, usdc_queued) =
+            split_vault_payout(usdc_amount_out, usdon_amount, vault_liquidity, mode)?;
+
+        if usdc_queued > 0 {
+            let claim_id = redemption_queue
+                .ok_or(OndoError::RedemptionQueueNotProvided)?
+                .push(
+                    &mut self.usdon_manager_state.queued_usdc,
+                    self.user.key(),
+                    destination,
+                    usdc_queued,
+                    Clock::get()?.unix_timestamp,
                 )?;
 
-                // Validate confidence interval is within threshold
-                // Reject prices with high uncertainty to prevent using unreliable oracle data
-                require!(price_update_data.price > 0, OndoError::InvalidPrice);
+            emit!(RedemptionQueued {
+                claim_id,
+                user: self.user.key(),
+                destination,
+                amount: usdc_queued,
+            });
+        } else if usdc_paid < usdc_amount_out {
+            emit!(VaultShortfall {
+                user: self.user.key(),
+                vault: vault_key,
+                requested: usdc_amount_out,
+                paid: usdc_paid,
+                usdon_amount: usdon_amount
+                    .checked_sub(usdon_swapped)
+                    .ok_or(OndoError::MathOverflow)?,
+            });
+        }
 
-                // Check exponent is negative (Pyth convention)
-                require!(
-                    price_update_data.exponent < 0,
-                    OndoError::InvalidPriceExponent
-                );
+        Ok((usdc_paid, usdon_swapped))
+    }
+
+    /// Returns the rate at which the settlement leg of a trade is converted to USDon,
+    /// as the USD price of the settlement asset scaled by USDC_PRICE_DECIMALS.
+    /// USDon and registered settlement assets convert at par, USDC at the oracle price
+    /// when oracle conversion is enabled and at par otherwise.
+    /// Runs the oracle sanity checks of the settlement asset.
+    /// # Arguments
+    /// * `is_usdon` - Whether the trade is settled in USDon
+    /// # Returns
+    /// * `Result<u64>` - The conversion rate
+    pub fn conversion_rate(&self, is_usdon: bool) -> Result<u64> {
+        if is_usdon {
+            return Ok(USDC_PEG_PRICE);
+        }
 
-                let conf = price_update_data.conf as u128;
-                let price = price_update_data.price as u128;
+        // Perform sanity checks on the settlement asset
+        self.settlement_oracle_sanity_check()
+    }
 
-                // Check: conf * 100 <= price * CONFIDENCE_THRESHOLD (equivalent to conf/price <= CONFIDENCE_THRESHOLD %)
-                let conf_times_100 = conf.checked_mul(100).ok_or(OndoError::MathOverflow)?;
-                let price_times_threshold = price
-                    .checked_mul(CONFIDENCE_THRESHOLD)
-                    .ok_or(OndoError::MathOverflow)?;
+    /// Checks the price of the non-USDon settlement asset before it is exchanged for USDon.
+    /// A registered settlement asset is checked against its own oracle configuration,
+    /// USDC against the USDonManagerState configuration when oracle pricing is enabled.
+    /// Returns the conversion rate of the settlement asset.
+    #[inline(always)]
+    fn settlement_oracle_sanity_check(&self) -> Result<u64> {
+        match self.settlement_asset {
+            Some(settlement_asset) => {
+                // Assets the admin registered as priced at par skip the oracle check
+                if !settlement_asset.has_oracle() {
+                    return Ok(USDC_PEG_PRICE);
+                }
+
+                let price_update_info = self
+                    .settlement_price_update
+                    .as_ref()
+                    .ok_or(OndoError::USDCOracleNotProvided)?
+                    .to_account_info();
 
-                require!(
-                    conf_times_100 <= price_times_threshold,
-                    OndoError::ConfidenceThresholdExceeded
+                require_keys_eq!(
+                    price_update_info.key(),
+                    settlement_asset.price_update,
+                    OndoError::InvalidOraclePriceAddress
                 );
 
-                let from_decimals = u8::try_from(-price_update_data.exponent)
-                    .map_err(|_| OndoError::InvalidPriceExponent)?;
+                let oracle_price = read_oracle_price(
+                    settlement_asset.oracle_adapter,
+                    &price_update_info,
+                    &settlement_asset.effective_price_update_owner(),
+                    &settlement_asset.feed_id,
+                    settlement_asset.oracle_max_age,
+                )?;
 
-                normalize_decimals(
-                    price_update_data.price as u64, // Safe to cast as we required price > 0 above
-                    from_decimals,
-                    USDC_PRICE_DECIMALS,
-                    false,
-                )?
-            }
-            _ => return err!(OndoError::USDCOracleNotImplemented),
-        };
+                // Reject prices with high uncertainty to prevent using unreliable oracle data
+                oracle_price.check_confidence(settlement_asset.confidence_threshold_bps)?;
 
-        // Validate that USDC price is above minimum threshold
-        require_gte!(usdc_price, MIN_PRICE, OndoError::USDCBelowMinimumPrice);
+                // Validate that the asset price is within its depeg bounds
+                require_gte!(
+                    oracle_price.price,
+                    settlement_asset.min_price,
+                    OndoError::USDCBelowMinimumPrice
+                );
+                require_gte!(
+                    settlement_asset.max_price,
+                    oracle_price.price,
+                    OndoError::USDCAboveMaximumPrice
+                );
 
-        Ok(())
+                Ok(USDC_PEG_PRICE)
+            }
+            None => usdc_conversion_rate(
+                self.usdon_manager_state,
+                self.usdc_price_update.map(|account| account.as_ref()),
+                self.usdc_secondary_price_update
+                    .map(|account| account.as_ref()),
+            ),
+        }
     }
 
     /// Verifies that the user is whitelisted by checking the whitelist account.
@@ -877,15 +1140,12 @@ impl<'a, 'info> TokenManager<'a, 'info> {
     /// * `Result<()>` - Ok if the user is whitelisted, Err(UserNotWhitelisted) otherwise.
     #[inline(always)]
     pub fn verify_whitelist(&self) -> Result<()> {
-        let whitelist_data = self.whitelist.try_borrow_data()?;
-        if whitelist_data.len() < 8 || whitelist_data[..8] != *Whitelist::DISCRIMINATOR {
-            return Err(OndoError::UserNotWhitelisted.into());
-        }
-        Ok(())
+        Whitelist::verify(self.whitelist)
     }
 
     /// Initializes the Ondo user account if it is not already initialized.
-    /// Sets the owner, mint, rate limit, limit window, and bump values.
+    /// Sets the owner, mint and bump values, and links the token's default limit profile
+    /// if one is set, otherwise copies the token's default user limits.
     /// # Arguments
     /// * `bump` - The bump seed used for PDA derivation.
     /// # Returns
@@ -895,19 +1155,448 @@ impl<'a, 'info> TokenManager<'a, 'info> {
         if self.ondo_user.owner != self.user.key() {
             self.ondo_user.owner = self.user.key();
             self.ondo_user.mint = self.mint.key();
-            self.ondo_user.rate_limit = self.token_limit_account.default_user_rate_limit;
-            self.ondo_user.limit_window = self.token_limit_account.default_user_limit_window;
+            if self.token_limit_account.default_limit_profile.is_some() {
+                self.ondo_user.rate_limit = None;
+                self.ondo_user.limit_window = None;
+                self.ondo_user.redeem_rate_limit = None;
+                self.ondo_user.redeem_limit_window = None;
+            } else {
+                self.ondo_user.rate_limit = self.token_limit_account.default_user_rate_limit;
+                self.ondo_user.limit_window = self.token_limit_account.default_user_limit_window;
+                self.ondo_user.redeem_rate_limit =
+                    self.token_limit_account.default_user_redeem_rate_limit;
+                self.ondo_user.redeem_limit_window =
+                    self.token_limit_account.default_user_redeem_limit_window;
+            }
             self.ondo_user.mint_capacity_used = Some(0);
             self.ondo_user.mint_last_updated = None;
             self.ondo_user.redeem_capacity_used = Some(0);
             self.ondo_user.redeem_last_updated = None;
             self.ondo_user.bump = bump;
+            self.ondo_user.limit_group = None;
+            self.ondo_user.limit_profile = self.token_limit_account.default_limit_profile;
 
             msg!("User initialized");
         }
 
         Ok(())
     }
+
+    /// Runs the checks shared by every attested mint and redeem before any funds move:
+    /// pause flags, whitelist, trading hours, attestation expiry, replay protection,
+    /// attestation signature, price sanity and rate limits.
+    /// # Arguments
+    /// * `attestation_id` - A unique 16-byte identifier for the attestation.
+    /// * `price` - The price associated with the attestation.
+    /// * `amount` - The amount of GM Tokens being minted or redeemed.
+    /// * `expiration` - The expiration timestamp of the attestation.
+    /// * `is_buy` - A boolean indicating if the trade is a mint (true) or a redemption (false).
+    /// * `ondo_user_bump` - The bump seed for the Ondo user account PDA.
+    /// * `attestation_id_account_bump` - The bump seed for the attestation ID account PDA.
+    /// # Returns
+    /// * `Result<()>` - Ok if every check passes, Err otherwise.
+    #[allow(clippy::too_many_arguments)]
+    pub fn validate_trade(
+        &mut self,
+        attestation_id: [u8; 16],
+        price: u64,
+        amount: u64,
+        expiration: i64,
+        is_buy: bool,
+        ondo_user_bump: u8,
+        attestation_id_account_bump: u8,
+    ) -> Result<()> {
+        if is_buy {
+            // Check if minting is paused
+            require!(
+                !self.gmtoken_manager_state.minting_paused,
+                OndoError::GMTokenMintingPaused
+            );
+
+            // Check if token-level minting is paused
+            require!(
+                !self.token_limit_account.minting_paused,
+                OndoError::GMTokenMintingPaused
+            );
+        } else {
+            // Check if redemptions are paused
+            require!(
+                !self.gmtoken_manager_state.redemption_paused,
+                OndoError::GMTokenRedemptionPaused
+            );
+
+            // Check if token-level redemptions are paused
+            require!(
+                !self.token_limit_account.redemption_paused,
+                OndoError::GMTokenRedemptionPaused
+            );
+        }
+
+        // Verify user is whitelisted
+        self.verify_whitelist()?;
+
+        // Validate input parameters
+        require_gt!(amount, 0);
+        require_gt!(price, 0);
+
+        let current_timestamp = Clock::get()?.unix_timestamp;
+
+        self.gmtoken_manager_state
+            .check_is_valid_hours(current_timestamp)?;
+
+        // Check attestation expiration
+        require!(
+            current_timestamp < expiration,
+            OndoError::AttestationExpired
+        );
+
+        // on-chain double check that expiration is within allowed max duration
+        require!(
+            expiration - current_timestamp <= MAX_ATTESTATION_EXPIRATION,
+            OndoError::AttestationExpirationTooLarge
+        );
+
+        // Create ondo user account if it doesn't exist
+        self.initialize_ondo_user(ondo_user_bump)?;
+
+        // Create attestation account if it doesn't exist marking the attestation as consumed
+        self.initialize_attestation_account(
+            attestation_id,
+            current_timestamp,
+            attestation_id_account_bump,
+        )?;
+
+        // Verify the attestation signature
+        self.verify_attestation(
+            CHAIN_ID.to_bytes(),
+            attestation_id,
+            if is_buy { BUY } else { SELL },
+            price,
+            amount,
+            expiration,
+        )?;
+
+        // Perform sanity check
+        self.sanity_check(price, current_timestamp)?;
+
+        // Check rate limit of the GM Token and user
+        self.rate_limit_check(price, amount, current_timestamp, is_buy)
+    }
+
+    /// Mints GM Tokens to the user's token account.
+    /// # Arguments
+    /// * `amount` - The amount of GM Tokens to mint.
+    /// * `mint_authority_bump` - The bump seed for the mint authority PDA.
+    /// # Returns
+    /// * `Result<()>` - Ok if the mint succeeds, Err otherwise.
+    pub fn mint_gm_tokens(&self, amount: u64, mint_authority_bump: u8) -> Result<()> {
+        mint_to(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                MintTo {
+                    mint: self.mint.to_account_info(),
+                    to: self.user_token_account.to_account_info(),
+                    authority: self.mint_authority.to_account_info(),
+                },
+                &[&[MINT_AUTHORITY_SEED, &[mint_authority_bump]]],
+            ),
+            amount,
+        )
+    }
+
+    /// Burns USDon from the USDon vault once the matching USDC has been received.
+    /// # Arguments
+    /// * `amount` - The amount of USDon to burn.
+    /// * `mint_authority_bump` - The bump seed for the mint authority PDA.
+    /// # Returns
+    /// * `Result<()>` - Ok if the burn succeeds, Err otherwise.
+    pub fn burn_usdon_from_vault(&self, amount: u64, mint_authority_bump: u8) -> Result<()> {
+        burn_checked(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                BurnChecked {
+                    mint: self.usdon_mint.to_account_info(),
+                    from: self.usdon_vault.to_account_info(),
+                    authority: self.mint_authority.to_account_info(),
+                },
+                &[&[MINT_AUTHORITY_SEED, &[mint_authority_bump]]],
+            ),
+            amount,
+            self.usdon_mint.decimals,
+        )
+    }
+
+    /// Pays for a GM Token mint out of a program-owned escrow and mints the GM Tokens to the user.
+    /// Uses the same rounding as `mint_with_attestation`: USDon is moved into the USDon vault,
+    /// or USDC is moved into the USDC vault and the matching USDon is burned from the USDon vault.
+    /// # Arguments
+    /// * `escrow` - The escrow token account holding the user's USDon or USDC.
+    /// * `escrow_authority` - The PDA that owns the escrow token account.
+    /// * `escrow_signer_seeds` - The signer seeds of the escrow authority.
+    /// * `escrow_balance` - The amount of the escrow that may be spent on this mint.
+    /// * `price` - The price associated with the attestation.
+    /// * `amount` - The amount of GM Tokens to mint.
+    /// * `is_usdon` - A boolean indicating if the escrow holds USDon (true) or USDC (false).
+    /// * `conversion_rate` - The USDC price returned by `conversion_rate`.
+    /// * `mint_authority_bump` - The bump seed for the mint authority PDA.
+    /// # Returns
+    /// * `Result<u64>` - The amount taken from the escrow.
+    #[allow(clippy::too_many_arguments)]
+    pub fn mint_from_escrow(
+        &mut self,
+        escrow: &AccountInfo<'info>,
+        escrow_authority: &AccountInfo<'info>,
+        escrow_signer_seeds: &[&[&[u8]]],
+        escrow_balance: u64,
+        price: u64,
+        amount: u64,
+        is_usdon: bool,
+        conversion_rate: u64,
+        mint_authority_bump: u8,
+    ) -> Result<u64> {
+        // Round up: Favours the protocol
+        let amount_sent = mul_div(price, amount, PRICE_SCALING_FACTOR as u64, true)?;
+
+        require_gt!(amount_sent, 0, OndoError::InvalidAmount);
+
+        let spent = if is_usdon {
+            require_gte!(
+                escrow_balance,
+                amount_sent,
+                OndoError::InsufficientEscrowBalance
+            );
+
+            // Transfer USDon from the escrow to the USDon vault
+            transfer_checked(
+                CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    TransferChecked {
+                        from: escrow.clone(),
+                        mint: self.usdon_mint.to_account_info(),
+                        to: self.usdon_vault.to_account_info(),
+                        authority: escrow_authority.clone(),
+                    },
+                    escrow_signer_seeds,
+                ),
+                amount_sent,
+                self.usdon_mint.decimals,
+            )?;
+
+            amount_sent
+        } else {
+            let usdc_mint = self.leg_mint()?;
+
+            // Normalize amount from GM Token decimals to USDC decimals
+            let normalized_amount =
+                normalize_decimals(amount_sent, self.mint.decimals, usdc_mint.decimals, true)?;
+
+            require_gt!(normalized_amount, 0, OndoError::InvalidAmount);
+
+            // Round up: Favours the protocol
+            let usdc_amount = usd_to_asset_amount(normalized_amount, conversion_rate, true)?;
+
+            require_gte!(
+                escrow_balance,
+                usdc_amount,
+                OndoError::InsufficientEscrowBalance
+            );
+
+            // Transfer USDC from the escrow to the USDC vault
+            transfer_checked(
+                CpiContext::new_with_signer(
+                    self.leg_token_program()?.to_account_info(),
+                    TransferChecked {
+                        from: escrow.clone(),
+                        mint: usdc_mint.to_account_info(),
+                        to: self.leg_vault()?.to_account_info(),
+                        authority: escrow_authority.clone(),
+                    },
+                    escrow_signer_seeds,
+                ),
+                usdc_amount,
+                usdc_mint.decimals,
+            )?;
+
+            // Then burn the matching USDon from the USDon vault
+            let amount_to_burn = normalize_decimals(
+                normalized_amount,
+                usdc_mint.decimals,
+                self.usdon_mint.decimals,
+                false,
+            )?;
+            self.burn_usdon_from_vault(amount_to_burn, mint_authority_bump)?;
+
+            usdc_amount
+        };
+
+        self.mint_gm_tokens(amount, mint_authority_bump)?;
+
+        Ok(spent)
+    }
+
+    /// Burns escrowed GM Tokens and pays the user out in USDon or USDC.
+    /// Uses the same rounding as `redeem_with_attestation`. For USDC payouts the USDon that would
+    /// have been swapped by the user is minted straight into the USDon vault, and any USDon dust
+    /// that cannot be represented in USDC is minted to the user.
+    /// # Arguments
+    /// * `escrow` - The escrow token account holding the user's GM Tokens.
+    /// * `escrow_authority` - The PDA that owns the escrow token account.
+    /// * `escrow_signer_seeds` - The signer seeds of the escrow authority.
+    /// * `price` - The price associated with the attestation.
+    /// * `amount` - The amount of GM Tokens to redeem.
+    /// * `is_usdon` - A boolean indicating if the user is paid in USDon (true) or USDC (false).
+    /// * `conversion_rate` - The USDC price returned by `conversion_rate`.
+    /// * `mint_authority_bump` - The bump seed for the mint authority PDA.
+    /// # Returns
+    /// * `Result<u64>` - The USDon value paid out to the user.
+    #[allow(clippy::too_many_arguments)]
+    pub fn redeem_from_escrow(
+        &mut self,
+        escrow: &AccountInfo<'info>,
+        escrow_authority: &AccountInfo<'info>,
+        escrow_signer_seeds: &[&[&[u8]]],
+        price: u64,
+        amount: u64,
+        is_usdon: bool,
+        conversion_rate: u64,
+        mint_authority_bump: u8,
+    ) -> Result<u64> {
+        // Round down: Protocol pays - protects the protocol
+        let mint_amount = mul_div(price, amount, PRICE_SCALING_FACTOR as u64, false)?;
+
+        require_gt!(mint_amount, 0, OndoError::InvalidAmount);
+
+        let signer_seeds: &[&[&[u8]]] = &[&[MINT_AUTHORITY_SEED, &[mint_authority_bump]]];
+
+        let user_usdon_amount = if is_usdon {
+            mint_amount
+        } else {
+            let usdc_mint = self.leg_mint()?;
+
+            // Normalize decimals from USDon (9 decimals) to USDC (6 decimals)
+            let normalized_amount_out = normalize_decimals(
+                mint_amount,
+                self.usdon_mint.decimals,
+                usdc_mint.decimals,
+                false,
+            )?;
+
+            require!(normalized_amount_out > 0, OndoError::InvalidAmount);
+
+            // Round down: Protocol pays - protects the protocol
+            let usdc_amount_out =
+                usd_to_asset_amount(normalized_amount_out, conversion_rate, false)?;
+
+            require!(usdc_amount_out > 0, OndoError::InvalidAmount);
+
+            let usdon_amount_to_vault = normalize_decimals(
+                normalized_amount_out,
+                usdc_mint.decimals,
+                self.usdon_mint.decimals,
+                false,
+            )?;
+
+            // Only swap what the USDC vault can cover or queue, the user is paid the rest in USDon
+            let (usdc_amount_out, usdon_amount_to_vault) =
+                self.vault_payout(usdc_amount_out, usdon_amount_to_vault)?;
+
+            // Mint the swapped USDon straight into the USDon vault
+            if usdon_amount_to_vault != 0 {
+                mint_usdon_to(
+                    self.usdon_manager_state,
+                    &self.token_program.to_account_info(),
+                    &self.usdon_mint.to_account_info(),
+                    &self.usdon_vault.to_account_info(),
+                    &self.mint_authority.to_account_info(),
+                    signer_seeds,
+                    usdon_amount_to_vault,
+                )?;
+            }
+
+            if usdc_amount_out != 0 {
+                // Transfer USDC from the USDC vault to the user
+                transfer_checked(
+                    CpiContext::new_with_signer(
+                        self.leg_token_program()?.to_account_info(),
+                        TransferChecked {
+                            from: self.leg_vault()?.to_account_info(),
+                            mint: usdc_mint.to_account_info(),
+                            to: self.user_leg_token_account()?.to_account_info(),
+                            authority: self.usdon_manager_state.to_account_info(),
+                        },
+                        &[&[USDON_MANAGER_STATE_SEED, &[self.usdon_manager_state.bump]]],
+                    ),
+                    usdc_amount_out,
+                    usdc_mint.decimals,
+                )?;
+            }
+
+            mint_amount
+                .checked_sub(usdon_amount_to_vault)
+                .ok_or(OndoError::MathOverflow)?
+        };
+
+        // Mint USDon to the user's token account
+        if user_usdon_amount > 0 {
+            mint_usdon_to(
+                self.usdon_manager_state,
+                &self.token_program.to_account_info(),
+                &self.usdon_mint.to_account_info(),
+                &self.user_usdon_token_account.to_account_info(),
+                &self.mint_authority.to_account_info(),
+                signer_seeds,
+                user_usdon_amount,
+            )?;
+        }
+
+        // Burn the escrowed GM Tokens
+        burn_checked(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                BurnChecked {
+                    mint: self.mint.to_account_info(),
+                    from: escrow.clone(),
+                    authority: escrow_authority.clone(),
+                },
+                escrow_signer_seeds,
+            ),
+            amount,
+            self.mint.decimals,
+        )?;
+
+        Ok(mint_amount)
+    }
+}
+
+/// Splits a payout out of a vault holding `vault_balance` between the vault, USDon and the redemption queue.
+/// # Arguments
+/// * `amount_out` - The amount owed to the user out of the vault
+/// * `usdon_amount` - The amount of USDon exchanged for `amount_out`
+/// * `vault_balance` - The balance of the vault available to the payout
+/// * `mode` - How a shortfall is handled
+/// # Returns
+/// * `Result<(u64, u64, u64)>` - The amount paid out of the vault now, the USDon exchanged for it and
+///   the amount queued as a claim. When settling in USDon the exchanged USDon is rounded up so the
+///   remainder never exceeds the unpaid share, when queueing all of the USDon is exchanged
+fn split_vault_payout(
+    amount_out: u64,
+    usdon_amount: u64,
+    vault_balance: u64,
+    mode: VaultShortfallMode,
+) -> Result<(u64, u64, u64)> {
+    if vault_balance >= amount_out {
+        return Ok((amount_out, usdon_amount, 0));
+    }
+
+    match mode {
+        VaultShortfallMode::Revert => err!(OndoError::InsufficientVaultLiquidity),
+        VaultShortfallMode::SettleInUSDon => {
+            let usdon_swapped = mul_div(usdon_amount, vault_balance, amount_out, true)?;
+
+            Ok((vault_balance, usdon_swapped, 0))
+        }
+        VaultShortfallMode::Queue => Ok((vault_balance, usdon_amount, amount_out - vault_balance)),
+    }
 }
 
 /// Mints GM Tokens to the user's token account after verifying the attestation.
@@ -923,7 +1612,7 @@ impl<'a, 'info> TokenManager<'a, 'info> {
 /// * `attestation_id_account_bump` - The bump seed for the attestation ID account PDA.
 /// * `mint_authority_bump` - The bump seed for the mint authority PDA.
 /// # Returns
-/// * `Result<()>` - Ok if the minting process is successful, Err otherwise.
+/// * `Result<u64>` - The conversion rate of the payment, see `TokenManager::conversion_rate`.
 #[allow(clippy::too_many_arguments)]
 pub fn mint_with_attestation(
     ctx: &mut TokenManager,
@@ -935,70 +1624,22 @@ pub fn mint_with_attestation(
     ondo_user_bump: u8,
     attestation_id_account_bump: u8,
     mint_authority_bump: u8,
-) -> Result<()> {
+) -> Result<u64> {
     // Validate token accounts
     ctx.validate(is_usdon)?;
 
-    // Check if minting is paused
-    require!(
-        !ctx.gmtoken_manager_state.minting_paused,
-        OndoError::GMTokenMintingPaused
-    );
-
-    // Check if token-level minting is paused
-    require!(
-        !ctx.token_limit_account.minting_paused,
-        OndoError::GMTokenMintingPaused
-    );
-
-    // Verify user is whitelisted
-    ctx.verify_whitelist()?;
-
-    // Validate input parameters
-    require_gt!(amount, 0);
-    require_gt!(price, 0);
-
-    let current_timestamp = Clock::get()?.unix_timestamp;
-
-    ctx.gmtoken_manager_state
-        .check_is_valid_hours(current_timestamp)?;
-
-    // Check attestation expiration
-    require!(
-        current_timestamp < expiration,
-        OndoError::AttestationExpired
-    );
-    // on-chain double check that expiration is within allowed max duration
-    require!(
-        expiration - current_timestamp <= MAX_ATTESTATION_EXPIRATION,
-        OndoError::AttestationExpirationTooLarge
-    );
-
-    // Create ondo user account if it doesn't exist
-    ctx.initialize_ondo_user(ondo_user_bump)?;
-
-    // Create attestation account if it doesn't exist marking the attestation as consumed
-    ctx.initialize_attestation_account(
+    // Run the pause, whitelist, trading hours, attestation, sanity and rate limit checks
+    ctx.validate_trade(
         attestation_id,
-        current_timestamp,
-        attestation_id_account_bump,
-    )?;
-
-    // Verify the attestation signature
-    ctx.verify_attestation(
-        CHAIN_ID.to_bytes(),
-        attestation_id,
-        BUY,
         price,
         amount,
         expiration,
+        true,
+        ondo_user_bump,
+        attestation_id_account_bump,
     )?;
 
-    // Perform sanity check
-    ctx.sanity_check(price, current_timestamp)?;
-
-    // Check rate limit of the GM Token and user
-    ctx.rate_limit_check(price, amount, current_timestamp, true)?;
+    let conversion_rate = ctx.conversion_rate(is_usdon)?;
 
     // Handle payment based on user's choice of USDon or USDC
     match is_usdon {
@@ -1024,11 +1665,7 @@ pub fn mint_with_attestation(
             )?;
         }
         false => {
-            let usdc_mint_decimals = ctx
-                .usdc_mint
-                .as_ref()
-                .ok_or(OndoError::InvalidInputMint)?
-                .decimals;
+            let usdc_mint_decimals = ctx.leg_mint()?.decimals;
 
             // Calculate the amount of USDC to be sent based on the price
             let amount_sent = mul_div(price, amount, PRICE_SCALING_FACTOR as u64, true)?;
@@ -1038,38 +1675,17 @@ pub fn mint_with_attestation(
                 normalize_decimals(amount_sent, ctx.mint.decimals, usdc_mint_decimals, true)?;
 
             // If the user wants to pay in USDC, transfer USDC from user to USDC vault
-            let amount_to_burn = ctx.swap_usdc_to_usdon(normalized_amount)?;
+            let amount_to_burn = ctx.swap_usdc_to_usdon(normalized_amount, conversion_rate)?;
 
             // Then burn USDon from the USDon vault
-            burn_checked(
-                CpiContext::new_with_signer(
-                    ctx.token_program.to_account_info(),
-                    BurnChecked {
-                        mint: ctx.usdon_mint.to_account_info(),
-                        from: ctx.usdon_vault.to_account_info(),
-                        authority: ctx.mint_authority.to_account_info(),
-                    },
-                    &[&[MINT_AUTHORITY_SEED, &[mint_authority_bump]]],
-                ),
-                amount_to_burn,
-                ctx.usdon_mint.decimals,
-            )?;
+            ctx.burn_usdon_from_vault(amount_to_burn, mint_authority_bump)?;
         }
     }
 
     // Mint GM Tokens to the user's token account
-    mint_to(
-        CpiContext::new_with_signer(
-            ctx.token_program.to_account_info(),
-            MintTo {
-                mint: ctx.mint.to_account_info(),
-                to: ctx.user_token_account.to_account_info(),
-                authority: ctx.mint_authority.to_account_info(),
-            },
-            &[&[MINT_AUTHORITY_SEED, &[mint_authority_bump]]],
-        ),
-        amount,
-    )
+    ctx.mint_gm_tokens(amount, mint_authority_bump)?;
+
+    Ok(conversion_rate)
 }
 
 /// Redeems GM Tokens from the user's token account after verifying the attestation.
@@ -1085,7 +1701,7 @@ pub fn mint_with_attestation(
 /// * `attestation_id_account_bump` - The bump seed for the attestation ID account PDA.
 /// * `mint_authority_bump` - The bump seed for the mint authority PDA.
 /// # Returns
-/// * `Result<()>` - Ok if the redemption process is successful, Err otherwise.
+/// * `Result<u64>` - The conversion rate of the payout, see `TokenManager::conversion_rate`.
 #[allow(clippy::too_many_arguments)]
 pub fn redeem_with_attestation(
     ctx: &mut TokenManager,
@@ -1097,71 +1713,22 @@ pub fn redeem_with_attestation(
     ondo_user_bump: u8,
     attestation_id_account_bump: u8,
     mint_authority_bump: u8,
-) -> Result<()> {
+) -> Result<u64> {
     // Validate token accounts
     ctx.validate(is_usdon)?;
 
-    // Check if redemptions are paused
-    require!(
-        !ctx.gmtoken_manager_state.redemption_paused,
-        OndoError::GMTokenRedemptionPaused
-    );
-
-    // Check if token-level redemptions are paused
-    require!(
-        !ctx.token_limit_account.redemption_paused,
-        OndoError::GMTokenRedemptionPaused
-    );
-
-    // Verify user is whitelisted
-    ctx.verify_whitelist()?;
-
-    // Validate input parameters
-    require_gt!(amount, 0);
-    require_gt!(price, 0);
-
-    let current_timestamp = Clock::get()?.unix_timestamp;
-
-    ctx.gmtoken_manager_state
-        .check_is_valid_hours(current_timestamp)?;
-
-    // Check attestation expiration
-    require!(
-        current_timestamp < expiration,
-        OndoError::AttestationExpired
-    );
-
-    // on-chain double check that expiration is within allowed max duration
-    require!(
-        expiration - current_timestamp <= MAX_ATTESTATION_EXPIRATION,
-        OndoError::AttestationExpirationTooLarge
-    );
-
-    // Create ondo user account if it doesn't exist
-    ctx.initialize_ondo_user(ondo_user_bump)?;
-
-    // Create attestation account if it doesn't exist marking the attestation as consumed
-    ctx.initialize_attestation_account(
-        attestation_id,
-        current_timestamp,
-        attestation_id_account_bump,
-    )?;
-
-    // Verify the attestation signature
-    ctx.verify_attestation(
-        CHAIN_ID.to_bytes(),
+    // Run the pause, whitelist, trading hours, attestation, sanity and rate limit checks
+    ctx.validate_trade(
         attestation_id,
-        SELL,
         price,
         amount,
         expiration,
+        false,
+        ondo_user_bump,
+        attestation_id_account_bump,
     )?;
 
-    // Perform sanity check
-    ctx.sanity_check(price, current_timestamp)?;
-
-    // Check rate limit of the GM Token and user
-    ctx.rate_limit_check(price, amount, current_timestamp, false)?;
+    let conversion_rate = ctx.conversion_rate(is_usdon)?;
 
     // Round down: Protocol pays - protects the protocol
     let mint_amount = mul_div(price, amount, PRICE_SCALING_FACTOR as u64, false)?;
@@ -1172,23 +1739,20 @@ pub fn redeem_with_attestation(
     let signer_seeds = &[&seeds[..]];
 
     // Mint USDon to user's token account
-    mint_to(
-        CpiContext::new_with_signer(
-            ctx.token_program.to_account_info(),
-            MintTo {
-                mint: ctx.usdon_mint.to_account_info(),
-                to: ctx.user_usdon_token_account.to_account_info(),
-                authority: ctx.mint_authority.to_account_info(),
-            },
-            signer_seeds,
-        ),
+    mint_usdon_to(
+        ctx.usdon_manager_state,
+        &ctx.token_program.to_account_info(),
+        &ctx.usdon_mint.to_account_info(),
+        &ctx.user_usdon_token_account.to_account_info(),
+        &ctx.mint_authority.to_account_info(),
+        signer_seeds,
         mint_amount,
     )?;
 
     if !is_usdon {
         // If the user wants to be paid in USDC, transfer USDon from user to the USDon vault
         // Then transfer USDC from the USDC vault to the user
-        ctx.swap_usdon_to_usdc(mint_amount)?;
+        ctx.swap_usdon_to_usdc(mint_amount, conversion_rate)?;
     }
 
     // Burn GM tokens from the user's token account
@@ -1203,7 +1767,9 @@ pub fn redeem_with_attestation(
         ),
         amount,
         ctx.mint.decimals,
-    )
+    )?;
+
+    Ok(conversion_rate)
 }
 
 /// Errors related to secp256k1 signature verification.
@@ -1227,6 +1793,7 @@ pub enum SecpError {
 mod tests {
     use super::*;
     use proptest::prelude::*;
+    use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
 
     // Helper functions for sanity check testing
     fn validate_price_deviation(
@@ -1284,6 +1851,38 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_split_vault_payout() {
+        // Vault covers the payout
+        assert_eq!(
+            split_vault_payout(1_000, 1_000_000, 1_000, VaultShortfallMode::Revert).unwrap(),
+            (1_000, 1_000_000, 0)
+        );
+
+        // Vault is short
+        assert!(split_vault_payout(1_000, 1_000_000, 999, VaultShortfallMode::Revert).is_err());
+        assert_eq!(
+            split_vault_payout(1_000, 1_000_000, 400, VaultShortfallMode::SettleInUSDon).unwrap(),
+            (400, 400_000, 0)
+        );
+        assert_eq!(
+            split_vault_payout(1_000, 1_000_000, 400, VaultShortfallMode::Queue).unwrap(),
+            (400, 1_000_000, 600)
+        );
+
+        // Rounds the swapped USDon up
+        assert_eq!(
+            split_vault_payout(3, 1_000, 1, VaultShortfallMode::SettleInUSDon).unwrap(),
+            (1, 334, 0)
+        );
+
+        // Empty vault
+        assert_eq!(
+            split_vault_payout(1_000, 1_000_000, 0, VaultShortfallMode::SettleInUSDon).unwrap(),
+            (0, 0, 0)
+        );
+    }
+
     #[test]
     fn test_price_update_v2_deserialization() {
         // Retreived using `solana account <account_address>` on a real Pyth price account
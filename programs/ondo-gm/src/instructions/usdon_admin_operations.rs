@@ -1,14 +1,17 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
-    token_2022::{burn_checked, mint_to, BurnChecked, MintTo},
+    token_2022::{burn_checked, BurnChecked},
     token_interface::{Mint, TokenAccount, TokenInterface},
 };
 
 use crate::{
-    constants::{MAX_MINT_AMOUNT, MINT_AUTHORITY_SEED, USDON_MANAGER_STATE_SEED},
+    constants::{
+        MAX_MINT_AMOUNT, MINTER_ALLOWANCE_SEED, MINT_AUTHORITY_SEED, USDON_MANAGER_STATE_SEED,
+    },
     errors::OndoError,
     events::{RoleGranted, RoleRevoked},
-    state::{RoleType, Roles, USDonManagerState},
+    state::{MinterAllowance, RoleType, Roles, USDonManagerState},
+    utils::mint_usdon_to,
 };
 
 /// Grant a USDon role for a user by creating a `Roles` account
@@ -193,6 +196,17 @@ pub struct USDonMinter<'info> {
     )]
     pub authority_role_account: Account<'info, Roles>,
 
+    /// The MinterAllowance account limiting how much USDon the authority can mint
+    /// # PDA Seeds
+    /// - MINTER_ALLOWANCE_SEED
+    /// - The authority's address
+    #[account(
+        mut,
+        seeds = [MINTER_ALLOWANCE_SEED, authority.key().as_ref()],
+        bump = minter_allowance.bump,
+    )]
+    pub minter_allowance: Account<'info, MinterAllowance>,
+
     /// The USDon mint
     #[account(
         mut,
@@ -229,18 +243,18 @@ impl<'info> USDonMinter<'info> {
             OndoError::AmountExceedsMaxMintAmount
         );
 
+        self.minter_allowance
+            .record_mint(amount, Clock::get()?.unix_timestamp)?;
+
         // Mint USDon to the destination account
         // Uses the mint authority PDA to sign
-        mint_to(
-            CpiContext::new_with_signer(
-                self.token_program.to_account_info(),
-                MintTo {
-                    mint: self.mint.to_account_info(),
-                    to: self.destination.to_account_info(),
-                    authority: self.mint_authority.to_account_info(),
-                },
-                &[&[MINT_AUTHORITY_SEED, &[bump]]],
-            ),
+        mint_usdon_to(
+            &self.usdon_manager_state,
+            &self.token_program.to_account_info(),
+            &self.mint.to_account_info(),
+            &self.destination.to_account_info(),
+            &self.mint_authority.to_account_info(),
+            &[&[MINT_AUTHORITY_SEED, &[bump]]],
             amount,
         )
     }
@@ -289,6 +303,20 @@ pub struct USDonBurner<'info> {
     )]
     pub authority_role_account: Account<'info, Roles>,
 
+    /// The MinterAllowance address of the authority, always required
+    /// Records the burn in the authority's lifetime burned amount if the authority is also a minter
+    /// # PDA Seeds
+    /// - MINTER_ALLOWANCE_SEED
+    /// - The authority's address
+    ///
+    /// CHECK: The address is verified by the seeds, the account is only deserialized if it is initialized
+    #[account(
+        mut,
+        seeds = [MINTER_ALLOWANCE_SEED, authority.key().as_ref()],
+        bump,
+    )]
+    pub minter_allowance: UncheckedAccount<'info>,
+
     /// The USDon mint
     #[account(
         mut,
@@ -322,6 +350,16 @@ impl<'info> USDonBurner<'info> {
         // Validate amount
         require_gt!(amount, 0, OndoError::InvalidAmount);
 
+        // The allowance only exists if the authority is also a minter
+        if *self.minter_allowance.owner == crate::ID {
+            let mut minter_allowance = MinterAllowance::try_deserialize(
+                &mut &self.minter_allowance.try_borrow_data()?[..],
+            )?;
+            minter_allowance.record_burn(amount)?;
+            minter_allowance
+                .try_serialize(&mut &mut self.minter_allowance.try_borrow_mut_data()?[..])?;
+        }
+
         // Burn USDon from the destination account
         burn_checked(
             CpiContext::new_with_signer(
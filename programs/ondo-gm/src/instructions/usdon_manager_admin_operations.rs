@@ -1,14 +1,24 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{prelude::*, Discriminator};
 use anchor_spl::{
     token_2022,
-    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+    token_interface::{Mint, TokenAccount, TokenInterface},
 };
 
 use crate::{
-    constants::{MAX_AGE_UPPER_BOUND, MINT_AUTHORITY_SEED, USDON_MANAGER_STATE_SEED},
+    constants::{
+        BASIS_POINTS_DIVISOR, MAX_AGE_UPPER_BOUND, MAX_CONFIDENCE_THRESHOLD_BPS,
+        MAX_CONVERSION_BAND_BPS, MAX_PRICE_UPPER_BOUND, MAX_PSM_FEE_BPS, MINT_AUTHORITY_SEED,
+        MIN_PRICE_LOWER_BOUND, RETRIEVAL_DESTINATION_SEED, RETRIEVAL_LIMIT_SEED, USDC_PEG_PRICE,
+        USDON_MANAGER_STATE_SEED,
+    },
     errors::OndoError,
     events::TokensRetrieved,
-    state::{RoleType, Roles, USDonManagerState},
+    instructions::transfer_from_vault,
+    state::{
+        OracleAdapter, RetrievalDestination, RetrievalLimit, RoleType, Roles, USDonManagerState,
+        VaultShortfallMode,
+    },
+    utils::migrate_account_len,
 };
 
 #[cfg(any(feature = "mainnet", feature = "testnet"))]
@@ -141,6 +151,32 @@ impl<'info> InitializeUSDonManager<'info> {
             usdc_vault: self.usdc_vault.key(),
             usdon_vault: self.usdon_vault.key(),
             bump: bumps.usdon_manager_state,
+            usdc_feed_id: [0; 32],
+            usdc_price_update_owner: Pubkey::default(),
+            usdc_oracle_adapter: OracleAdapter::Pyth,
+            usdc_secondary_price_update: Pubkey::default(),
+            usdc_secondary_oracle_adapter: OracleAdapter::Pyth,
+            usdc_secondary_feed_id: [0; 32],
+            usdc_secondary_price_update_owner: Pubkey::default(),
+            usdc_oracle_max_divergence_bps: 0,
+            usdc_min_price: 0,
+            usdc_max_price: 0,
+            usdc_confidence_threshold_bps: 0,
+            usdc_oracle_conversion_enabled: false,
+            usdc_conversion_band_bps: 0,
+            usdc_vault_shortfall_mode: VaultShortfallMode::Revert,
+            psm_fee_bps: 0,
+            psm_deposit_paused: false,
+            psm_redemption_paused: false,
+            psm_rate_limit: 0,
+            psm_limit_window: 0,
+            psm_deposit_capacity_used: 0,
+            psm_deposit_last_updated: 0,
+            psm_redemption_capacity_used: 0,
+            psm_redemption_last_updated: 0,
+            usdon_minting_paused: false,
+            usdon_supply_cap: 0,
+            queued_usdc: 0,
         });
 
         Ok(())
@@ -231,6 +267,273 @@ impl<'info> USDonManagerAdmin<'info> {
 
         Ok(())
     }
+
+    /// Set the USDC price feed ID and the program that must own the price update account
+    /// # Arguments
+    /// * `usdc_feed_id` - The Pyth USDC/USD feed ID, all zeroes for the default `USDC_PYTH_ID`
+    /// * `usdc_price_update_owner` - The owner of the price update account, default pubkey for the Pyth receiver program
+    /// # Returns
+    /// * `Result<()>` - Ok if the USDC oracle feed is successfully set, Err otherwise
+    pub fn set_usdc_oracle_feed(
+        &mut self,
+        usdc_feed_id: [u8; 32],
+        usdc_price_update_owner: Pubkey,
+    ) -> Result<()> {
+        self.usdon_manager_state.usdc_feed_id = usdc_feed_id;
+        self.usdon_manager_state.usdc_price_update_owner = usdc_price_update_owner;
+
+        Ok(())
+    }
+
+    /// Select the kind of oracle account the USDC price is read from
+    /// # Arguments
+    /// * `usdc_oracle_adapter` - The oracle adapter used for USDC price checks
+    /// # Returns
+    /// * `Result<()>` - Ok if the USDC oracle adapter is successfully set, Err otherwise
+    pub fn set_usdc_oracle_adapter(&mut self, usdc_oracle_adapter: OracleAdapter) -> Result<()> {
+        self.usdon_manager_state.usdc_oracle_adapter = usdc_oracle_adapter;
+
+        Ok(())
+    }
+
+    /// Configure the secondary USDC price oracle
+    /// The secondary price is used when the primary price is stale or too uncertain,
+    /// and cross-checked against the primary when both are usable
+    /// # Arguments
+    /// * `price_update` - The secondary price oracle account, default pubkey to disable the secondary oracle
+    /// * `oracle_adapter` - The kind of oracle account the secondary price is read from
+    /// * `feed_id` - The Pyth USDC/USD feed ID, all zeroes for the default `USDC_PYTH_ID`
+    /// * `price_update_owner` - The owner of the price update account, default pubkey for the adapter's default owner
+    /// * `max_divergence_bps` - The maximum divergence between both prices, 0 to disable the cross-check
+    /// # Returns
+    /// * `Result<()>` - Ok if the secondary USDC oracle is successfully set, Err otherwise
+    pub fn set_usdc_secondary_oracle(
+        &mut self,
+        price_update: Pubkey,
+        oracle_adapter: OracleAdapter,
+        feed_id: [u8; 32],
+        price_update_owner: Pubkey,
+        max_divergence_bps: u64,
+    ) -> Result<()> {
+        // The secondary oracle must be a distinct account
+        require_keys_neq!(
+            price_update,
+            self.usdon_manager_state.usdc_price_update,
+            OndoError::InvalidOraclePriceAddress
+        );
+
+        require_gte!(
+            BASIS_POINTS_DIVISOR,
+            max_divergence_bps,
+            OndoError::InvalidOracleDivergence
+        );
+
+        self.usdon_manager_state.usdc_secondary_price_update = price_update;
+        self.usdon_manager_state.usdc_secondary_oracle_adapter = oracle_adapter;
+        self.usdon_manager_state.usdc_secondary_feed_id = feed_id;
+        self.usdon_manager_state.usdc_secondary_price_update_owner = price_update_owner;
+        self.usdon_manager_state.usdc_oracle_max_divergence_bps = max_divergence_bps;
+
+        Ok(())
+    }
+
+    /// Set the accepted USDC price range (depeg bounds)
+    /// # Arguments
+    /// * `usdc_min_price` - The depeg floor, between MIN_PRICE_LOWER_BOUND and USDC_PEG_PRICE
+    /// * `usdc_max_price` - The depeg ceiling, between USDC_PEG_PRICE and MAX_PRICE_UPPER_BOUND, 0 to disable it
+    /// # Returns
+    /// * `Result<()>` - Ok if the USDC price bounds are successfully set, Err otherwise
+    pub fn set_usdc_price_bounds(
+        &mut self,
+        usdc_min_price: u64,
+        usdc_max_price: u64,
+    ) -> Result<()> {
+        require!(
+            (MIN_PRICE_LOWER_BOUND..=USDC_PEG_PRICE).contains(&usdc_min_price),
+            OndoError::InvalidUSDCPriceBounds
+        );
+
+        require!(
+            usdc_max_price == 0
+                || (USDC_PEG_PRICE..=MAX_PRICE_UPPER_BOUND).contains(&usdc_max_price),
+            OndoError::InvalidUSDCPriceBounds
+        );
+
+        self.usdon_manager_state.usdc_min_price = usdc_min_price;
+        self.usdon_manager_state.usdc_max_price = usdc_max_price;
+
+        Ok(())
+    }
+
+    /// Set the maximum accepted oracle confidence interval
+    /// # Arguments
+    /// * `confidence_threshold_bps` - The threshold in basis points of the price (must be > 0 and <= MAX_CONFIDENCE_THRESHOLD_BPS)
+    /// # Returns
+    /// * `Result<()>` - Ok if the confidence threshold is successfully set, Err otherwise
+    pub fn set_usdc_confidence_threshold(&mut self, confidence_threshold_bps: u64) -> Result<()> {
+        require_gt!(
+            confidence_threshold_bps,
+            0,
+            OndoError::InvalidConfidenceThreshold
+        );
+
+        require_gte!(
+            MAX_CONFIDENCE_THRESHOLD_BPS,
+            confidence_threshold_bps,
+            OndoError::InvalidConfidenceThreshold
+        );
+
+        self.usdon_manager_state.usdc_confidence_threshold_bps = confidence_threshold_bps;
+
+        Ok(())
+    }
+
+    /// Enable or disable converting USDC at the oracle price instead of 1:1
+    /// # Arguments
+    /// * `is_enabled` - Whether USDC is converted at the oracle price
+    /// * `conversion_band_bps` - The band around the peg within which the oracle price is used (must be > 0 and <= MAX_CONVERSION_BAND_BPS when enabled)
+    /// # Returns
+    /// * `Result<()>` - Ok if the oracle conversion is successfully set, Err otherwise
+    pub fn set_usdc_oracle_conversion(
+        &mut self,
+        is_enabled: bool,
+        conversion_band_bps: u64,
+    ) -> Result<()> {
+        if is_enabled {
+            require_gt!(conversion_band_bps, 0, OndoError::InvalidConversionBand);
+        }
+
+        require_gte!(
+            MAX_CONVERSION_BAND_BPS,
+            conversion_band_bps,
+            OndoError::InvalidConversionBand
+        );
+
+        self.usdon_manager_state.usdc_oracle_conversion_enabled = is_enabled;
+        self.usdon_manager_state.usdc_conversion_band_bps = conversion_band_bps;
+
+        Ok(())
+    }
+
+    /// Select how redemptions are settled when the USDC vault cannot cover the payout
+    /// # Arguments
+    /// * `usdc_vault_shortfall_mode` - Revert, settle the shortfall in USDon or queue it
+    /// # Returns
+    /// * `Result<()>` - Ok if the shortfall mode is successfully set, Err otherwise
+    pub fn set_usdc_vault_shortfall_mode(
+        &mut self,
+        usdc_vault_shortfall_mode: VaultShortfallMode,
+    ) -> Result<()> {
+        self.usdon_manager_state.usdc_vault_shortfall_mode = usdc_vault_shortfall_mode;
+
+        Ok(())
+    }
+
+    /// Set the fee charged on PSM deposits and redemptions
+    /// # Arguments
+    /// * `psm_fee_bps` - The fee in basis points (must be <= MAX_PSM_FEE_BPS)
+    /// # Returns
+    /// * `Result<()>` - Ok if the fee is successfully set, Err otherwise
+    pub fn set_psm_fee(&mut self, psm_fee_bps: u64) -> Result<()> {
+        require_gte!(MAX_PSM_FEE_BPS, psm_fee_bps, OndoError::InvalidPSMFee);
+
+        self.usdon_manager_state.psm_fee_bps = psm_fee_bps;
+
+        Ok(())
+    }
+
+    /// Set the PSM rate limit, shared by the deposit and redemption directions
+    /// # Arguments
+    /// * `rate_limit` - The maximum amount of USDon minted or redeemed in the limit window (must be > 0)
+    /// * `limit_window` - The limit window in seconds (must be > 0)
+    /// # Returns
+    /// * `Result<()>` - Ok if the rate limit is successfully set, Err otherwise
+    pub fn set_psm_rate_limit(&mut self, rate_limit: u64, limit_window: u64) -> Result<()> {
+        require_gt!(rate_limit, 0, OndoError::InvalidRateLimit);
+        require_gt!(limit_window, 0, OndoError::InvalidRateLimit);
+
+        self.usdon_manager_state.psm_rate_limit = rate_limit;
+        self.usdon_manager_state.psm_limit_window = limit_window;
+
+        Ok(())
+    }
+
+    /// Pause or unpause PSM deposits and redemptions
+    /// # Arguments
+    /// * `deposit_paused` - Whether USDC deposits are paused
+    /// * `redemption_paused` - Whether USDon redemptions are paused
+    /// # Returns
+    /// * `Result<()>` - Ok if the pause flags are successfully set, Err otherwise
+    pub fn set_psm_paused(&mut self, deposit_paused: bool, redemption_paused: bool) -> Result<()> {
+        self.usdon_manager_state.psm_deposit_paused = deposit_paused;
+        self.usdon_manager_state.psm_redemption_paused = redemption_paused;
+
+        Ok(())
+    }
+
+    /// Pause or resume USDon minting, e.g. once reserves are restored after `reconcile_reserves` paused it
+    /// # Arguments
+    /// * `paused` - Whether USDon minting is paused
+    /// # Returns
+    /// * `Result<()>` - Ok if the pause flag is successfully set, Err otherwise
+    pub fn set_usdon_minting_paused(&mut self, paused: bool) -> Result<()> {
+        self.usdon_manager_state.usdon_minting_paused = paused;
+
+        Ok(())
+    }
+}
+
+/// Grow the USDon Manager state account to the current layout
+/// Requires `ADMIN_ROLE_USDON_MANAGER` role
+/// Fields added since the account was created read as zero, which selects their defaults
+#[derive(Accounts)]
+pub struct MigrateUSDonManagerState<'info> {
+    /// The account with the authority to execute the migration, pays for the extra rent
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The Roles account verifying the authority has the `ADMIN_ROLE_USDON_MANAGER` role
+    /// # PDA Seeds
+    /// - ADMIN_ROLE_USDON_MANAGER
+    /// - The authority's address
+    #[account(
+        seeds = [RoleType::ADMIN_ROLE_USDON_MANAGER, authority.key().as_ref()],
+        bump = authority_role_account.bump,
+    )]
+    pub authority_role_account: Account<'info, Roles>,
+
+    /// The USDonManagerState account to be migrated
+    /// # PDA Seeds
+    /// - USDON_MANAGER_STATE_SEED
+    ///
+    /// CHECK: Seeds and owner constraints validate the account.
+    /// Not deserialized as it may be smaller than the current layout.
+    #[account(
+        mut,
+        seeds = [USDON_MANAGER_STATE_SEED],
+        bump,
+        owner = crate::ID,
+    )]
+    pub usdon_manager_state: UncheckedAccount<'info>,
+
+    /// The system program
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateUSDonManagerState<'info> {
+    /// Resize the account to the current layout, topping up rent if needed
+    /// Does nothing if the account already has the current size
+    /// # Returns
+    /// * `Result<()>` - Ok if the account is migrated, Err otherwise
+    pub fn migrate_usdon_manager_state(&self) -> Result<()> {
+        migrate_account_len(
+            &self.usdon_manager_state.to_account_info(),
+            USDonManagerState::DISCRIMINATOR,
+            8 + USDonManagerState::INIT_SPACE,
+            &self.authority.to_account_info(),
+            &self.system_program.to_account_info(),
+        )
+    }
 }
 
 /// Retrieve (withdraw) tokens from a vault
@@ -283,37 +586,61 @@ pub struct RetrieveTokens<'info> {
     )]
     pub destination: InterfaceAccount<'info, TokenAccount>,
 
+    /// The RetrievalDestination account allowlisting the destination
+    /// # PDA Seeds
+    /// - RETRIEVAL_DESTINATION_SEED
+    /// - The destination's address
+    #[account(
+        seeds = [RETRIEVAL_DESTINATION_SEED, destination.key().as_ref()],
+        bump = retrieval_destination.bump,
+    )]
+    pub retrieval_destination: Account<'info, RetrievalDestination>,
+
+    /// The RetrievalLimit account of the mint
+    /// # PDA Seeds
+    /// - RETRIEVAL_LIMIT_SEED
+    /// - The mint's address
+    #[account(
+        mut,
+        seeds = [RETRIEVAL_LIMIT_SEED, token_mint.key().as_ref()],
+        bump = retrieval_limit.bump,
+    )]
+    pub retrieval_limit: Account<'info, RetrievalLimit>,
+
     /// The token program (SPL Token or Token-2022)
     pub token_program: Interface<'info, TokenInterface>,
 }
 
 impl<'info> RetrieveTokens<'info> {
-    /// Retrieve tokens from the vault
+    /// Retrieve tokens from the vault to an allowlisted destination
     /// # Arguments
     /// * `amount` - The amount of tokens to retrieve
     /// # Returns
     /// * `Result<()>` - Ok if the tokens are successfully retrieved, Err otherwise
-    pub fn retrieve_tokens(&self, amount: u64) -> Result<()> {
+    /// # Errors
+    /// * `OndoError::RetrievalRequiresTimelock` - If the amount is above the timelock threshold of the mint
+    /// * `OndoError::InvalidRateLimit` - If the amount exceeds the withdrawal limit of the mint
+    pub fn retrieve_tokens(&mut self, amount: u64) -> Result<()> {
         // Validate amount is not zero
         require!(amount > 0, OndoError::InvalidAmount);
 
+        // Large retrievals must go through `request_retrieval`
+        require!(
+            !self.retrieval_limit.requires_timelock(amount),
+            OndoError::RetrievalRequiresTimelock
+        );
+
+        self.retrieval_limit
+            .record_retrieval(amount, Clock::get()?.unix_timestamp)?;
+
         // Transfer tokens from vault to destination
-        let seeds = &[USDON_MANAGER_STATE_SEED, &[self.usdon_manager_state.bump]];
-        let signer_seeds = &[&seeds[..]];
-
-        transfer_checked(
-            CpiContext::new_with_signer(
-                self.token_program.to_account_info(),
-                TransferChecked {
-                    from: self.source_vault.to_account_info(),
-                    mint: self.token_mint.to_account_info(),
-                    to: self.destination.to_account_info(),
-                    authority: self.usdon_manager_state.to_account_info(),
-                },
-                signer_seeds,
-            ),
+        transfer_from_vault(
+            &self.token_program,
+            &self.usdon_manager_state,
+            &self.source_vault,
+            &self.token_mint,
+            &self.destination,
             amount,
-            self.token_mint.decimals,
         )?;
 
         // Emit event for tokens retrieved
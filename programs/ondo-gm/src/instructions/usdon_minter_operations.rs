@@ -0,0 +1,129 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{MINTER_ALLOWANCE_SEED, USDON_MANAGER_STATE_SEED},
+    errors::OndoError,
+    events::{MinterAllowanceSet, USDonSupplyCapSet},
+    state::{MinterAllowance, RoleType, Roles, USDonManagerState},
+};
+
+/// Set the rolling USDon mint allowance of a minter, creating its allowance account if needed
+/// Requires the `GUARDIAN_USDON` role
+#[derive(Accounts)]
+#[instruction(minter: Pubkey)]
+pub struct SetMinterAllowance<'info> {
+    /// Pays for account creation
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The account with the authority to set the allowance
+    pub authority: Signer<'info>,
+
+    /// The Roles account verifying the authority has the `GUARDIAN_USDON` role
+    /// # PDA Seeds
+    /// - GUARDIAN_USDON
+    /// - The authority's address
+    #[account(
+        seeds = [RoleType::GUARDIAN_USDON, authority.key().as_ref()],
+        bump = authority_role_account.bump,
+    )]
+    pub authority_role_account: Account<'info, Roles>,
+
+    /// The MinterAllowance account of the minter
+    /// # PDA Seeds
+    /// - MINTER_ALLOWANCE_SEED
+    /// - The minter's address
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + MinterAllowance::INIT_SPACE,
+        seeds = [MINTER_ALLOWANCE_SEED, minter.as_ref()],
+        bump,
+    )]
+    pub minter_allowance: Account<'info, MinterAllowance>,
+
+    /// The system program
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetMinterAllowance<'info> {
+    /// Set the allowance of a minter, keeping the allowance used and the lifetime totals
+    /// # Arguments
+    /// * `minter` - The minter the allowance applies to
+    /// * `rate_limit` - The maximum amount of USDon the minter can mint in the limit window, 0 blocks minting
+    /// * `limit_window` - The limit window in seconds (must be > 0)
+    /// * `bump` - The bump of the allowance PDA
+    /// # Returns
+    /// * `Result<()>` - Ok if the allowance is successfully set, Err otherwise
+    pub fn set_minter_allowance(
+        &mut self,
+        minter: Pubkey,
+        rate_limit: u64,
+        limit_window: u64,
+        bump: u8,
+    ) -> Result<()> {
+        require_gt!(limit_window, 0, OndoError::InvalidRateLimit);
+
+        let allowance = &mut self.minter_allowance;
+        allowance.minter = minter;
+        allowance.rate_limit = rate_limit;
+        allowance.limit_window = limit_window;
+        allowance.bump = bump;
+
+        emit!(MinterAllowanceSet {
+            minter,
+            rate_limit,
+            limit_window,
+            authority: self.authority.key(),
+        });
+
+        Ok(())
+    }
+}
+
+/// Set the global USDon supply ceiling
+/// Requires the `GUARDIAN_USDON` role
+#[derive(Accounts)]
+pub struct SetUSDonSupplyCap<'info> {
+    /// The account with the authority to set the supply ceiling
+    pub authority: Signer<'info>,
+
+    /// The Roles account verifying the authority has the `GUARDIAN_USDON` role
+    /// # PDA Seeds
+    /// - GUARDIAN_USDON
+    /// - The authority's address
+    #[account(
+        seeds = [RoleType::GUARDIAN_USDON, authority.key().as_ref()],
+        bump = authority_role_account.bump,
+    )]
+    pub authority_role_account: Account<'info, Roles>,
+
+    /// The USDonManagerState account
+    /// # PDA Seeds
+    /// - USDON_MANAGER_STATE_SEED
+    #[account(
+        mut,
+        seeds = [USDON_MANAGER_STATE_SEED],
+        bump = usdon_manager_state.bump,
+    )]
+    pub usdon_manager_state: Account<'info, USDonManagerState>,
+}
+
+impl<'info> SetUSDonSupplyCap<'info> {
+    /// Set the maximum USDon supply, above which USDon cannot be minted
+    /// # Arguments
+    /// * `usdon_supply_cap` - The supply ceiling in USDon base units, 0 removes the ceiling
+    /// # Returns
+    /// * `Result<()>` - Ok if the supply ceiling is successfully set, Err otherwise
+    pub fn set_usdon_supply_cap(&mut self, usdon_supply_cap: u64) -> Result<()> {
+        let prev_usdon_supply_cap = self.usdon_manager_state.usdon_supply_cap;
+        self.usdon_manager_state.usdon_supply_cap = usdon_supply_cap;
+
+        emit!(USDonSupplyCapSet {
+            prev_usdon_supply_cap,
+            new_usdon_supply_cap: usdon_supply_cap,
+        });
+
+        Ok(())
+    }
+}
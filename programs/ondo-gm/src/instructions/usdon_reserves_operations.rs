@@ -0,0 +1,293 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::Token,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    constants::{USDON_MANAGER_STATE_SEED, USDON_RESERVES_SEED},
+    errors::OndoError,
+    events::{ReservesAttested, ReservesReconciled},
+    state::{collateralization_bps, RoleType, Roles, USDonManagerState, USDonReserves},
+    utils::normalize_decimals,
+};
+
+/// Create the account tracking the reserves backing USDon
+/// Requires `ADMIN_ROLE_USDON_MANAGER` role
+#[derive(Accounts)]
+pub struct InitializeUSDonReserves<'info> {
+    /// The account with the authority to create the reserves account, pays for account creation
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The Roles account verifying the authority has the `ADMIN_ROLE_USDON_MANAGER` role
+    /// # PDA Seeds
+    /// - ADMIN_ROLE_USDON_MANAGER
+    /// - The authority's address
+    #[account(
+        seeds = [RoleType::ADMIN_ROLE_USDON_MANAGER, authority.key().as_ref()],
+        bump = authority_role_account.bump,
+    )]
+    pub authority_role_account: Account<'info, Roles>,
+
+    /// The USDonReserves account being created
+    /// # PDA Seeds
+    /// - USDON_RESERVES_SEED
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + USDonReserves::INIT_SPACE,
+        seeds = [USDON_RESERVES_SEED],
+        bump,
+    )]
+    pub usdon_reserves: Account<'info, USDonReserves>,
+
+    /// The system program
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializeUSDonReserves<'info> {
+    /// Create the reserves account with no attested reserves
+    /// # Arguments
+    /// * `custodian` - The key allowed to attest off-chain reserves
+    /// * `min_collateralization_bps` - The collateralization below which USDon minting is paused (must be > 0)
+    /// * `max_attestation_age` - How long (in seconds) an attestation is counted (must be > 0)
+    /// * `bump` - The bump of the reserves PDA
+    /// # Returns
+    /// * `Result<()>` - Ok if the reserves account is created, Err otherwise
+    pub fn initialize_usdon_reserves(
+        &mut self,
+        custodian: Pubkey,
+        min_collateralization_bps: u64,
+        max_attestation_age: u64,
+        bump: u8,
+    ) -> Result<()> {
+        validate_reserves_config(min_collateralization_bps, max_attestation_age)?;
+
+        self.usdon_reserves.set_inner(USDonReserves {
+            custodian,
+            offchain_reserves: 0,
+            attested_at: 0,
+            max_attestation_age,
+            min_collateralization_bps,
+            last_collateralization_bps: 0,
+            last_reconciled_at: 0,
+            bump,
+        });
+
+        Ok(())
+    }
+}
+
+/// Update the reserves configuration
+/// Requires `ADMIN_ROLE_USDON_MANAGER` role
+#[derive(Accounts)]
+pub struct USDonReservesAdmin<'info> {
+    /// The account with the authority to update the configuration
+    pub authority: Signer<'info>,
+
+    /// The Roles account verifying the authority has the `ADMIN_ROLE_USDON_MANAGER` role
+    /// # PDA Seeds
+    /// - ADMIN_ROLE_USDON_MANAGER
+    /// - The authority's address
+    #[account(
+        seeds = [RoleType::ADMIN_ROLE_USDON_MANAGER, authority.key().as_ref()],
+        bump = authority_role_account.bump,
+    )]
+    pub authority_role_account: Account<'info, Roles>,
+
+    /// The USDonReserves account
+    /// # PDA Seeds
+    /// - USDON_RESERVES_SEED
+    #[account(
+        mut,
+        seeds = [USDON_RESERVES_SEED],
+        bump = usdon_reserves.bump,
+    )]
+    pub usdon_reserves: Account<'info, USDonReserves>,
+}
+
+impl<'info> USDonReservesAdmin<'info> {
+    /// Set the custodian and the reconciliation thresholds
+    /// # Arguments
+    /// * `custodian` - The key allowed to attest off-chain reserves
+    /// * `min_collateralization_bps` - The collateralization below which USDon minting is paused (must be > 0)
+    /// * `max_attestation_age` - How long (in seconds) an attestation is counted (must be > 0)
+    /// # Returns
+    /// * `Result<()>` - Ok if the configuration is successfully set, Err otherwise
+    pub fn set_usdon_reserves_config(
+        &mut self,
+        custodian: Pubkey,
+        min_collateralization_bps: u64,
+        max_attestation_age: u64,
+    ) -> Result<()> {
+        validate_reserves_config(min_collateralization_bps, max_attestation_age)?;
+
+        self.usdon_reserves.custodian = custodian;
+        self.usdon_reserves.min_collateralization_bps = min_collateralization_bps;
+        self.usdon_reserves.max_attestation_age = max_attestation_age;
+
+        Ok(())
+    }
+}
+
+/// Record the off-chain reserves backing USDon
+/// Signed by the custodian stored in the reserves account
+#[derive(Accounts)]
+pub struct AttestReserves<'info> {
+    /// The custodian attesting the reserves
+    pub custodian: Signer<'info>,
+
+    /// The USDonReserves account
+    /// # PDA Seeds
+    /// - USDON_RESERVES_SEED
+    #[account(
+        mut,
+        seeds = [USDON_RESERVES_SEED],
+        bump = usdon_reserves.bump,
+        has_one = custodian @ OndoError::InvalidUser,
+    )]
+    pub usdon_reserves: Account<'info, USDonReserves>,
+}
+
+impl<'info> AttestReserves<'info> {
+    /// Record the off-chain reserves as of now
+    /// # Arguments
+    /// * `offchain_reserves` - The off-chain reserves, in USDon base units
+    /// # Returns
+    /// * `Result<()>` - Ok if the reserves are recorded, Err otherwise
+    pub fn attest_reserves(&mut self, offchain_reserves: u64) -> Result<()> {
+        let attested_at = Clock::get()?.unix_timestamp;
+
+        self.usdon_reserves.offchain_reserves = offchain_reserves;
+        self.usdon_reserves.attested_at = attested_at;
+
+        emit!(ReservesAttested {
+            custodian: self.custodian.key(),
+            offchain_reserves,
+            attested_at,
+        });
+
+        Ok(())
+    }
+}
+
+/// Compare the reserves backing USDon with the USDon supply
+/// Pauses USDon minting when USDon is undercollateralized.
+/// Unpermissioned
+#[derive(Accounts)]
+pub struct ReconcileReserves<'info> {
+    /// The account triggering the reconciliation
+    pub cranker: Signer<'info>,
+
+    /// The USDonManagerState account holding the USDon minting pause flag
+    /// # PDA Seeds
+    /// - USDON_MANAGER_STATE_SEED
+    #[account(
+        mut,
+        seeds = [USDON_MANAGER_STATE_SEED],
+        bump = usdon_manager_state.bump,
+    )]
+    pub usdon_manager_state: Box<Account<'info, USDonManagerState>>,
+
+    /// The USDonReserves account
+    /// # PDA Seeds
+    /// - USDON_RESERVES_SEED
+    #[account(
+        mut,
+        seeds = [USDON_RESERVES_SEED],
+        bump = usdon_reserves.bump,
+    )]
+    pub usdon_reserves: Box<Account<'info, USDonReserves>>,
+
+    /// The USDC vault holding the on-chain reserves
+    #[account(
+        address = usdon_manager_state.usdc_vault,
+        token::mint = usdc_mint,
+        token::token_program = spl_token_program,
+    )]
+    pub usdc_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The USDC mint (SPL Token)
+    #[account(
+        mint::token_program = spl_token_program,
+    )]
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The USDon mint (Token-2022)
+    #[account(
+        address = usdon_manager_state.usdon_mint,
+        mint::token_program = token_program,
+    )]
+    pub usdon_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The token program (Token-2022)
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The legacy SPL Token program
+    pub spl_token_program: Program<'info, Token>,
+}
+
+impl<'info> ReconcileReserves<'info> {
+    /// Compute the collateralization of USDon from the USDC vault balance and the attested
+    /// off-chain reserves, pausing USDon minting if it is below the configured threshold.
+    /// Fails if no attestation was made or the last one is stale, pausing minting on a missing
+    /// attestation is left to the guardian. Minting is never resumed automatically.
+    /// # Returns
+    /// * `Result<()>` - Ok if the reconciliation completes, Err otherwise
+    pub fn reconcile_reserves(&mut self) -> Result<()> {
+        let current_timestamp = Clock::get()?.unix_timestamp;
+
+        // USDC reserved for queued redemption claims is owed, not backing
+        let onchain_reserves = normalize_decimals(
+            self.usdon_manager_state
+                .unreserved_usdc(self.usdc_vault.amount),
+            self.usdc_mint.decimals,
+            self.usdon_mint.decimals,
+            false,
+        )?;
+        let offchain_reserves = self
+            .usdon_reserves
+            .attested_offchain_reserves(current_timestamp)
+            .ok_or(OndoError::StaleReservesAttestation)?;
+
+        let backing = onchain_reserves
+            .checked_add(offchain_reserves)
+            .ok_or(OndoError::MathOverflow)?;
+        let supply = self.usdon_mint.supply;
+        let collateralization = collateralization_bps(backing, supply)?;
+
+        if collateralization < self.usdon_reserves.min_collateralization_bps {
+            self.usdon_manager_state.usdon_minting_paused = true;
+        }
+
+        self.usdon_reserves.last_collateralization_bps = collateralization;
+        self.usdon_reserves.last_reconciled_at = current_timestamp;
+
+        emit!(ReservesReconciled {
+            onchain_reserves,
+            offchain_reserves,
+            supply,
+            collateralization_bps: collateralization,
+            minting_paused: self.usdon_manager_state.usdon_minting_paused,
+            cranker: self.cranker.key(),
+        });
+
+        Ok(())
+    }
+}
+
+/// Rejects zero collateralization thresholds and attestation ages
+fn validate_reserves_config(
+    min_collateralization_bps: u64,
+    max_attestation_age: u64,
+) -> Result<()> {
+    require_gt!(
+        min_collateralization_bps,
+        0,
+        OndoError::InvalidReservesConfig
+    );
+    require_gt!(max_attestation_age, 0, OndoError::InvalidReservesConfig);
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::USER_NOTIONAL_LIMIT_SEED,
+    state::{NotionalUsage, UserNotionalLimit},
+};
+
+/// Create the account tracking a user's notional across all GM Tokens
+/// Unpermissioned, the payer covers the rent
+/// Required to trade while the per-user notional limit is enabled
+#[derive(Accounts)]
+pub struct InitializeUserNotionalLimit<'info> {
+    /// Pays for account creation
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The user the account is created for
+    /// CHECK: Only used to derive the `UserNotionalLimit` address
+    pub user: UncheckedAccount<'info>,
+
+    /// The `UserNotionalLimit` account being created
+    /// # PDA Seeds
+    /// - `USER_NOTIONAL_LIMIT_SEED`
+    /// - User's address
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + UserNotionalLimit::INIT_SPACE,
+        seeds = [USER_NOTIONAL_LIMIT_SEED, user.key().as_ref()],
+        bump,
+    )]
+    pub user_notional_limit: Account<'info, UserNotionalLimit>,
+
+    /// The system program
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializeUserNotionalLimit<'info> {
+    /// Create the account with no notional used
+    /// # Arguments
+    /// * `bump` - The bump of the `UserNotionalLimit` PDA
+    /// # Returns
+    /// * `Result<()>` - Ok if the account is created, Err otherwise
+    pub fn initialize_user_notional_limit(&mut self, bump: u8) -> Result<()> {
+        self.user_notional_limit.set_inner(UserNotionalLimit {
+            user: self.user.key(),
+            mint_usage: NotionalUsage::default(),
+            redeem_usage: NotionalUsage::default(),
+            bump,
+        });
+
+        Ok(())
+    }
+}
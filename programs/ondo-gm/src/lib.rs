@@ -10,9 +10,10 @@ pub mod security;
 mod state;
 mod utils;
 
+use errors::OndoError;
 use events::TradeExecuted;
 use instructions::*;
-use state::RoleType;
+use state::{OndoUserLimitsUpdate, OracleAdapter, RoleType, VaultShortfallMode};
 
 #[cfg(feature = "devnet")]
 declare_id!("sSV6QQi2UTvjmPx4UMLDFJas9CQE3VmBz64wPJHN1gm");
@@ -105,15 +106,408 @@ pub mod ondo_gm {
             .set_usdc_price_update_address(new_price_update_address)
     }
 
+    /// Set the USDC price feed ID and the program that must own the USDC price update account
+    ///
+    /// Zero values fall back to `USDC_PYTH_ID` and the Pyth receiver program.
+    /// Signer must have the ADMIN_ROLE_USDON_MANAGER role
+    pub fn set_usdc_oracle_feed(
+        ctx: Context<USDonManagerAdmin>,
+        usdc_feed_id: [u8; 32],
+        usdc_price_update_owner: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts
+            .set_usdc_oracle_feed(usdc_feed_id, usdc_price_update_owner)
+    }
+
+    /// Select the kind of oracle account the USDC price is read from
+    ///
+    /// Pyth pull updates, Switchboard On-Demand feeds or admin-pushed prices.
+    /// Signer must have the ADMIN_ROLE_USDON_MANAGER role
+    pub fn set_usdc_oracle_adapter(
+        ctx: Context<USDonManagerAdmin>,
+        usdc_oracle_adapter: OracleAdapter,
+    ) -> Result<()> {
+        ctx.accounts.set_usdc_oracle_adapter(usdc_oracle_adapter)
+    }
+
+    /// Configure the secondary USDC price oracle
+    ///
+    /// Used when the primary price is stale or too uncertain, and cross-checked
+    /// against the primary when both are usable.
+    /// Signer must have the ADMIN_ROLE_USDON_MANAGER role
+    pub fn set_usdc_secondary_oracle(
+        ctx: Context<USDonManagerAdmin>,
+        price_update: Pubkey,
+        oracle_adapter: OracleAdapter,
+        feed_id: [u8; 32],
+        price_update_owner: Pubkey,
+        max_divergence_bps: u64,
+    ) -> Result<()> {
+        ctx.accounts.set_usdc_secondary_oracle(
+            price_update,
+            oracle_adapter,
+            feed_id,
+            price_update_owner,
+            max_divergence_bps,
+        )
+    }
+
+    /// Set the accepted USDC price range
+    ///
+    /// Swaps are rejected while the USDC price is below the floor or above the optional ceiling.
+    /// Signer must have the ADMIN_ROLE_USDON_MANAGER role
+    pub fn set_usdc_price_bounds(
+        ctx: Context<USDonManagerAdmin>,
+        usdc_min_price: u64,
+        usdc_max_price: u64,
+    ) -> Result<()> {
+        ctx.accounts
+            .set_usdc_price_bounds(usdc_min_price, usdc_max_price)
+    }
+
+    /// Set the maximum accepted oracle confidence interval, in basis points of the price
+    ///
+    /// Signer must have the ADMIN_ROLE_USDON_MANAGER role
+    pub fn set_usdc_confidence_threshold(
+        ctx: Context<USDonManagerAdmin>,
+        confidence_threshold_bps: u64,
+    ) -> Result<()> {
+        ctx.accounts
+            .set_usdc_confidence_threshold(confidence_threshold_bps)
+    }
+
+    /// Enable or disable converting USDC at the oracle price instead of 1:1
+    ///
+    /// Conversions are rounded in the protocol's favour and rejected while the
+    /// price is more than `conversion_band_bps` away from the peg.
+    /// Signer must have the ADMIN_ROLE_USDON_MANAGER role
+    pub fn set_usdc_oracle_conversion(
+        ctx: Context<USDonManagerAdmin>,
+        is_enabled: bool,
+        conversion_band_bps: u64,
+    ) -> Result<()> {
+        ctx.accounts
+            .set_usdc_oracle_conversion(is_enabled, conversion_band_bps)
+    }
+
+    /// Select how redemptions are settled when the USDC vault cannot cover the payout
+    ///
+    /// Either reject the redemption, or pay out what the vault holds and settle the remainder
+    /// in USDon or queue it in the redemption queue.
+    /// Signer must have the ADMIN_ROLE_USDON_MANAGER role
+    pub fn set_usdc_vault_shortfall_mode(
+        ctx: Context<USDonManagerAdmin>,
+        usdc_vault_shortfall_mode: VaultShortfallMode,
+    ) -> Result<()> {
+        ctx.accounts
+            .set_usdc_vault_shortfall_mode(usdc_vault_shortfall_mode)
+    }
+
+    /// Create the queue holding USDC owed to redemptions the USDC vault could not cover
+    /// Signer must have the ADMIN_ROLE_USDON_MANAGER role
+    pub fn initialize_redemption_queue(ctx: Context<InitializeRedemptionQueue>) -> Result<()> {
+        ctx.accounts
+            .initialize_redemption_queue(ctx.bumps.redemption_queue)
+    }
+
+    /// Pay queued redemption claims out of the USDC vault, oldest first
+    ///
+    /// The USDC token accounts of the claims are passed via remaining_accounts, in queue order.
+    /// Unpermissioned
+    pub fn process_redemption_queue<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ProcessRedemptionQueue<'info>>,
+    ) -> Result<()> {
+        ctx.accounts
+            .process_redemption_queue(ctx.remaining_accounts)
+    }
+
+    /// Re-route a queued redemption claim to another USDC token account of its user
+    ///
+    /// Unblocks the queue when the claim's token account can no longer receive USDC.
+    /// Signer must have the GUARDIAN_USDON role
+    pub fn reroute_redemption_claim(
+        ctx: Context<RerouteRedemptionClaim>,
+        claim_id: u64,
+    ) -> Result<()> {
+        ctx.accounts.reroute_redemption_claim(claim_id)
+    }
+
+    /// Move the claim at the front of the redemption queue to the back
+    ///
+    /// Unblocks the claims behind a claim that cannot be paid.
+    /// Signer must have the GUARDIAN_USDON role
+    pub fn defer_redemption_claim(ctx: Context<DeferRedemptionClaim>) -> Result<()> {
+        ctx.accounts.defer_redemption_claim()
+    }
+
+    /// Set the fee charged on PSM deposits and redemptions
+    /// Signer must have the ADMIN_ROLE_USDON_MANAGER role
+    pub fn set_psm_fee(ctx: Context<USDonManagerAdmin>, psm_fee_bps: u64) -> Result<()> {
+        ctx.accounts.set_psm_fee(psm_fee_bps)
+    }
+
+    /// Set the PSM rate limit, applied separately to deposits and redemptions
+    /// Signer must have the ADMIN_ROLE_USDON_MANAGER role
+    pub fn set_psm_rate_limit(
+        ctx: Context<USDonManagerAdmin>,
+        rate_limit: u64,
+        limit_window: u64,
+    ) -> Result<()> {
+        ctx.accounts.set_psm_rate_limit(rate_limit, limit_window)
+    }
+
+    /// Pause or unpause PSM deposits and redemptions
+    /// Signer must have the ADMIN_ROLE_USDON_MANAGER role
+    pub fn set_psm_paused(
+        ctx: Context<USDonManagerAdmin>,
+        deposit_paused: bool,
+        redemption_paused: bool,
+    ) -> Result<()> {
+        ctx.accounts
+            .set_psm_paused(deposit_paused, redemption_paused)
+    }
+
+    /// Deposit USDC into the peg stability module (PSM) for USDon
+    ///
+    /// USDC is converted at the same oracle-checked rate as GM Token trades, minus the PSM fee.
+    /// Unpermissioned - the user must be whitelisted
+    pub fn deposit_usdc_for_usdon(
+        ctx: Context<PSMContext>,
+        amount: u64,
+        min_amount_out: u64,
+    ) -> Result<()> {
+        ctx.accounts
+            .deposit_usdc_for_usdon(amount, min_amount_out, ctx.bumps.mint_authority)
+    }
+
+    /// Redeem USDon from the peg stability module (PSM) for USDC
+    ///
+    /// USDon is converted at the same oracle-checked rate as GM Token trades, minus the PSM fee.
+    /// Unpermissioned - the user must be whitelisted
+    pub fn redeem_usdon_for_usdc(
+        ctx: Context<PSMContext>,
+        amount: u64,
+        min_amount_out: u64,
+    ) -> Result<()> {
+        ctx.accounts.redeem_usdon_for_usdc(amount, min_amount_out)
+    }
+
+    /// Pause or resume USDon minting
+    ///
+    /// Used to resume minting once reserves are restored after `reconcile_reserves` paused it.
+    /// Signer must have the ADMIN_ROLE_USDON_MANAGER role
+    pub fn set_usdon_minting_paused(ctx: Context<USDonManagerAdmin>, paused: bool) -> Result<()> {
+        ctx.accounts.set_usdon_minting_paused(paused)
+    }
+
+    /// Create the account tracking the reserves backing USDon
+    /// Signer must have the ADMIN_ROLE_USDON_MANAGER role
+    pub fn initialize_usdon_reserves(
+        ctx: Context<InitializeUSDonReserves>,
+        custodian: Pubkey,
+        min_collateralization_bps: u64,
+        max_attestation_age: u64,
+    ) -> Result<()> {
+        ctx.accounts.initialize_usdon_reserves(
+            custodian,
+            min_collateralization_bps,
+            max_attestation_age,
+            ctx.bumps.usdon_reserves,
+        )
+    }
+
+    /// Set the reserves custodian and reconciliation thresholds
+    /// Signer must have the ADMIN_ROLE_USDON_MANAGER role
+    pub fn set_usdon_reserves_config(
+        ctx: Context<USDonReservesAdmin>,
+        custodian: Pubkey,
+        min_collateralization_bps: u64,
+        max_attestation_age: u64,
+    ) -> Result<()> {
+        ctx.accounts.set_usdon_reserves_config(
+            custodian,
+            min_collateralization_bps,
+            max_attestation_age,
+        )
+    }
+
+    /// Record the off-chain reserves backing USDon
+    /// Signer must be the custodian stored in the reserves account
+    pub fn attest_reserves(ctx: Context<AttestReserves>, offchain_reserves: u64) -> Result<()> {
+        ctx.accounts.attest_reserves(offchain_reserves)
+    }
+
+    /// Compare the USDC vault balance plus attested off-chain reserves with the USDon supply
+    ///
+    /// Pauses USDon minting when the collateralization is below the configured threshold.
+    /// Fails while the off-chain reserves attestation is missing or stale.
+    /// Unpermissioned
+    pub fn reconcile_reserves(ctx: Context<ReconcileReserves>) -> Result<()> {
+        ctx.accounts.reconcile_reserves()
+    }
+
+    /// Create an admin-pushed price account for a mint
+    ///
+    /// Selected with the `Pushed` oracle adapter where no external oracle is available.
+    /// The adapter does not exist on mainnet builds, so the account is never read there.
+    /// Signer must have the ADMIN_ROLE_USDON_MANAGER role
+    pub fn initialize_pushed_price(ctx: Context<InitializePushedPrice>) -> Result<()> {
+        ctx.accounts.initialize_pushed_price(ctx.bumps.pushed_price)
+    }
+
+    /// Push a new price, scaled by USDC_PRICE_DECIMALS, to an admin-pushed price account
+    /// Signer must have the ADMIN_ROLE_USDON_MANAGER role
+    pub fn push_price(ctx: Context<PushPrice>, price: u64, conf: u64) -> Result<()> {
+        ctx.accounts.push_price(price, conf)
+    }
+
+    /// Grow the USDon manager state account to the current layout
+    ///
+    /// Must be run once after an upgrade that adds fields to the account.
+    /// Signer must have the ADMIN_ROLE_USDON_MANAGER role
+    pub fn migrate_usdon_manager_state(ctx: Context<MigrateUSDonManagerState>) -> Result<()> {
+        ctx.accounts.migrate_usdon_manager_state()
+    }
+
     /// Retrieve (withdraw) tokens from a vault controlled by the USDon manager
     ///
     /// Allows admins to withdraw any tokens (USDC, USDon, etc.) from vaults
-    /// owned by the usdon_manager_state PDA.
+    /// owned by the usdon_manager_state PDA to an allowlisted destination,
+    /// within the withdrawal limit of the mint. Amounts above the timelock
+    /// threshold of the mint must go through `request_retrieval`.
     /// Signer must have the ADMIN_ROLE_USDON_MANAGER role
     pub fn retrieve_tokens(ctx: Context<RetrieveTokens>, amount: u64) -> Result<()> {
         ctx.accounts.retrieve_tokens(amount)
     }
 
+    /// Allowlist a token account as a destination for retrievals
+    /// Signer must have the GUARDIAN_USDON role
+    pub fn add_retrieval_destination(
+        ctx: Context<AddRetrievalDestination>,
+        destination: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts
+            .add_retrieval_destination(destination, ctx.bumps.retrieval_destination)
+    }
+
+    /// Remove a token account from the retrieval destination allowlist
+    /// Signer must have the GUARDIAN_USDON role
+    pub fn remove_retrieval_destination(ctx: Context<RemoveRetrievalDestination>) -> Result<()> {
+        ctx.accounts.remove_retrieval_destination()
+    }
+
+    /// Set the rolling withdrawal limit and timelock policy for retrieving a mint
+    /// Signer must have the GUARDIAN_USDON role
+    pub fn set_retrieval_limit(
+        ctx: Context<SetRetrievalLimit>,
+        rate_limit: u64,
+        limit_window: u64,
+        timelock_threshold: u64,
+        timelock_delay: u64,
+    ) -> Result<()> {
+        ctx.accounts.set_retrieval_limit(
+            rate_limit,
+            limit_window,
+            timelock_threshold,
+            timelock_delay,
+            ctx.bumps.retrieval_limit,
+        )
+    }
+
+    /// Request a retrieval, executable with `execute_retrieval` once the timelock delay of the mint has passed
+    /// Signer must have the ADMIN_ROLE_USDON_MANAGER role
+    pub fn request_retrieval(
+        ctx: Context<RequestRetrieval>,
+        request_id: u64,
+        amount: u64,
+    ) -> Result<()> {
+        ctx.accounts
+            .request_retrieval(request_id, amount, ctx.bumps.pending_retrieval)
+    }
+
+    /// Execute a requested retrieval once its timelock delay has passed
+    /// Signer must have the ADMIN_ROLE_USDON_MANAGER role
+    pub fn execute_retrieval(ctx: Context<ExecuteRetrieval>) -> Result<()> {
+        ctx.accounts.execute_retrieval()
+    }
+
+    /// Cancel a requested retrieval
+    /// Signer must have the GUARDIAN_USDON role
+    pub fn cancel_retrieval(ctx: Context<CancelRetrieval>) -> Result<()> {
+        ctx.accounts.cancel_retrieval()
+    }
+
+    /// Register a settlement asset that mints and redemptions can settle in
+    ///
+    /// The asset is exchanged 1:1 with USDon through the USDon manager's vault for it.
+    /// `priced_at_par` exchanges the asset at par without an oracle check, the oracle
+    /// configuration must then be left unset. USDC cannot be registered, nor Token-2022 mints
+    /// with extensions such as transfer fees that change what the vault receives.
+    /// Signer must have the ADMIN_ROLE_USDON_MANAGER role
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_settlement_asset(
+        ctx: Context<AddSettlementAsset>,
+        priced_at_par: bool,
+        price_update: Pubkey,
+        oracle_adapter: OracleAdapter,
+        price_update_owner: Pubkey,
+        feed_id: [u8; 32],
+        oracle_max_age: u64,
+        confidence_threshold_bps: u64,
+        min_price: u64,
+        max_price: u64,
+    ) -> Result<()> {
+        ctx.accounts.add_settlement_asset(
+            priced_at_par,
+            price_update,
+            oracle_adapter,
+            price_update_owner,
+            feed_id,
+            oracle_max_age,
+            confidence_threshold_bps,
+            min_price,
+            max_price,
+            ctx.bumps.settlement_asset,
+        )
+    }
+
+    /// Set the oracle configuration and depeg bounds of a settlement asset
+    /// Signer must have the ADMIN_ROLE_USDON_MANAGER role
+    #[allow(clippy::too_many_arguments)]
+    pub fn set_settlement_asset_oracle(
+        ctx: Context<SettlementAssetAdmin>,
+        priced_at_par: bool,
+        price_update: Pubkey,
+        oracle_adapter: OracleAdapter,
+        price_update_owner: Pubkey,
+        feed_id: [u8; 32],
+        oracle_max_age: u64,
+        confidence_threshold_bps: u64,
+        min_price: u64,
+        max_price: u64,
+    ) -> Result<()> {
+        ctx.accounts.set_settlement_asset_oracle(
+            priced_at_par,
+            price_update,
+            oracle_adapter,
+            price_update_owner,
+            feed_id,
+            oracle_max_age,
+            confidence_threshold_bps,
+            min_price,
+            max_price,
+        )
+    }
+
+    /// Enable or disable settlement in a registered asset
+    /// Signer must have the ADMIN_ROLE_USDON_MANAGER role
+    pub fn set_settlement_asset_enabled(
+        ctx: Context<SettlementAssetAdmin>,
+        enabled: bool,
+    ) -> Result<()> {
+        ctx.accounts.set_settlement_asset_enabled(enabled)
+    }
+
     /// Initialize a user account with optional rate limits
     pub fn initialize_user(
         ctx: Context<InitializeUser>,
@@ -145,22 +539,74 @@ pub mod ondo_gm {
     }
 
     /// Update token-level rate limits
+    ///
+    /// The mint and redeem limits are set independently; redeem limits
+    /// that were never set fall back to the mint limits.
     /// Signer must have the ADMIN_ROLE_GMTOKEN_MANAGER role
+    #[allow(clippy::too_many_arguments)]
     pub fn set_token_limit(
         ctx: Context<SetTokenLimit>,
         rate_limit: Option<u64>,
         limit_window: Option<u64>,
         default_user_rate_limit: Option<u64>,
         default_user_limit_window: Option<u64>,
+        redeem_rate_limit: Option<u64>,
+        redeem_limit_window: Option<u64>,
+        default_user_redeem_rate_limit: Option<u64>,
+        default_user_redeem_limit_window: Option<u64>,
     ) -> Result<()> {
         ctx.accounts.set_token_limit(
             rate_limit,
             limit_window,
             default_user_rate_limit,
             default_user_limit_window,
+            redeem_rate_limit,
+            redeem_limit_window,
+            default_user_redeem_rate_limit,
+            default_user_redeem_limit_window,
         )
     }
 
+    /// Enable or disable net-flow rate limiting for a token
+    ///
+    /// In net-flow mode mints and redemptions offset each other, and the
+    /// token's mint rate limit caps the absolute net flow per window.
+    /// Signer must have the ADMIN_ROLE_GMTOKEN_MANAGER role
+    pub fn set_token_net_flow_mode(ctx: Context<SetTokenLimit>, enabled: bool) -> Result<()> {
+        ctx.accounts.set_token_net_flow_mode(enabled)
+    }
+
+    /// Set or clear an additional rate limit tier for a token
+    ///
+    /// Tiers are evaluated alongside the token's rate limit, e.g. an hourly
+    /// burst cap next to a daily cap. Mints and redemptions are capped separately.
+    /// A zero limit window clears the tier.
+    /// Signer must have the ADMIN_ROLE_GMTOKEN_MANAGER role
+    pub fn set_token_limit_tier(
+        ctx: Context<SetTokenLimit>,
+        index: u8,
+        rate_limit: u64,
+        limit_window: u64,
+    ) -> Result<()> {
+        ctx.accounts
+            .set_token_limit_tier(index, rate_limit, limit_window)
+    }
+
+    /// Grow a TokenLimit account created with an older layout
+    /// Signer must have the ADMIN_ROLE_GMTOKEN_MANAGER role
+    pub fn migrate_token_limit(ctx: Context<MigrateTokenLimit>) -> Result<()> {
+        ctx.accounts.migrate_token_limit()
+    }
+
+    /// Grow an OndoUser account created with an older layout
+    ///
+    /// Existing accounts cannot trade until migrated, so every account must be
+    /// swept right after an upgrade that grows OndoUser (see "Upgrading" in the README).
+    /// Unpermissioned, the signer pays for the extra rent
+    pub fn migrate_ondo_user(ctx: Context<MigrateOndoUser>) -> Result<()> {
+        ctx.accounts.migrate_ondo_user()
+    }
+
     /// Initialize sanity check parameters for a token
     ///
     /// Sets up price deviation and time delay checks to ensure safe trading.
@@ -183,13 +629,13 @@ pub mod ondo_gm {
     ///
     /// Requires a valid attestation with price, amount, and expiration.
     pub fn mint_with_usdon(
-        ctx: Context<USDonSwapContext>,
+        ctx: Context<SettlementSwapContext>,
         attestation_id: [u8; 16],
         price: u64,
         amount: u64,
         expiration: i64,
     ) -> Result<()> {
-        mint_with_attestation(
+        let conversion_rate = mint_with_attestation(
             &mut ctx.accounts.into_token_manager(),
             attestation_id,
             price,
@@ -203,6 +649,7 @@ pub mod ondo_gm {
 
         emit_cpi!(TradeExecuted {
             execution_id: ctx.accounts.gmtoken_manager_state.next_execution_id()?,
+            conversion_rate,
         });
 
         Ok(())
@@ -212,13 +659,19 @@ pub mod ondo_gm {
     ///
     /// Requires a valid attestation with price, amount, and expiration.
     pub fn mint_with_usdc(
-        ctx: Context<USDCSwapContext>,
+        ctx: Context<SettlementSwapContext>,
         attestation_id: [u8; 16],
         price: u64,
         amount: u64,
         expiration: i64,
     ) -> Result<()> {
-        mint_with_attestation(
+        // The settlement accounts must form the USDC leg
+        require!(
+            ctx.accounts.settlement_asset.is_none(),
+            OndoError::InvalidInputMint
+        );
+
+        let conversion_rate = mint_with_attestation(
             &mut ctx.accounts.into_token_manager(),
             attestation_id,
             price,
@@ -232,6 +685,7 @@ pub mod ondo_gm {
 
         emit_cpi!(TradeExecuted {
             execution_id: ctx.accounts.gmtoken_manager_state.next_execution_id()?,
+            conversion_rate,
         });
 
         Ok(())
@@ -241,13 +695,13 @@ pub mod ondo_gm {
     ///
     /// Requires a valid attestation with price, amount, and expiration.
     pub fn redeem_for_usdon(
-        ctx: Context<USDonSwapContext>,
+        ctx: Context<SettlementSwapContext>,
         attestation_id: [u8; 16],
         price: u64,
         amount: u64,
         expiration: i64,
     ) -> Result<()> {
-        redeem_with_attestation(
+        let conversion_rate = redeem_with_attestation(
             &mut ctx.accounts.into_token_manager(),
             attestation_id,
             price,
@@ -261,6 +715,73 @@ pub mod ondo_gm {
 
         emit_cpi!(TradeExecuted {
             execution_id: ctx.accounts.gmtoken_manager_state.next_execution_id()?,
+            conversion_rate,
+        });
+
+        Ok(())
+    }
+
+    /// Mint GM tokens by paying with USDon or a registered settlement asset
+    ///
+    /// Requires a valid attestation with price, amount, and expiration.
+    pub fn mint_with_settlement_asset(
+        ctx: Context<SettlementSwapContext>,
+        attestation_id: [u8; 16],
+        price: u64,
+        amount: u64,
+        expiration: i64,
+    ) -> Result<()> {
+        // Without a registered settlement asset the trade settles in USDon
+        let is_usdon = ctx.accounts.settlement_asset.is_none();
+
+        let conversion_rate = mint_with_attestation(
+            &mut ctx.accounts.into_token_manager(),
+            attestation_id,
+            price,
+            amount,
+            expiration,
+            is_usdon,
+            ctx.bumps.ondo_user,
+            ctx.bumps.attestation_id_account,
+            ctx.bumps.mint_authority,
+        )?;
+
+        emit_cpi!(TradeExecuted {
+            execution_id: ctx.accounts.gmtoken_manager_state.next_execution_id()?,
+            conversion_rate,
+        });
+
+        Ok(())
+    }
+
+    /// Redeem GM tokens for USDon or a registered settlement asset
+    ///
+    /// Requires a valid attestation with price, amount, and expiration.
+    pub fn redeem_for_settlement_asset(
+        ctx: Context<SettlementSwapContext>,
+        attestation_id: [u8; 16],
+        price: u64,
+        amount: u64,
+        expiration: i64,
+    ) -> Result<()> {
+        // Without a registered settlement asset the trade settles in USDon
+        let is_usdon = ctx.accounts.settlement_asset.is_none();
+
+        let conversion_rate = redeem_with_attestation(
+            &mut ctx.accounts.into_token_manager(),
+            attestation_id,
+            price,
+            amount,
+            expiration,
+            is_usdon,
+            ctx.bumps.ondo_user,
+            ctx.bumps.attestation_id_account,
+            ctx.bumps.mint_authority,
+        )?;
+
+        emit_cpi!(TradeExecuted {
+            execution_id: ctx.accounts.gmtoken_manager_state.next_execution_id()?,
+            conversion_rate,
         });
 
         Ok(())
@@ -270,13 +791,19 @@ pub mod ondo_gm {
     ///
     /// Requires a valid attestation with price, amount, and expiration.
     pub fn redeem_for_usdc(
-        ctx: Context<USDCSwapContext>,
+        ctx: Context<SettlementSwapContext>,
         attestation_id: [u8; 16],
         price: u64,
         amount: u64,
         expiration: i64,
     ) -> Result<()> {
-        redeem_with_attestation(
+        // The settlement accounts must form the USDC leg
+        require!(
+            ctx.accounts.settlement_asset.is_none(),
+            OndoError::InvalidInputMint
+        );
+
+        let conversion_rate = redeem_with_attestation(
             &mut ctx.accounts.into_token_manager(),
             attestation_id,
             price,
@@ -290,6 +817,219 @@ pub mod ondo_gm {
 
         emit_cpi!(TradeExecuted {
             execution_id: ctx.accounts.gmtoken_manager_state.next_execution_id()?,
+            conversion_rate,
+        });
+
+        Ok(())
+    }
+
+    /// Queue a mint or redemption outside market hours
+    ///
+    /// Escrows USDon or USDC for mints, or GM tokens for redemptions, until the order
+    /// is settled by an operator or cancelled by the user.
+    /// Unpermissioned - the user must be whitelisted
+    pub fn create_order(
+        ctx: Context<CreateOrder>,
+        order_id: u64,
+        is_buy: bool,
+        is_usdon: bool,
+        amount: u64,
+    ) -> Result<()> {
+        ctx.accounts
+            .create_order(order_id, is_buy, is_usdon, amount, ctx.bumps.order)
+    }
+
+    /// Cancel a queued order and refund the escrow
+    /// Signer must be the user who queued the order
+    pub fn cancel_order(ctx: Context<CancelOrder>) -> Result<()> {
+        ctx.accounts.cancel_order()
+    }
+
+    /// Settle a queued order with a fresh attestation
+    ///
+    /// Requires a valid attestation with price, amount, and expiration for the order's user.
+    /// Signer must have the ORDER_OPERATOR_ROLE role
+    pub fn settle_order(
+        ctx: Context<SettleOrder>,
+        attestation_id: [u8; 16],
+        price: u64,
+        amount: u64,
+        expiration: i64,
+    ) -> Result<()> {
+        let conversion_rate =
+            ctx.accounts
+                .settle_order(attestation_id, price, amount, expiration, &ctx.bumps)?;
+
+        emit_cpi!(TradeExecuted {
+            execution_id: ctx.accounts.gmtoken_manager_state.next_execution_id()?,
+            conversion_rate,
+        });
+
+        Ok(())
+    }
+
+    /// Place a limit order to mint or redeem GM tokens
+    ///
+    /// Escrows USDon or USDC for mints, or GM tokens for redemptions. The order can only be
+    /// filled at or better than `limit_price` and before `expires_at`.
+    /// Unpermissioned - the user must be whitelisted
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_limit_order(
+        ctx: Context<CreateLimitOrder>,
+        order_id: u64,
+        is_buy: bool,
+        is_usdon: bool,
+        amount: u64,
+        limit_price: u64,
+        expires_at: i64,
+    ) -> Result<()> {
+        ctx.accounts.create_limit_order(
+            order_id,
+            is_buy,
+            is_usdon,
+            amount,
+            limit_price,
+            expires_at,
+            ctx.bumps.order,
+        )
+    }
+
+    /// Cancel a limit order and refund the remaining escrow
+    /// Signer must be the user who placed the order, or anyone once the order has expired
+    pub fn cancel_limit_order(ctx: Context<CancelLimitOrder>) -> Result<()> {
+        ctx.accounts.cancel_limit_order()
+    }
+
+    /// Fill a limit order in full or in part
+    ///
+    /// Requires a valid attestation for the order's user priced at or better than the limit.
+    /// Signer must have the ORDER_OPERATOR_ROLE role
+    pub fn fill_limit_order(
+        ctx: Context<FillLimitOrder>,
+        attestation_id: [u8; 16],
+        price: u64,
+        amount: u64,
+        expiration: i64,
+    ) -> Result<()> {
+        let conversion_rate =
+            ctx.accounts
+                .fill_limit_order(attestation_id, price, amount, expiration, &ctx.bumps)?;
+
+        emit_cpi!(TradeExecuted {
+            execution_id: ctx.accounts.gmtoken_manager_state.next_execution_id()?,
+            conversion_rate,
+        });
+
+        Ok(())
+    }
+
+    /// Request a GM token mint settled at the final execution price
+    ///
+    /// Locks `amount` of USDon or USDC until an operator settles the request at a price no higher than `max_price`.
+    /// The GM token amount requested is what the deposit buys at `max_price`, valued at par.
+    /// Unpermissioned - the user must be whitelisted
+    pub fn create_mint_request(
+        ctx: Context<CreateMintRequest>,
+        request_id: u64,
+        is_usdon: bool,
+        amount: u64,
+        max_price: u64,
+    ) -> Result<()> {
+        ctx.accounts
+            .create_mint_request(request_id, is_usdon, amount, max_price, ctx.bumps.request)
+    }
+
+    /// Settle a mint request at the final execution price and refund the difference
+    ///
+    /// Requires a valid attestation for the request's user with price, amount, and expiration.
+    /// The amount must be the requested amount, the deposit it does not cost at the price is refunded.
+    /// Signer must have the ORDER_OPERATOR_ROLE role
+    pub fn settle_mint_request(
+        ctx: Context<SettleMintRequest>,
+        attestation_id: [u8; 16],
+        price: u64,
+        amount: u64,
+        expiration: i64,
+    ) -> Result<()> {
+        let conversion_rate = ctx.accounts.settle_mint_request(
+            attestation_id,
+            price,
+            amount,
+            expiration,
+            &ctx.bumps,
+        )?;
+
+        emit_cpi!(TradeExecuted {
+            execution_id: ctx.accounts.gmtoken_manager_state.next_execution_id()?,
+            conversion_rate,
+        });
+
+        Ok(())
+    }
+
+    /// Refund a mint request that timed out before being settled
+    /// Unpermissioned
+    pub fn refund_mint_request(ctx: Context<RefundMintRequest>) -> Result<()> {
+        ctx.accounts.refund_mint_request()
+    }
+
+    /// Create a recurring GM token purchase schedule paid for in USDon
+    ///
+    /// Escrows a USDon allowance that is spent by at most `amount_per_execution` every `interval` seconds.
+    /// Unpermissioned - the user must be whitelisted
+    pub fn create_recurring_order(
+        ctx: Context<CreateRecurringOrder>,
+        order_id: u64,
+        amount_per_execution: u64,
+        interval: i64,
+        first_execution_at: i64,
+        deposit_amount: u64,
+    ) -> Result<()> {
+        ctx.accounts.create_recurring_order(
+            order_id,
+            amount_per_execution,
+            interval,
+            first_execution_at,
+            deposit_amount,
+            ctx.bumps.order,
+        )
+    }
+
+    /// Top up the USDon allowance of a recurring purchase schedule
+    /// Signer must be the user who created the schedule
+    pub fn fund_recurring_order(ctx: Context<FundRecurringOrder>, amount: u64) -> Result<()> {
+        ctx.accounts.fund_recurring_order(amount)
+    }
+
+    /// Cancel a recurring purchase schedule and refund the remaining allowance
+    /// Signer must be the user who created the schedule
+    pub fn cancel_recurring_order(ctx: Context<CancelRecurringOrder>) -> Result<()> {
+        ctx.accounts.cancel_recurring_order()
+    }
+
+    /// Execute the next installment of a recurring purchase schedule
+    ///
+    /// Requires a valid attestation for the schedule's user with price, amount, and expiration.
+    /// The amount must spend the per-installment amount, or the remaining allowance if lower.
+    /// Unpermissioned
+    pub fn execute_recurring_order(
+        ctx: Context<ExecuteRecurringOrder>,
+        attestation_id: [u8; 16],
+        price: u64,
+        amount: u64,
+        expiration: i64,
+    ) -> Result<()> {
+        let conversion_rate = ctx.accounts.execute_recurring_order(
+            attestation_id,
+            price,
+            amount,
+            expiration,
+            &ctx.bumps,
+        )?;
+
+        emit_cpi!(TradeExecuted {
+            execution_id: ctx.accounts.gmtoken_manager_state.next_execution_id()?,
+            conversion_rate,
         });
 
         Ok(())
@@ -336,6 +1076,8 @@ pub mod ondo_gm {
     }
 
     /// Mint USDon tokens (admin function)
+    ///
+    /// Limited by the signer's MinterAllowance and the USDon supply cap.
     /// Signer must have the MINTER_ROLE_USDON role
     pub fn mint_usdon(ctx: Context<USDonMinter>, amount: u64) -> Result<()> {
         ctx.accounts.mint_usdon(amount, ctx.bumps.mint_authority)
@@ -348,6 +1090,31 @@ pub mod ondo_gm {
             .burn_usdon(amount, ctx.bumps.permanent_delegate)
     }
 
+    /// Set the rolling USDon mint allowance of a minter
+    /// Signer must have the GUARDIAN_USDON role
+    pub fn set_minter_allowance(
+        ctx: Context<SetMinterAllowance>,
+        minter: Pubkey,
+        rate_limit: u64,
+        limit_window: u64,
+    ) -> Result<()> {
+        ctx.accounts.set_minter_allowance(
+            minter,
+            rate_limit,
+            limit_window,
+            ctx.bumps.minter_allowance,
+        )
+    }
+
+    /// Set the maximum USDon supply, 0 removes the ceiling
+    /// Signer must have the GUARDIAN_USDON role
+    pub fn set_usdon_supply_cap(
+        ctx: Context<SetUSDonSupplyCap>,
+        usdon_supply_cap: u64,
+    ) -> Result<()> {
+        ctx.accounts.set_usdon_supply_cap(usdon_supply_cap)
+    }
+
     /// Mint GM tokens directly (admin function)
     /// Signer must have the MINTER_ROLE_GMTOKEN role
     pub fn mint_gm(ctx: Context<GMTokenMinter>, amount: u64) -> Result<()> {
@@ -517,8 +1284,104 @@ pub mod ondo_gm {
         ctx: Context<GMTokenManagerAdminSetUserLimits>,
         rate_limit: u64,
         limit_window: u64,
+        redeem_rate_limit: Option<u64>,
+        redeem_limit_window: Option<u64>,
+    ) -> Result<()> {
+        ctx.accounts.set_ondo_user_limits(
+            rate_limit,
+            limit_window,
+            redeem_rate_limit,
+            redeem_limit_window,
+        )
+    }
+
+    /// Set the limits of, or reset the capacity used by, many OndoUser accounts at once
+    ///
+    /// OndoUser accounts are passed via remaining_accounts, constraints:
+    /// 1. Accounts must be marked writable
+    /// 2. No other accounts should present in `remaining_accounts`
+    /// 3. Each account must be an OndoUser PDA of this program
+    /// Signer must have the ADMIN_ROLE_GMTOKEN_MANAGER role
+    pub fn batch_set_ondo_user_limits<'info>(
+        ctx: Context<'_, '_, 'info, 'info, GMTokenManagerAdminBatchSetUserLimits<'info>>,
+        update: OndoUserLimitsUpdate,
+    ) -> Result<()> {
+        ctx.accounts
+            .batch_set_ondo_user_limits(update, ctx.remaining_accounts)
+    }
+
+    /// Set the shared rate limit of a limit group for a GM Token
+    ///
+    /// All OndoUser accounts attached to the group draw from one shared
+    /// capacity, on top of their own limits. Creates the group if needed.
+    /// Signer must have the ADMIN_ROLE_GMTOKEN_MANAGER role
+    pub fn set_limit_group(
+        ctx: Context<SetLimitGroup>,
+        group_id: u64,
+        rate_limit: u64,
+        limit_window: u64,
+    ) -> Result<()> {
+        ctx.accounts
+            .set_limit_group(group_id, rate_limit, limit_window, ctx.bumps.limit_group)
+    }
+
+    /// Attach an OndoUser to a limit group, or detach it when no group is provided
+    /// Signer must have the ADMIN_ROLE_GMTOKEN_MANAGER role
+    pub fn set_ondo_user_limit_group(ctx: Context<SetOndoUserLimitGroup>) -> Result<()> {
+        ctx.accounts.set_ondo_user_limit_group()
+    }
+
+    /// Set the limits of a limit profile
+    ///
+    /// OndoUser accounts linked to the profile use its limits instead of their
+    /// own, so editing the profile updates all of them. Creates the profile if needed.
+    /// Signer must have the ADMIN_ROLE_GMTOKEN_MANAGER role
+    pub fn set_limit_profile(
+        ctx: Context<SetLimitProfile>,
+        profile_id: u64,
+        rate_limit: u64,
+        limit_window: u64,
+        redeem_rate_limit: Option<u64>,
+        redeem_limit_window: Option<u64>,
+    ) -> Result<()> {
+        ctx.accounts.set_limit_profile(
+            profile_id,
+            rate_limit,
+            limit_window,
+            redeem_rate_limit,
+            redeem_limit_window,
+            ctx.bumps.limit_profile,
+        )
+    }
+
+    /// Link an OndoUser to a limit profile, or unlink it when no profile is provided
+    /// Signer must have the ADMIN_ROLE_GMTOKEN_MANAGER role
+    pub fn set_ondo_user_limit_profile(ctx: Context<SetOndoUserLimitProfile>) -> Result<()> {
+        ctx.accounts.set_ondo_user_limit_profile()
+    }
+
+    /// Set the limit profile new OndoUser accounts of a token are linked to,
+    /// or clear it to copy the default user limits instead
+    /// Signer must have the ADMIN_ROLE_GMTOKEN_MANAGER role
+    pub fn set_token_default_limit_profile(
+        ctx: Context<SetTokenDefaultLimitProfile>,
     ) -> Result<()> {
-        ctx.accounts.set_ondo_user_limits(rate_limit, limit_window)
+        ctx.accounts.set_token_default_limit_profile()
+    }
+
+    /// Preview a mint or redemption without changing any state
+    ///
+    /// Returns the market hours status, the sanity check band, the available
+    /// token and user capacity and the exact USDon/USDC amounts of the trade
+    /// through the transaction return data.
+    /// Unpermissioned
+    pub fn preview_trade(
+        ctx: Context<PreviewTrade>,
+        price: u64,
+        amount: u64,
+        is_buy: bool,
+    ) -> Result<TradePreview> {
+        ctx.accounts.preview_trade(price, amount, is_buy)
     }
 
     /// Revoke a role by closing the Roles account and reclaim rent
@@ -557,6 +1420,43 @@ pub mod ondo_gm {
         Ok(())
     }
 
+    /// Set the USD notional limits applied across all GM Tokens
+    ///
+    /// The global limit caps the notional of all users, the per-user limit caps
+    /// each user's notional across every GM Token. A zero window disables a limit.
+    /// Signer must have the ADMIN_ROLE_GMTOKEN_MANAGER role
+    pub fn set_notional_limits(
+        ctx: Context<GMTokenManagerAdminGlobalPauser>,
+        notional_rate_limit: u64,
+        notional_limit_window: u64,
+        user_notional_rate_limit: u64,
+        user_notional_limit_window: u64,
+    ) -> Result<()> {
+        ctx.accounts.set_notional_limits(
+            notional_rate_limit,
+            notional_limit_window,
+            user_notional_rate_limit,
+            user_notional_limit_window,
+        )
+    }
+
+    /// Grow the GM Token manager state account to the current layout
+    ///
+    /// Must be run once after an upgrade that adds fields to the account.
+    /// Signer must have the ADMIN_ROLE_GMTOKEN_MANAGER role
+    pub fn migrate_gmtoken_manager_state(ctx: Context<MigrateGMTokenManagerState>) -> Result<()> {
+        ctx.accounts.migrate_gmtoken_manager_state()
+    }
+
+    /// Create the account tracking a user's notional across all GM Tokens
+    ///
+    /// Required to trade while the per-user notional limit is enabled.
+    /// Unpermissioned, the signer pays for the account
+    pub fn initialize_user_notional_limit(ctx: Context<InitializeUserNotionalLimit>) -> Result<()> {
+        ctx.accounts
+            .initialize_user_notional_limit(ctx.bumps.user_notional_limit)
+    }
+
     /// Update the secp256k1 attestation signer address
     /// Signer must have the ADMIN_ROLE_GMTOKEN_MANAGER role
     pub fn set_attestation_signer_secp(
@@ -3,6 +3,7 @@ use anchor_lang::prelude::*;
 use crate::{
     constants::{SECONDS_PER_DAY, SECONDS_PER_HOUR},
     errors::OndoError,
+    state::NotionalUsage,
 };
 
 /// GM Token Manager State account - tracks global state for GM Token operations
@@ -33,6 +34,27 @@ pub struct GMTokenManagerState {
     /// Trading hours offset from UTC in seconds
     /// Positive values are east of UTC, negative values are west of UTC
     pub trading_hours_offset: i64,
+
+    // Fields below were added after the initial deployment
+    // Accounts created before then are grown by `migrate_gmtoken_manager_state` and read as zero
+
+    // The maximum USD notional that can be minted, and separately redeemed, across all GM Tokens
+    pub notional_rate_limit: u64,
+
+    // The limit window (in seconds) for the global notional limit, 0 disables the limit
+    pub notional_limit_window: u64,
+
+    // The notional minted across all GM Tokens
+    pub mint_notional_usage: NotionalUsage,
+
+    // The notional redeemed across all GM Tokens
+    pub redeem_notional_usage: NotionalUsage,
+
+    // The maximum USD notional a single user can mint, and separately redeem, across all GM Tokens
+    pub user_notional_rate_limit: u64,
+
+    // The limit window (in seconds) for the per-user notional limit, 0 disables the limit
+    pub user_notional_limit_window: u64,
 }
 
 impl GMTokenManagerState {
@@ -54,7 +76,78 @@ impl GMTokenManagerState {
         Ok(())
     }
 
+    /// Returns whether trades must be charged to the per-user notional limit
+    pub fn user_notional_limit_enabled(&self) -> bool {
+        self.user_notional_limit_window != 0
+    }
+
+    /// Returns the largest amount `consume_notional` would accept at `current_timestamp`,
+    /// None if the global notional limit is disabled
+    pub fn available_notional(&self, current_timestamp: i64, is_buy: bool) -> Result<Option<u64>> {
+        if self.notional_limit_window == 0 {
+            return Ok(None);
+        }
+
+        let usage = if is_buy {
+            &self.mint_notional_usage
+        } else {
+            &self.redeem_notional_usage
+        };
+
+        usage
+            .available(
+                current_timestamp,
+                self.notional_rate_limit,
+                self.notional_limit_window,
+            )
+            .map(Some)
+    }
+
+    /// Consumes `amount` of the global notional limit, if enabled
+    /// # Arguments
+    /// * `amount` - The USD notional of the trade
+    /// * `current_timestamp` - The current timestamp
+    /// * `is_buy` - Whether the trade is a mint (true) or a redemption (false)
+    /// # Returns
+    /// * `Result<()>` - Ok if the amount fits in the available limit, Err(InvalidRateLimit) otherwise
+    pub fn consume_notional(
+        &mut self,
+        amount: u64,
+        current_timestamp: i64,
+        is_buy: bool,
+    ) -> Result<()> {
+        if self.notional_limit_window == 0 {
+            return Ok(());
+        }
+
+        let usage = if is_buy {
+            &mut self.mint_notional_usage
+        } else {
+            &mut self.redeem_notional_usage
+        };
+
+        usage
+            .consume(
+                amount,
+                current_timestamp,
+                self.notional_rate_limit,
+                self.notional_limit_window,
+            )
+            .inspect_err(|_| msg!("Global notional limit exceeded"))
+    }
+
     pub fn check_is_valid_hours(&self, timestamp: i64) -> Result<()> {
+        require!(
+            self.is_market_open(timestamp),
+            OndoError::OutsideMarketHours
+        );
+
+        Ok(())
+    }
+
+    // Returns true if the timestamp falls on a trading day (Monday to Friday)
+    // after applying the trading hours offset
+    pub fn is_market_open(&self, timestamp: i64) -> bool {
         let adjusted_timestamp = timestamp + self.trading_hours_offset;
 
         let days_since_epoch = adjusted_timestamp / SECONDS_PER_DAY;
@@ -63,9 +156,7 @@ impl GMTokenManagerState {
         let day_of_week = (days_since_epoch + 3).rem_euclid(7);
 
         // 5 = Saturday, 6 = Sunday
-        require!(day_of_week < 5, OndoError::OutsideMarketHours);
-
-        Ok(())
+        day_of_week < 5
     }
 }
 
@@ -82,6 +173,12 @@ mod tests {
             bump: 0,
             attestation_signer_secp: [0u8; 20],
             trading_hours_offset,
+            notional_rate_limit: 0,
+            notional_limit_window: 0,
+            mint_notional_usage: NotionalUsage::default(),
+            redeem_notional_usage: NotionalUsage::default(),
+            user_notional_rate_limit: 0,
+            user_notional_limit_window: 0,
         }
     }
 
@@ -190,6 +287,23 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_is_market_open_matches_check_is_valid_hours() {
+        let state = create_test_state(-5 * SECONDS_PER_HOUR);
+
+        // Walk a full week in one hour steps
+        for hour in 0..(7 * 24) {
+            let timestamp = hour * SECONDS_PER_HOUR;
+            assert_eq!(
+                state.is_market_open(timestamp),
+                state.check_is_valid_hours(timestamp).is_ok()
+            );
+        }
+
+        // Saturday, Jan 3, 1970 12:00:00 UTC is closed
+        assert!(!state.is_market_open(2 * SECONDS_PER_DAY + 12 * SECONDS_PER_HOUR));
+    }
+
     #[test]
     fn test_check_is_valid_hours_with_trading_hours_offset() {
         // Test with +8 hours trading_hours offset (e.g., Singapore/Hong Kong)
@@ -326,4 +440,22 @@ mod tests {
             "EDT offset (0s) should be valid"
         );
     }
+
+    #[test]
+    fn test_consume_notional() {
+        let mut state = create_test_state(0);
+
+        // Disabled by default
+        state.consume_notional(u64::MAX, 0, true).unwrap();
+
+        state.notional_rate_limit = 1_000;
+        state.notional_limit_window = 100;
+
+        state.consume_notional(1_000, 0, true).unwrap();
+        assert!(state.consume_notional(1, 0, true).is_err());
+
+        // Mints and redemptions are limited separately
+        state.consume_notional(1_000, 0, false).unwrap();
+        assert!(state.consume_notional(1, 0, false).is_err());
+    }
 }
@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+
+use crate::state::NotionalUsage;
+
+/// LimitGroup state account - rate limit shared by every OndoUser attached to the group,
+/// e.g. all the wallets of one institution, for a single GM Token.
+/// The per-wallet OndoUser limits still apply on top.
+#[account]
+#[derive(InitSpace)]
+pub struct LimitGroup {
+    // The ID chosen by the admin, unique per mint
+    pub group_id: u64,
+
+    // The GM Token mint the group's limit applies to
+    pub mint: Pubkey,
+
+    // The maximum amount the group can mint, and separately redeem, in the limit window
+    pub rate_limit: u64,
+
+    // The limit window, in seconds
+    pub limit_window: u64,
+
+    // The amount minted by the group
+    pub mint_usage: NotionalUsage,
+
+    // The amount redeemed by the group
+    pub redeem_usage: NotionalUsage,
+
+    // The bump used to derive the PDA for this account
+    // Stored so we don't need to recalculate it later
+    pub bump: u8,
+}
+
+impl LimitGroup {
+    /// Returns the largest amount `consume` would accept at `current_timestamp`
+    pub fn available_capacity(&self, current_timestamp: i64, is_buy: bool) -> Result<u64> {
+        let usage = if is_buy {
+            &self.mint_usage
+        } else {
+            &self.redeem_usage
+        };
+
+        usage.available(current_timestamp, self.rate_limit, self.limit_window)
+    }
+
+    /// Consumes `amount` of the group's shared capacity
+    /// # Arguments
+    /// * `amount` - The amount minted or redeemed
+    /// * `current_timestamp` - The current timestamp
+    /// * `is_buy` - Whether the trade is a mint (true) or a redemption (false)
+    /// # Returns
+    /// * `Result<()>` - Ok if the amount fits in the available capacity, Err(InvalidRateLimit) otherwise
+    pub fn consume(&mut self, amount: u64, current_timestamp: i64, is_buy: bool) -> Result<()> {
+        let usage = if is_buy {
+            &mut self.mint_usage
+        } else {
+            &mut self.redeem_usage
+        };
+
+        usage
+            .consume(
+                amount,
+                current_timestamp,
+                self.rate_limit,
+                self.limit_window,
+            )
+            .inspect_err(|_| msg!("Limit group {} rate limit exceeded", self.group_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_group_capacity_is_shared() {
+        let mut group = LimitGroup {
+            group_id: 1,
+            mint: Pubkey::default(),
+            rate_limit: 1_000,
+            limit_window: 100,
+            mint_usage: NotionalUsage::default(),
+            redeem_usage: NotionalUsage::default(),
+            bump: 0,
+        };
+
+        // Two wallets of the group minting draw from the same capacity
+        group.consume(600, 0, true).unwrap();
+        assert!(group.consume(401, 0, true).is_err());
+        group.consume(400, 0, true).unwrap();
+
+        // Redemptions are limited separately
+        group.consume(1_000, 0, false).unwrap();
+    }
+}
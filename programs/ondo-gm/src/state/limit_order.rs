@@ -0,0 +1,144 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::OndoError;
+
+/// LimitOrder account - a mint or redemption that executes only at or better than a limit price.
+///
+/// The user's deposit is held in an associated token account owned by this PDA.
+/// The order can be filled in several parts until the escrow is empty, it expires or
+/// the user cancels it.
+#[account]
+#[derive(InitSpace)]
+pub struct LimitOrder {
+    // The user who placed the order
+    pub user: Pubkey,
+
+    // User supplied identifier, allows a user to place several orders at once
+    pub order_id: u64,
+
+    // The GM Token being minted or redeemed
+    pub mint: Pubkey,
+
+    // The mint of the escrowed tokens
+    // USDon or USDC for mints, the GM Token for redemptions
+    pub deposit_mint: Pubkey,
+
+    // The token account holding the escrowed tokens
+    pub escrow: Pubkey,
+
+    // True if the order mints GM Tokens, false if it redeems them
+    pub is_buy: bool,
+
+    // True if the order settles in USDon, false if it settles in USDC
+    pub is_usdon: bool,
+
+    // The worst acceptable price, scaled by PRICE_SCALING_FACTOR
+    // Mints fill at or below it, redemptions at or above it
+    pub limit_price: u64,
+
+    // Unix timestamp after which the order can no longer be filled
+    pub expires_at: i64,
+
+    // The amount escrowed when the order was placed
+    pub deposit_amount: u64,
+
+    // The amount of GM Tokens minted or redeemed so far
+    pub filled_amount: u64,
+
+    // Unix timestamp of when the order was placed
+    pub created_at: i64,
+
+    // The bump used to derive the PDA for this account
+    // Stored so we don't need to recalculate it later
+    pub bump: u8,
+}
+
+impl LimitOrder {
+    /// Returns true if an attested price satisfies the order's limit price
+    pub fn is_price_acceptable(&self, price: u64) -> bool {
+        if self.is_buy {
+            price <= self.limit_price
+        } else {
+            price >= self.limit_price
+        }
+    }
+
+    /// Checks that the order can be filled at an attested price
+    /// # Arguments
+    /// * `price` - The attested price
+    /// * `current_timestamp` - The current unix timestamp
+    /// # Returns
+    /// * `Result<()>` - Ok if the order is live and the price satisfies its limit price
+    pub fn validate_fill(&self, price: u64, current_timestamp: i64) -> Result<()> {
+        require!(current_timestamp < self.expires_at, OndoError::OrderExpired);
+        require!(self.is_price_acceptable(price), OndoError::LimitPriceNotMet);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_order() -> LimitOrder {
+        LimitOrder {
+            user: Pubkey::default(),
+            order_id: 0,
+            mint: Pubkey::default(),
+            deposit_mint: Pubkey::default(),
+            escrow: Pubkey::default(),
+            is_buy: true,
+            is_usdon: true,
+            limit_price: 100_000_000_000,
+            expires_at: 1_000,
+            deposit_amount: 0,
+            filled_amount: 0,
+            created_at: 0,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn test_buy_order_accepts_prices_at_or_below_limit() {
+        let order = create_test_order();
+
+        assert!(order.is_price_acceptable(99_999_999_999));
+        assert!(order.is_price_acceptable(100_000_000_000));
+        assert!(!order.is_price_acceptable(100_000_000_001));
+    }
+
+    #[test]
+    fn test_sell_order_accepts_prices_at_or_above_limit() {
+        let order = LimitOrder {
+            is_buy: false,
+            ..create_test_order()
+        };
+
+        assert!(!order.is_price_acceptable(99_999_999_999));
+        assert!(order.is_price_acceptable(100_000_000_000));
+        assert!(order.is_price_acceptable(100_000_000_001));
+    }
+
+    #[test]
+    fn test_fill_rejects_price_beyond_limit() {
+        let order = create_test_order();
+
+        assert!(order.validate_fill(100_000_000_000, 0).is_ok());
+        assert_eq!(
+            order.validate_fill(100_000_000_001, 0).unwrap_err(),
+            OndoError::LimitPriceNotMet.into()
+        );
+    }
+
+    #[test]
+    fn test_fill_rejects_expired_order() {
+        let order = create_test_order();
+
+        assert!(order.validate_fill(100_000_000_000, 999).is_ok());
+        assert_eq!(
+            order.validate_fill(100_000_000_000, 1_000).unwrap_err(),
+            OndoError::OrderExpired.into()
+        );
+    }
+}
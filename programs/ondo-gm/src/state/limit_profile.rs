@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+
+/// LimitProfile state account - per-user rate limit shared by reference.
+/// OndoUser accounts linked to a profile use its limits instead of their own,
+/// so editing the profile updates every linked user at once.
+/// Each user keeps tracking its own capacity used.
+#[account]
+#[derive(InitSpace)]
+pub struct LimitProfile {
+    // The ID chosen by the admin, unique across all GM Tokens
+    pub profile_id: u64,
+
+    // The maximum amount each linked user can mint in the limit window
+    // Also applies to redemptions unless a redeem rate limit is set
+    pub rate_limit: u64,
+
+    // The mint limit window, in seconds
+    // Also applies to redemptions unless a redeem limit window is set
+    pub limit_window: u64,
+
+    // The maximum amount each linked user can redeem in the redeem limit window
+    // None falls back to `rate_limit`
+    pub redeem_rate_limit: Option<u64>,
+
+    // The redeem limit window, in seconds
+    // None falls back to `limit_window`
+    pub redeem_limit_window: Option<u64>,
+
+    // The bump used to derive the PDA for this account
+    // Stored so we don't need to recalculate it later
+    pub bump: u8,
+}
+
+impl LimitProfile {
+    /// Returns the rate limit and limit window for mints (`is_buy`) or redemptions
+    pub fn limit(&self, is_buy: bool) -> (u64, u64) {
+        if is_buy {
+            (self.rate_limit, self.limit_window)
+        } else {
+            (
+                self.redeem_rate_limit.unwrap_or(self.rate_limit),
+                self.redeem_limit_window.unwrap_or(self.limit_window),
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redeem_limit_falls_back_to_mint_limit() {
+        let mut profile = LimitProfile {
+            profile_id: 1,
+            rate_limit: 1_000,
+            limit_window: 3_600,
+            redeem_rate_limit: None,
+            redeem_limit_window: None,
+            bump: 255,
+        };
+        assert_eq!(profile.limit(false), (1_000, 3_600));
+
+        profile.redeem_rate_limit = Some(500);
+        assert_eq!(profile.limit(true), (1_000, 3_600));
+        assert_eq!(profile.limit(false), (500, 3_600));
+    }
+}
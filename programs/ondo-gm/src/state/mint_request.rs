@@ -0,0 +1,208 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::PRICE_SCALING_FACTOR,
+    errors::OndoError,
+    utils::{mul_div, normalize_decimals},
+};
+
+/// MintRequest account - the first phase of a two-phase mint.
+///
+/// The user's quote asset is locked in an associated token account owned by this PDA
+/// while the underlying is bought. An operator then settles the request at the final
+/// execution price, or anyone refunds the user once the request times out.
+#[account]
+#[derive(InitSpace)]
+pub struct MintRequest {
+    // The user who requested the mint
+    pub user: Pubkey,
+
+    // User supplied identifier, allows a user to have several open requests
+    pub request_id: u64,
+
+    // The GM Token being minted
+    pub mint: Pubkey,
+
+    // The mint of the locked quote asset, USDon or USDC
+    pub deposit_mint: Pubkey,
+
+    // The token account holding the locked quote asset
+    pub escrow: Pubkey,
+
+    // True if the request is paid in USDon, false if it is paid in USDC
+    pub is_usdon: bool,
+
+    // The amount of the quote asset locked
+    pub deposit_amount: u64,
+
+    // The highest execution price the user accepts, scaled by PRICE_SCALING_FACTOR
+    pub max_price: u64,
+
+    // The amount of GM Tokens requested, what the deposit buys at the max price
+    pub amount: u64,
+
+    // Unix timestamp of when the request was created
+    pub created_at: i64,
+
+    // Unix timestamp from which the request can no longer be settled and is refundable
+    pub expires_at: i64,
+
+    // The bump used to derive the PDA for this account
+    // Stored so we don't need to recalculate it later
+    pub bump: u8,
+}
+
+impl MintRequest {
+    /// Returns the amount of GM Tokens a deposit buys at the max price, fixed when the
+    /// request is created. The deposit is valued at par: USDon, or USDC at its peg.
+    /// # Arguments
+    /// * `deposit_amount` - The amount of the quote asset locked
+    /// * `max_price` - The highest execution price the user accepts
+    /// * `deposit_decimals` - The decimals of the deposit mint
+    /// * `gm_token_decimals` - The decimals of the GM Token
+    /// # Returns
+    /// * `Result<u64>` - The amount of GM Tokens requested, rounded down
+    pub fn requested_amount(
+        deposit_amount: u64,
+        max_price: u64,
+        deposit_decimals: u8,
+        gm_token_decimals: u8,
+    ) -> Result<u64> {
+        require_gt!(max_price, 0, OndoError::InvalidPrice);
+
+        let deposit_value =
+            normalize_decimals(deposit_amount, deposit_decimals, gm_token_decimals, false)?;
+
+        mul_div(deposit_value, PRICE_SCALING_FACTOR as u64, max_price, false)
+    }
+
+    /// Checks that the request can be settled at the attested execution price.
+    /// The requested amount is minted, the deposit left over at a lower price is refunded.
+    /// # Arguments
+    /// * `price` - The attested execution price
+    /// * `amount` - The attested amount of GM Tokens
+    /// * `current_timestamp` - The current unix timestamp
+    /// # Returns
+    /// * `Result<()>` - Ok if the request has not timed out, the price is within its max price
+    ///   and `amount` is the requested amount
+    pub fn validate_settlement(
+        &self,
+        price: u64,
+        amount: u64,
+        current_timestamp: i64,
+    ) -> Result<()> {
+        require!(current_timestamp < self.expires_at, OndoError::OrderExpired);
+        require_gte!(self.max_price, price, OndoError::MaxPriceExceeded);
+        require_eq!(amount, self.amount, OndoError::MintAmountMismatch);
+
+        Ok(())
+    }
+
+    /// Checks that the request has timed out and can be refunded
+    /// # Arguments
+    /// * `current_timestamp` - The current unix timestamp
+    /// # Returns
+    /// * `Result<()>` - Ok if the request has timed out, Err(OrderNotExpired) otherwise
+    pub fn validate_refund(&self, current_timestamp: i64) -> Result<()> {
+        require_gte!(
+            current_timestamp,
+            self.expires_at,
+            OndoError::OrderNotExpired
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_request() -> MintRequest {
+        MintRequest {
+            user: Pubkey::default(),
+            request_id: 0,
+            mint: Pubkey::default(),
+            deposit_mint: Pubkey::default(),
+            escrow: Pubkey::default(),
+            is_usdon: true,
+            deposit_amount: 150_000_000_000,
+            max_price: 150_000_000_000,
+            amount: 1_000_000_000,
+            created_at: 0,
+            expires_at: 1_000,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn test_settlement_rejects_price_above_max_price() {
+        let request = create_test_request();
+
+        assert!(request
+            .validate_settlement(150_000_000_000, 1_000_000_000, 0)
+            .is_ok());
+        assert_eq!(
+            request
+                .validate_settlement(150_000_000_001, 1_000_000_000, 0)
+                .unwrap_err(),
+            OndoError::MaxPriceExceeded.into()
+        );
+    }
+
+    #[test]
+    fn test_timed_out_request_is_refunded_not_settled() {
+        let request = create_test_request();
+
+        assert!(request
+            .validate_settlement(100_000_000_000, 1_000_000_000, 999)
+            .is_ok());
+        assert_eq!(
+            request.validate_refund(999).unwrap_err(),
+            OndoError::OrderNotExpired.into()
+        );
+
+        assert_eq!(
+            request
+                .validate_settlement(100_000_000_000, 1_000_000_000, 1_000)
+                .unwrap_err(),
+            OndoError::OrderExpired.into()
+        );
+        assert!(request.validate_refund(1_000).is_ok());
+    }
+
+    #[test]
+    fn test_requested_amount_is_what_the_deposit_buys_at_max_price() {
+        // 150 USDon buys 1 GM Token at 150 USD
+        assert_eq!(
+            MintRequest::requested_amount(150_000_000_000, 150_000_000_000, 9, 9).unwrap(),
+            1_000_000_000
+        );
+        // 100 USDC buys 0.666666666 GM Tokens at 150 USD
+        assert_eq!(
+            MintRequest::requested_amount(100_000_000, 150_000_000_000, 6, 9).unwrap(),
+            666_666_666
+        );
+    }
+
+    #[test]
+    fn test_settlement_mints_the_requested_amount() {
+        let request = create_test_request();
+
+        assert!(request
+            .validate_settlement(100_000_000_000, 1_000_000_000, 0)
+            .is_ok());
+        assert_eq!(
+            request
+                .validate_settlement(100_000_000_000, 999_999_999, 0)
+                .unwrap_err(),
+            OndoError::MintAmountMismatch.into()
+        );
+        assert_eq!(
+            request
+                .validate_settlement(100_000_000_000, 1_500_000_000, 0)
+                .unwrap_err(),
+            OndoError::MintAmountMismatch.into()
+        );
+    }
+}
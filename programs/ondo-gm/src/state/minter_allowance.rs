@@ -0,0 +1,119 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::OndoError, utils::consume_capacity};
+
+/// MinterAllowance state account - rolling USDon mint allowance of a single minter,
+/// set by the guardian, with the minter's lifetime minted and burned amounts
+#[account]
+#[derive(InitSpace)]
+pub struct MinterAllowance {
+    // The minter the allowance applies to
+    pub minter: Pubkey,
+
+    // The maximum amount of USDon the minter can mint in the limit window
+    pub rate_limit: u64,
+
+    // The limit window, in seconds
+    pub limit_window: u64,
+
+    // The allowance used, decaying linearly over the limit window
+    pub capacity_used: u64,
+
+    // Unix timestamp of the last mint
+    pub last_updated: i64,
+
+    // The total amount of USDon minted by the minter
+    pub total_minted: u64,
+
+    // The total amount of USDon burned by the minter
+    pub total_burned: u64,
+
+    // The bump used to derive the PDA for this account
+    // Stored so we don't need to recalculate it later
+    pub bump: u8,
+}
+
+impl MinterAllowance {
+    /// Consumes `amount` of the allowance and records it as minted
+    /// # Arguments
+    /// * `amount` - The amount of USDon minted
+    /// * `current_timestamp` - The current timestamp
+    /// # Returns
+    /// * `Result<()>` - Ok if the amount fits in the available allowance, Err(InvalidRateLimit) otherwise
+    pub fn record_mint(&mut self, amount: u64, current_timestamp: i64) -> Result<()> {
+        let time_since_last_update = current_timestamp
+            .checked_sub(self.last_updated)
+            .ok_or(OndoError::MathOverflow)?;
+
+        self.capacity_used = consume_capacity(
+            amount,
+            time_since_last_update,
+            self.limit_window,
+            self.capacity_used,
+            self.rate_limit,
+        )?;
+        self.last_updated = current_timestamp;
+        self.total_minted = self
+            .total_minted
+            .checked_add(amount)
+            .ok_or(OndoError::MathOverflow)?;
+
+        Ok(())
+    }
+
+    /// Records `amount` as burned by the minter
+    pub fn record_burn(&mut self, amount: u64) -> Result<()> {
+        self.total_burned = self
+            .total_burned
+            .checked_add(amount)
+            .ok_or(OndoError::MathOverflow)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allowance_decays_over_window() {
+        let mut allowance = MinterAllowance {
+            minter: Pubkey::default(),
+            rate_limit: 1_000,
+            limit_window: 100,
+            capacity_used: 0,
+            last_updated: 0,
+            total_minted: 0,
+            total_burned: 0,
+            bump: 0,
+        };
+
+        allowance.record_mint(1_000, 0).unwrap();
+        assert!(allowance.record_mint(1, 0).is_err());
+
+        // A quarter of the window restores a quarter of the allowance
+        allowance.record_mint(250, 25).unwrap();
+        assert!(allowance.record_mint(1, 25).is_err());
+
+        allowance.record_burn(400).unwrap();
+        assert_eq!(allowance.total_minted, 1_250);
+        assert_eq!(allowance.total_burned, 400);
+    }
+
+    #[test]
+    fn test_zero_allowance_blocks_minting() {
+        let mut allowance = MinterAllowance {
+            minter: Pubkey::default(),
+            rate_limit: 0,
+            limit_window: 0,
+            capacity_used: 0,
+            last_updated: 0,
+            total_minted: 0,
+            total_burned: 0,
+            bump: 0,
+        };
+
+        assert!(allowance.record_mint(1, 10).is_err());
+    }
+}
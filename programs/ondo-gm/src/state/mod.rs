@@ -1,17 +1,43 @@
 pub mod attestation;
 pub mod gmtoken_manager_state;
+pub mod limit_group;
+pub mod limit_order;
+pub mod limit_profile;
+pub mod mint_request;
+pub mod minter_allowance;
+pub mod notional_limit;
 pub mod ondo_user;
+pub mod oracle;
+pub mod order;
+pub mod recurring_order;
+pub mod redemption_queue;
+pub mod retrieval;
 pub mod roles;
 pub mod sanity_check;
+pub mod settlement_asset;
 pub mod token_limit;
 pub mod usdon_manager_state;
+pub mod usdon_reserves;
 pub mod whitelist;
 
 pub use attestation::*;
 pub use gmtoken_manager_state::*;
+pub use limit_group::*;
+pub use limit_order::*;
+pub use limit_profile::*;
+pub use mint_request::*;
+pub use minter_allowance::*;
+pub use notional_limit::*;
 pub use ondo_user::*;
+pub use oracle::*;
+pub use order::*;
+pub use recurring_order::*;
+pub use redemption_queue::*;
+pub use retrieval::*;
 pub use roles::*;
 pub use sanity_check::*;
+pub use settlement_asset::*;
 pub use token_limit::*;
 pub use usdon_manager_state::*;
+pub use usdon_reserves::*;
 pub use whitelist::*;
@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::OndoError,
+    utils::{available_capacity, consume_capacity},
+};
+
+/// Usage of a USD notional rate limit, decaying linearly over the limit window
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, AnchorSerialize, AnchorDeserialize, InitSpace,
+)]
+pub struct NotionalUsage {
+    // The notional used, decaying linearly over the limit window
+    pub capacity_used: u64,
+
+    // Unix timestamp of the last update
+    pub last_updated: i64,
+}
+
+impl NotionalUsage {
+    /// Consumes `amount` of the notional rate limit
+    /// # Arguments
+    /// * `amount` - The USD notional of the trade
+    /// * `current_timestamp` - The current timestamp
+    /// * `rate_limit` - The maximum notional in the limit window
+    /// * `limit_window` - The limit window in seconds
+    /// # Returns
+    /// * `Result<()>` - Ok if the amount fits in the available limit, Err(InvalidRateLimit) otherwise
+    pub fn consume(
+        &mut self,
+        amount: u64,
+        current_timestamp: i64,
+        rate_limit: u64,
+        limit_window: u64,
+    ) -> Result<()> {
+        let time_since_last_update = current_timestamp
+            .checked_sub(self.last_updated)
+            .ok_or(OndoError::MathOverflow)?;
+
+        self.capacity_used = consume_capacity(
+            amount,
+            time_since_last_update,
+            limit_window,
+            self.capacity_used,
+            rate_limit,
+        )?;
+        self.last_updated = current_timestamp;
+
+        Ok(())
+    }
+
+    /// Returns the largest amount `consume` would accept at `current_timestamp`
+    pub fn available(
+        &self,
+        current_timestamp: i64,
+        rate_limit: u64,
+        limit_window: u64,
+    ) -> Result<u64> {
+        let time_since_last_update = current_timestamp
+            .checked_sub(self.last_updated)
+            .ok_or(OndoError::MathOverflow)?;
+
+        available_capacity(
+            time_since_last_update,
+            limit_window,
+            self.capacity_used,
+            rate_limit,
+        )
+    }
+}
+
+/// UserNotionalLimit state account - USD notional minted and redeemed by a user
+/// across all GM Tokens, limited by the per-user notional limit of the GM Token Manager
+#[account]
+#[derive(InitSpace)]
+pub struct UserNotionalLimit {
+    // The user the usage applies to
+    pub user: Pubkey,
+
+    // The notional minted by the user
+    pub mint_usage: NotionalUsage,
+
+    // The notional redeemed by the user
+    pub redeem_usage: NotionalUsage,
+
+    // The bump used to derive the PDA for this account
+    // Stored so we don't need to recalculate it later
+    pub bump: u8,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notional_usage_decays_over_window() {
+        let mut usage = NotionalUsage::default();
+
+        usage.consume(1_000, 0, 1_000, 100).unwrap();
+        assert!(usage.consume(1, 0, 1_000, 100).is_err());
+
+        // Half the window restores half the limit
+        assert_eq!(usage.available(50, 1_000, 100).unwrap(), 500);
+        usage.consume(500, 50, 1_000, 100).unwrap();
+        assert!(usage.consume(1, 50, 1_000, 100).is_err());
+        assert_eq!(usage.available(50, 1_000, 100).unwrap(), 0);
+        assert_eq!(usage.last_updated, 50);
+    }
+}
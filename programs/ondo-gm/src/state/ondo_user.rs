@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+use crate::{errors::OndoError, state::LimitProfile, utils::available_capacity};
+
 /// OndoUser state account - tracks user-specific data for a GM Token
 #[account]
 #[derive(InitSpace)]
@@ -10,10 +12,12 @@ pub struct OndoUser {
     // The GM Token mint associated with this OndoUser account
     pub mint: Pubkey,
 
-    // Rate limit defines the maximum amount of tokens that can be minted/redeemed
+    // Rate limit defines the maximum amount of tokens that can be minted
+    // Also applies to redemptions unless a redeem rate limit is set
     pub rate_limit: Option<u64>,
 
-    // Limit window defines the time frame (in seconds) for the rate limit
+    // Limit window defines the time frame (in seconds) for the mint rate limit
+    // Also applies to redemptions unless a redeem limit window is set
     pub limit_window: Option<u64>,
 
     // The amount of mint capacity used in the current limit window
@@ -31,4 +35,183 @@ pub struct OndoUser {
     // The bump used to derive the PDA for this account
     // Stored so we don't need to recalculate it later
     pub bump: u8,
+
+    // Fields below were added after the initial deployment
+    // Accounts created before then are grown by `migrate_ondo_user` and read as None
+
+    // The maximum amount of tokens that can be redeemed
+    // None falls back to `rate_limit`
+    pub redeem_rate_limit: Option<u64>,
+
+    // The time frame (in seconds) for the redeem rate limit
+    // None falls back to `limit_window`
+    pub redeem_limit_window: Option<u64>,
+
+    // The LimitGroup whose shared capacity the user also draws from, if any
+    pub limit_group: Option<Pubkey>,
+
+    // The LimitProfile whose limits replace the ones above, if any
+    pub limit_profile: Option<Pubkey>,
+}
+
+/// The update applied by `batch_set_ondo_user_limits` to each OndoUser account
+#[derive(Clone, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub enum OndoUserLimitsUpdate {
+    /// Set the user's own limits, keeping the capacity already used
+    SetLimits {
+        rate_limit: u64,
+        limit_window: u64,
+        redeem_rate_limit: Option<u64>,
+        redeem_limit_window: Option<u64>,
+    },
+    /// Clear the capacity used, keeping the limits
+    ResetUsage,
+}
+
+impl OndoUser {
+    /// Returns the rate limit and limit window for mints (`is_buy`) or redemptions,
+    /// None if the limit is not configured
+    pub fn limit(&self, is_buy: bool) -> Option<(u64, u64)> {
+        if is_buy {
+            self.rate_limit.zip(self.limit_window)
+        } else {
+            self.redeem_rate_limit
+                .or(self.rate_limit)
+                .zip(self.redeem_limit_window.or(self.limit_window))
+        }
+    }
+
+    /// Sets the user's own limits, initializing the capacity used if not already set
+    /// # Arguments
+    /// * `rate_limit` - The mint rate limit
+    /// * `limit_window` - The mint limit window in seconds
+    /// * `redeem_rate_limit` - The redeem rate limit, None to use the mint rate limit
+    /// * `redeem_limit_window` - The redeem limit window in seconds, None to use the mint window
+    pub fn set_limits(
+        &mut self,
+        rate_limit: u64,
+        limit_window: u64,
+        redeem_rate_limit: Option<u64>,
+        redeem_limit_window: Option<u64>,
+    ) {
+        self.rate_limit = Some(rate_limit);
+        self.limit_window = Some(limit_window);
+        self.redeem_rate_limit = redeem_rate_limit;
+        self.redeem_limit_window = redeem_limit_window;
+
+        if self.mint_capacity_used.is_none() {
+            self.mint_capacity_used = Some(0);
+        }
+        if self.redeem_capacity_used.is_none() {
+            self.redeem_capacity_used = Some(0);
+        }
+    }
+
+    /// Clears the mint and redeem capacity used, restoring the full limits
+    pub fn reset_usage(&mut self) {
+        self.mint_capacity_used = Some(0);
+        self.mint_last_updated = None;
+        self.redeem_capacity_used = Some(0);
+        self.redeem_last_updated = None;
+    }
+
+    /// Returns the limit that applies to the user: the linked profile's if any, its own otherwise
+    /// # Arguments
+    /// * `is_buy` - Whether the trade is a mint (true) or a redemption (false)
+    /// * `limit_profile` - The LimitProfile account, required when the user is linked to one
+    /// # Returns
+    /// * `Result<Option<(u64, u64)>>` - The rate limit and limit window, None if not configured
+    pub fn effective_limit(
+        &self,
+        is_buy: bool,
+        limit_profile: Option<&Account<LimitProfile>>,
+    ) -> Result<Option<(u64, u64)>> {
+        let Some(limit_profile_key) = self.limit_profile else {
+            return Ok(self.limit(is_buy));
+        };
+
+        let limit_profile = limit_profile.ok_or(OndoError::LimitProfileNotProvided)?;
+        require_keys_eq!(
+            limit_profile.key(),
+            limit_profile_key,
+            OndoError::InvalidLimitProfile
+        );
+
+        Ok(Some(limit_profile.limit(is_buy)))
+    }
+
+    /// Returns the largest amount the user-level check would accept for a mint (`is_buy`)
+    /// or redemption at `current_timestamp`.
+    /// None if the trade would fail because the limit is not configured
+    pub fn available_capacity(
+        &self,
+        is_buy: bool,
+        current_timestamp: i64,
+        limit_profile: Option<&Account<LimitProfile>>,
+    ) -> Result<Option<u64>> {
+        let Some((rate_limit, limit_window)) = self.effective_limit(is_buy, limit_profile)? else {
+            return Ok(None);
+        };
+        let (capacity_used, last_updated) = if is_buy {
+            (self.mint_capacity_used, self.mint_last_updated)
+        } else {
+            (self.redeem_capacity_used, self.redeem_last_updated)
+        };
+        let Some(capacity_used) = capacity_used else {
+            return Ok(None);
+        };
+        let time_since_last_update = current_timestamp
+            .checked_sub(last_updated.unwrap_or(current_timestamp))
+            .ok_or(OndoError::MathOverflow)?;
+
+        available_capacity(
+            time_since_last_update,
+            limit_window,
+            capacity_used,
+            rate_limit,
+        )
+        .map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_limits_keeps_usage_until_reset() {
+        let mut ondo_user = OndoUser {
+            owner: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            rate_limit: None,
+            limit_window: None,
+            mint_capacity_used: None,
+            mint_last_updated: None,
+            redeem_capacity_used: None,
+            redeem_last_updated: None,
+            bump: 255,
+            redeem_rate_limit: None,
+            redeem_limit_window: None,
+            limit_group: None,
+            limit_profile: None,
+        };
+
+        ondo_user.set_limits(1_000, 3_600, Some(500), None);
+        assert_eq!(ondo_user.limit(true), Some((1_000, 3_600)));
+        assert_eq!(ondo_user.limit(false), Some((500, 3_600)));
+        assert_eq!(ondo_user.mint_capacity_used, Some(0));
+
+        ondo_user.mint_capacity_used = Some(400);
+        ondo_user.mint_last_updated = Some(100);
+        ondo_user.set_limits(2_000, 3_600, None, None);
+        assert_eq!(ondo_user.mint_capacity_used, Some(400));
+
+        ondo_user.reset_usage();
+        assert_eq!(ondo_user.mint_capacity_used, Some(0));
+        assert_eq!(ondo_user.mint_last_updated, None);
+        assert_eq!(
+            ondo_user.available_capacity(true, 200, None).unwrap(),
+            Some(2_000)
+        );
+    }
 }
@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::SWITCHBOARD_ON_DEMAND_PROGRAM_ID;
+
+/// The kind of oracle account a price is read from
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, AnchorDeserialize, AnchorSerialize, InitSpace,
+)]
+pub enum OracleAdapter {
+    // Pyth pull oracle `PriceUpdateV2` account
+    #[default]
+    Pyth,
+    // Switchboard On-Demand `PullFeedAccountData` account
+    Switchboard,
+    // `PushedPrice` account written by an admin
+    // Not available on mainnet builds, where prices must come from an external oracle
+    #[cfg(not(feature = "mainnet"))]
+    Pushed,
+}
+
+impl OracleAdapter {
+    /// Returns the program that owns price accounts of this kind
+    pub fn default_owner(&self) -> Pubkey {
+        match self {
+            OracleAdapter::Pyth => pyth_solana_receiver_sdk::ID,
+            OracleAdapter::Switchboard => SWITCHBOARD_ON_DEMAND_PROGRAM_ID,
+            #[cfg(not(feature = "mainnet"))]
+            OracleAdapter::Pushed => crate::ID,
+        }
+    }
+}
+
+/// PushedPrice account - a price written directly by an admin.
+///
+/// Used where no external oracle is available, such as on a local test validator.
+/// The `Pushed` adapter reading it does not exist on mainnet builds.
+#[account]
+#[derive(InitSpace)]
+pub struct PushedPrice {
+    // The mint the price is for
+    pub mint: Pubkey,
+
+    // The price, scaled by USDC_PRICE_DECIMALS
+    pub price: u64,
+
+    // The confidence interval of the price, scaled by USDC_PRICE_DECIMALS
+    pub conf: u64,
+
+    // Unix timestamp of the last update
+    pub updated_at: i64,
+
+    // The bump used to derive the PDA for this account
+    // Stored so we don't need to recalculate it later
+    pub bump: u8,
+}
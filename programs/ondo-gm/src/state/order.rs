@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+
+/// Order account - a mint or redemption queued outside market hours.
+///
+/// The user's USDon, USDC or GM Tokens are held in an associated token account
+/// owned by this PDA until an operator settles the order or the user cancels it.
+#[account]
+#[derive(InitSpace)]
+pub struct Order {
+    // The user who queued the order
+    pub user: Pubkey,
+
+    // User supplied identifier, allows a user to queue several orders at once
+    pub order_id: u64,
+
+    // The GM Token being minted or redeemed
+    pub mint: Pubkey,
+
+    // The mint of the escrowed tokens
+    // USDon or USDC for mints, the GM Token for redemptions
+    pub deposit_mint: Pubkey,
+
+    // The token account holding the escrowed tokens
+    pub escrow: Pubkey,
+
+    // True if the order mints GM Tokens, false if it redeems them
+    pub is_buy: bool,
+
+    // True if the order settles in USDon, false if it settles in USDC
+    pub is_usdon: bool,
+
+    // The amount escrowed when the order was created
+    pub deposit_amount: u64,
+
+    // Unix timestamp of when the order was created
+    pub created_at: i64,
+
+    // The bump used to derive the PDA for this account
+    // Stored so we don't need to recalculate it later
+    pub bump: u8,
+}
+
+impl Order {
+    /// Returns true if the order can settle against `usdc_mint`
+    /// USDC mints spend the escrowed USDC, so it must be the escrowed mint
+    pub fn accepts_usdc_mint(&self, usdc_mint: &Pubkey) -> bool {
+        !self.is_buy || self.is_usdon || self.deposit_mint == *usdc_mint
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_order() -> Order {
+        Order {
+            user: Pubkey::default(),
+            order_id: 0,
+            mint: Pubkey::default(),
+            deposit_mint: Pubkey::new_unique(),
+            escrow: Pubkey::default(),
+            is_buy: true,
+            is_usdon: false,
+            deposit_amount: 0,
+            created_at: 0,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn test_usdc_mint_order_requires_escrowed_usdc_mint() {
+        let order = create_test_order();
+
+        assert!(order.accepts_usdc_mint(&order.deposit_mint));
+        assert!(!order.accepts_usdc_mint(&Pubkey::new_unique()));
+    }
+
+    #[test]
+    fn test_other_orders_accept_any_usdc_mint() {
+        let usdon_order = Order {
+            is_usdon: true,
+            ..create_test_order()
+        };
+        assert!(usdon_order.accepts_usdc_mint(&Pubkey::new_unique()));
+
+        let redemption = Order {
+            is_buy: false,
+            ..create_test_order()
+        };
+        assert!(redemption.accepts_usdc_mint(&Pubkey::new_unique()));
+    }
+}
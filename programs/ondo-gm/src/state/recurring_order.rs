@@ -0,0 +1,193 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::OndoError;
+
+/// RecurringOrder account - a schedule of GM Token purchases paid for in USDon.
+///
+/// The user's USDon allowance is held in an associated token account owned by this PDA.
+/// Each installment is executed with an attestation once `next_execution_at` is reached.
+#[account]
+#[derive(InitSpace)]
+pub struct RecurringOrder {
+    // The user who created the schedule
+    pub user: Pubkey,
+
+    // User supplied identifier, allows a user to run several schedules at once
+    pub order_id: u64,
+
+    // The GM Token being purchased
+    pub mint: Pubkey,
+
+    // The token account holding the USDon allowance
+    pub escrow: Pubkey,
+
+    // The maximum USDon spent by a single installment
+    pub amount_per_execution: u64,
+
+    // Number of seconds between installments
+    pub interval: i64,
+
+    // Unix timestamp from which the next installment can be executed
+    pub next_execution_at: i64,
+
+    // Number of installments executed so far
+    pub executions: u64,
+
+    // Total USDon spent so far
+    pub total_spent: u64,
+
+    // Unix timestamp of when the schedule was created
+    pub created_at: i64,
+
+    // The bump used to derive the PDA for this account
+    // Stored so we don't need to recalculate it later
+    pub bump: u8,
+}
+
+impl RecurringOrder {
+    /// Checks that the next installment is due
+    /// # Arguments
+    /// * `current_timestamp` - The current unix timestamp
+    /// # Returns
+    /// * `Result<()>` - Ok if `next_execution_at` is reached, Err(InstallmentNotDue) otherwise
+    pub fn validate_due(&self, current_timestamp: i64) -> Result<()> {
+        require_gte!(
+            current_timestamp,
+            self.next_execution_at,
+            OndoError::InstallmentNotDue
+        );
+
+        Ok(())
+    }
+
+    /// Checks that an installment spends its budget, the per-installment amount or the
+    /// remaining allowance if lower, so the permissionless crank cannot execute installments
+    /// smaller than the schedule. One unit below the budget is accepted to absorb the
+    /// rounding of the cost.
+    /// # Arguments
+    /// * `budget` - The most the installment can spend
+    /// * `spent` - The amount the installment spent
+    /// # Returns
+    /// * `Result<()>` - Ok if `spent` is within one unit of `budget`, Err(InstallmentUnderspent) otherwise
+    pub fn validate_spend(&self, budget: u64, spent: u64) -> Result<()> {
+        require_gte!(
+            spent.saturating_add(1),
+            budget,
+            OndoError::InstallmentUnderspent
+        );
+
+        Ok(())
+    }
+
+    /// Moves the schedule to the first installment strictly after `current_timestamp`.
+    /// Installments missed while the market was closed or the crank was idle are skipped
+    /// rather than executed back to back.
+    /// # Arguments
+    /// * `current_timestamp` - The current unix timestamp
+    /// # Returns
+    /// * `Result<()>` - Ok if the schedule was advanced, Err on overflow
+    pub fn advance_schedule(&mut self, current_timestamp: i64) -> Result<()> {
+        require_gt!(self.interval, 0, OndoError::InvalidInterval);
+
+        let elapsed = current_timestamp
+            .checked_sub(self.next_execution_at)
+            .ok_or(OndoError::MathOverflow)?
+            .max(0);
+
+        let periods = elapsed
+            .checked_div(self.interval)
+            .ok_or(OndoError::MathOverflow)?
+            .checked_add(1)
+            .ok_or(OndoError::MathOverflow)?;
+
+        self.next_execution_at = periods
+            .checked_mul(self.interval)
+            .and_then(|offset| self.next_execution_at.checked_add(offset))
+            .ok_or(OndoError::MathOverflow)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_order() -> RecurringOrder {
+        RecurringOrder {
+            user: Pubkey::default(),
+            order_id: 0,
+            mint: Pubkey::default(),
+            escrow: Pubkey::default(),
+            amount_per_execution: 0,
+            interval: 100,
+            next_execution_at: 1_000,
+            executions: 0,
+            total_spent: 0,
+            created_at: 0,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn test_advance_schedule_on_time() {
+        let mut order = create_test_order();
+
+        order.advance_schedule(1_000).unwrap();
+        assert_eq!(order.next_execution_at, 1_100);
+    }
+
+    #[test]
+    fn test_advance_schedule_late_within_interval() {
+        let mut order = create_test_order();
+
+        order.advance_schedule(1_099).unwrap();
+        assert_eq!(order.next_execution_at, 1_100);
+    }
+
+    #[test]
+    fn test_advance_schedule_skips_missed_installments() {
+        let mut order = create_test_order();
+
+        // Three installments were missed, the next one stays on the original grid
+        order.advance_schedule(1_350).unwrap();
+        assert_eq!(order.next_execution_at, 1_400);
+
+        // Exactly on a grid point moves to the following one
+        order.advance_schedule(1_400).unwrap();
+        assert_eq!(order.next_execution_at, 1_500);
+    }
+
+    #[test]
+    fn test_advance_schedule_rejects_zero_interval() {
+        let mut order = RecurringOrder {
+            interval: 0,
+            ..create_test_order()
+        };
+
+        assert!(order.advance_schedule(1_000).is_err());
+    }
+
+    #[test]
+    fn test_installment_due_from_next_execution() {
+        let order = create_test_order();
+
+        assert_eq!(
+            order.validate_due(999).unwrap_err(),
+            OndoError::InstallmentNotDue.into()
+        );
+        assert!(order.validate_due(1_000).is_ok());
+    }
+
+    #[test]
+    fn test_installment_must_spend_its_budget() {
+        let order = create_test_order();
+
+        assert!(order.validate_spend(1_000, 1_000).is_ok());
+        assert!(order.validate_spend(1_000, 999).is_ok());
+        assert_eq!(
+            order.validate_spend(1_000, 998).unwrap_err(),
+            OndoError::InstallmentUnderspent.into()
+        );
+    }
+}
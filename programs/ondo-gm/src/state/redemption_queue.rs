@@ -0,0 +1,252 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::MAX_REDEMPTION_CLAIMS, errors::OndoError};
+
+/// RedemptionQueue state account - USDC owed to redemptions the USDC vault could not cover,
+/// paid out oldest first as the vault is topped up
+#[account]
+#[derive(InitSpace)]
+pub struct RedemptionQueue {
+    // The ID assigned to the next queued claim
+    pub next_claim_id: u64,
+
+    // The total amount of USDC owed to queued claims
+    // Mirrored by `USDonManagerState::queued_usdc`, which reserves it in the USDC vault
+    pub total_owed: u64,
+
+    // The queued claims, oldest first
+    #[max_len(MAX_REDEMPTION_CLAIMS)]
+    pub claims: Vec<RedemptionClaim>,
+
+    // The bump used to derive the PDA for this account
+    // Stored so we don't need to recalculate it later
+    pub bump: u8,
+}
+
+/// USDC owed to a single redemption
+#[derive(Clone, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct RedemptionClaim {
+    // The unique ID of the claim
+    pub id: u64,
+
+    // The user who redeemed
+    pub user: Pubkey,
+
+    // The USDC token account the claim is paid to
+    pub destination: Pubkey,
+
+    // The amount of USDC still owed
+    pub amount: u64,
+
+    // Unix timestamp at which the claim was queued
+    pub created_at: i64,
+}
+
+impl RedemptionQueue {
+    /// Returns true if no more claims can be queued
+    pub fn is_full(&self) -> bool {
+        self.claims.len() >= MAX_REDEMPTION_CLAIMS
+    }
+
+    /// Appends a claim to the back of the queue
+    /// # Arguments
+    /// * `queued_usdc` - The USDonManagerState's counter of USDC reserved for queued claims
+    /// # Returns
+    /// * `Result<u64>` - The ID of the new claim
+    pub fn push(
+        &mut self,
+        queued_usdc: &mut u64,
+        user: Pubkey,
+        destination: Pubkey,
+        amount: u64,
+        created_at: i64,
+    ) -> Result<u64> {
+        require_gt!(amount, 0, OndoError::InvalidAmount);
+        require!(!self.is_full(), OndoError::RedemptionQueueFull);
+
+        let id = self.next_claim_id;
+
+        self.claims.push(RedemptionClaim {
+            id,
+            user,
+            destination,
+            amount,
+            created_at,
+        });
+        self.next_claim_id = id.checked_add(1).ok_or(OndoError::MathOverflow)?;
+        self.total_owed = self
+            .total_owed
+            .checked_add(amount)
+            .ok_or(OndoError::MathOverflow)?;
+        *queued_usdc = queued_usdc
+            .checked_add(amount)
+            .ok_or(OndoError::MathOverflow)?;
+
+        Ok(id)
+    }
+
+    /// Pays up to `available` towards the claim at the front of the queue,
+    /// removing it once it is paid in full
+    /// # Arguments
+    /// * `queued_usdc` - The USDonManagerState's counter of USDC reserved for queued claims
+    /// * `available` - The USDC available for the payment
+    /// # Returns
+    /// * `Result<Option<(RedemptionClaim, u64)>>` - The claim as it was before the payment and the amount paid,
+    ///   None if the queue is empty or nothing is available
+    pub fn pay_front(
+        &mut self,
+        queued_usdc: &mut u64,
+        available: u64,
+    ) -> Result<Option<(RedemptionClaim, u64)>> {
+        let Some(front) = self.claims.first_mut() else {
+            return Ok(None);
+        };
+
+        if available == 0 {
+            return Ok(None);
+        }
+
+        let claim = front.clone();
+        let paid = available.min(front.amount);
+
+        front.amount -= paid;
+        if front.amount == 0 {
+            self.claims.remove(0);
+        }
+
+        self.total_owed = self
+            .total_owed
+            .checked_sub(paid)
+            .ok_or(OndoError::MathOverflow)?;
+        *queued_usdc = queued_usdc
+            .checked_sub(paid)
+            .ok_or(OndoError::MathOverflow)?;
+
+        Ok(Some((claim, paid)))
+    }
+
+    /// Changes the USDC token account a claim is paid to
+    /// # Returns
+    /// * `Result<RedemptionClaim>` - The claim as it was before the change
+    pub fn reroute(&mut self, claim_id: u64, destination: Pubkey) -> Result<RedemptionClaim> {
+        let claim = self
+            .claims
+            .iter_mut()
+            .find(|claim| claim.id == claim_id)
+            .ok_or(OndoError::RedemptionClaimNotFound)?;
+
+        let previous = claim.clone();
+        claim.destination = destination;
+
+        Ok(previous)
+    }
+
+    /// Moves the claim at the front of the queue to the back, so a claim that cannot be paid
+    /// does not block the claims behind it
+    /// # Returns
+    /// * `Result<RedemptionClaim>` - The deferred claim
+    pub fn defer_front(&mut self) -> Result<RedemptionClaim> {
+        require!(!self.claims.is_empty(), OndoError::RedemptionClaimNotFound);
+
+        let claim = self.claims.remove(0);
+        self.claims.push(claim.clone());
+
+        Ok(claim)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_queue() -> RedemptionQueue {
+        RedemptionQueue {
+            next_claim_id: 0,
+            total_owed: 0,
+            claims: vec![],
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn test_claims_are_paid_in_order() {
+        let mut queue = create_test_queue();
+        let first = Pubkey::new_unique();
+        let second = Pubkey::new_unique();
+
+        let mut queued_usdc = 0;
+
+        assert!(queue.push(&mut queued_usdc, first, first, 0, 1).is_err());
+        assert_eq!(
+            queue.push(&mut queued_usdc, first, first, 100, 1).unwrap(),
+            0
+        );
+        assert_eq!(
+            queue.push(&mut queued_usdc, second, second, 50, 2).unwrap(),
+            1
+        );
+        assert_eq!((queue.total_owed, queued_usdc), (150, 150));
+
+        // Partial payment of the oldest claim
+        let (claim, paid) = queue.pay_front(&mut queued_usdc, 60).unwrap().unwrap();
+        assert_eq!((claim.id, paid), (0, 60));
+        assert_eq!(queue.claims[0].amount, 40);
+        assert_eq!((queue.total_owed, queued_usdc), (90, 90));
+
+        // Nothing available
+        assert!(queue.pay_front(&mut queued_usdc, 0).unwrap().is_none());
+
+        // The oldest claim is paid off before the next one
+        let (claim, paid) = queue.pay_front(&mut queued_usdc, 1_000).unwrap().unwrap();
+        assert_eq!((claim.id, paid), (0, 40));
+        let (claim, paid) = queue.pay_front(&mut queued_usdc, 1_000).unwrap().unwrap();
+        assert_eq!((claim.user, paid), (second, 50));
+
+        assert!(queue.claims.is_empty());
+        assert_eq!((queue.total_owed, queued_usdc), (0, 0));
+        assert!(queue.pay_front(&mut queued_usdc, 1_000).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_queue_is_bounded() {
+        let mut queue = create_test_queue();
+
+        let mut queued_usdc = 0;
+
+        for _ in 0..MAX_REDEMPTION_CLAIMS {
+            queue
+                .push(&mut queued_usdc, Pubkey::default(), Pubkey::default(), 1, 0)
+                .unwrap();
+        }
+
+        assert!(queue.is_full());
+        assert!(queue
+            .push(&mut queued_usdc, Pubkey::default(), Pubkey::default(), 1, 0)
+            .is_err());
+    }
+
+    #[test]
+    fn test_stuck_claim_can_be_rerouted_or_deferred() {
+        let mut queue = create_test_queue();
+        let mut queued_usdc = 0;
+        let (first, second) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+        queue.push(&mut queued_usdc, first, first, 100, 1).unwrap();
+        queue.push(&mut queued_usdc, second, second, 50, 2).unwrap();
+
+        // Deferring moves the front claim behind the others, keeping what it is owed
+        assert_eq!(queue.defer_front().unwrap().id, 0);
+        let (claim, paid) = queue.pay_front(&mut queued_usdc, 1_000).unwrap().unwrap();
+        assert_eq!((claim.id, paid), (1, 50));
+
+        let new_destination = Pubkey::new_unique();
+        assert_eq!(
+            queue.reroute(0, new_destination).unwrap().destination,
+            first
+        );
+        assert_eq!(queue.claims[0].destination, new_destination);
+        assert!(queue.reroute(1, new_destination).is_err());
+
+        assert_eq!((queue.total_owed, queued_usdc), (100, 100));
+    }
+}
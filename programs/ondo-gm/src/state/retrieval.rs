@@ -0,0 +1,135 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::OndoError, utils::consume_capacity};
+
+/// RetrievalDestination state account - a token account that tokens can be retrieved to
+/// from the vaults owned by the USDon manager. The account existing allowlists the destination.
+#[account]
+#[derive(InitSpace)]
+pub struct RetrievalDestination {
+    // The allowlisted token account
+    pub destination: Pubkey,
+
+    // The bump used to derive the PDA for this account
+    // Stored so we don't need to recalculate it later
+    pub bump: u8,
+}
+
+/// RetrievalLimit state account - rolling withdrawal limit and timelock policy
+/// for retrieving a single mint from the vaults owned by the USDon manager
+#[account]
+#[derive(InitSpace)]
+pub struct RetrievalLimit {
+    // The mint the limit applies to
+    pub mint: Pubkey,
+
+    // The maximum amount that can be retrieved in the limit window
+    pub rate_limit: u64,
+
+    // The limit window, in seconds
+    pub limit_window: u64,
+
+    // The amount retrieved, decaying linearly over the limit window
+    pub capacity_used: u64,
+
+    // Unix timestamp of the last retrieval
+    pub last_updated: i64,
+
+    // Retrievals above this amount must be requested and wait for the timelock delay
+    // 0 disables the timelock
+    pub timelock_threshold: u64,
+
+    // The delay (in seconds) between requesting and executing a timelocked retrieval
+    pub timelock_delay: u64,
+
+    // The bump used to derive the PDA for this account
+    // Stored so we don't need to recalculate it later
+    pub bump: u8,
+}
+
+impl RetrievalLimit {
+    /// Returns whether retrieving `amount` must go through the timelock
+    pub fn requires_timelock(&self, amount: u64) -> bool {
+        self.timelock_threshold != 0 && amount > self.timelock_threshold
+    }
+
+    /// Consumes `amount` of the rolling withdrawal limit
+    /// # Arguments
+    /// * `amount` - The amount retrieved
+    /// * `current_timestamp` - The current timestamp
+    /// # Returns
+    /// * `Result<()>` - Ok if the amount fits in the available limit, Err(InvalidRateLimit) otherwise
+    pub fn record_retrieval(&mut self, amount: u64, current_timestamp: i64) -> Result<()> {
+        let time_since_last_update = current_timestamp
+            .checked_sub(self.last_updated)
+            .ok_or(OndoError::MathOverflow)?;
+
+        self.capacity_used = consume_capacity(
+            amount,
+            time_since_last_update,
+            self.limit_window,
+            self.capacity_used,
+            self.rate_limit,
+        )?;
+        self.last_updated = current_timestamp;
+
+        Ok(())
+    }
+}
+
+/// PendingRetrieval state account - a retrieval above the timelock threshold,
+/// executable once its timelock delay has passed
+#[account]
+#[derive(InitSpace)]
+pub struct PendingRetrieval {
+    // The ID chosen by the requester, unique per mint and destination
+    pub request_id: u64,
+
+    // The mint of the tokens to retrieve
+    pub mint: Pubkey,
+
+    // The token account the tokens are retrieved to
+    pub destination: Pubkey,
+
+    // The amount to retrieve
+    pub amount: u64,
+
+    // The admin who requested the retrieval
+    pub requested_by: Pubkey,
+
+    // Unix timestamp from which the retrieval can be executed
+    pub executable_at: i64,
+
+    // The bump used to derive the PDA for this account
+    // Stored so we don't need to recalculate it later
+    pub bump: u8,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retrieval_limit() {
+        let mut limit = RetrievalLimit {
+            mint: Pubkey::default(),
+            rate_limit: 1_000,
+            limit_window: 100,
+            capacity_used: 0,
+            last_updated: 0,
+            timelock_threshold: 0,
+            timelock_delay: 0,
+            bump: 0,
+        };
+
+        assert!(!limit.requires_timelock(u64::MAX));
+        limit.timelock_threshold = 500;
+        assert!(!limit.requires_timelock(500));
+        assert!(limit.requires_timelock(501));
+
+        limit.record_retrieval(600, 0).unwrap();
+        assert!(limit.record_retrieval(401, 0).is_err());
+        limit.record_retrieval(500, 10).unwrap();
+        assert_eq!(limit.capacity_used, 1_000);
+    }
+}
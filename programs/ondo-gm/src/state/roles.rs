@@ -41,6 +41,8 @@ pub enum RoleType {
     AdminRoleWhitelist,
     UpdateMultiplierRole,
     UpdateMetadataRole,
+    OrderOperatorRole,
+    RateLimitExemptRole,
 }
 
 impl RoleType {
@@ -66,6 +68,8 @@ impl RoleType {
     pub const UPDATE_MULTIPLIER_ROLE: &[u8] = b"UpdateMultiplierRole";
 
     pub const UPDATE_METADATA_ROLE: &[u8] = b"UpdateMetadataRole";
+    pub const ORDER_OPERATOR_ROLE: &[u8] = b"OrderOperatorRole";
+    pub const RATE_LIMIT_EXEMPT_ROLE: &[u8] = b"RateLimitExemptRole";
 
     pub const fn seed(&self) -> &'static [u8] {
         match self {
@@ -90,6 +94,8 @@ impl RoleType {
             RoleType::AdminRoleWhitelist => Self::ADMIN_ROLE_WHITELIST,
             RoleType::UpdateMultiplierRole => Self::UPDATE_MULTIPLIER_ROLE,
             RoleType::UpdateMetadataRole => Self::UPDATE_METADATA_ROLE,
+            RoleType::OrderOperatorRole => Self::ORDER_OPERATOR_ROLE,
+            RoleType::RateLimitExemptRole => Self::RATE_LIMIT_EXEMPT_ROLE,
         }
     }
 }
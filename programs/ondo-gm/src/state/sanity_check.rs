@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+use crate::{constants::BASIS_POINTS_DIVISOR, errors::OndoError};
+
 /// OracleSanityCheck state account - tracks sanity check parameters for a specific mint
 #[account]
 #[derive(InitSpace)]
@@ -23,3 +25,26 @@ pub struct OracleSanityCheck {
     // Stored so we don't need to recalculate it later
     pub bump: u8,
 }
+
+impl OracleSanityCheck {
+    /// Returns the lowest and highest prices accepted by the sanity check
+    pub fn price_band(&self) -> Result<(u64, u64)> {
+        let deviation = self
+            .last_price
+            .checked_mul(self.allowed_deviation_bps)
+            .ok_or(OndoError::MathOverflow)?
+            .checked_div(BASIS_POINTS_DIVISOR)
+            .ok_or(OndoError::MathOverflow)?;
+
+        let min_price = self
+            .last_price
+            .checked_sub(deviation)
+            .ok_or(OndoError::MathOverflow)?;
+        let max_price = self
+            .last_price
+            .checked_add(deviation)
+            .ok_or(OndoError::MathOverflow)?;
+
+        Ok((min_price, max_price))
+    }
+}
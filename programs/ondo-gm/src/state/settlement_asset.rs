@@ -0,0 +1,306 @@
+use anchor_lang::prelude::*;
+
+use super::OracleAdapter;
+use crate::{
+    constants::{
+        MAX_AGE_UPPER_BOUND, MAX_CONFIDENCE_THRESHOLD_BPS, MAX_PRICE_UPPER_BOUND, USDC_PEG_PRICE,
+        USDC_PRICE_DECIMALS,
+    },
+    errors::OndoError,
+};
+
+/// SettlementAsset account - a stablecoin accepted in place of USDon for mints and redemptions.
+///
+/// The asset is exchanged 1:1 with USDon through a vault owned by the USDonManagerState,
+/// the same way USDC is. One account exists per accepted mint.
+#[account]
+#[derive(InitSpace)]
+pub struct SettlementAsset {
+    // The mint of the settlement asset
+    pub mint: Pubkey,
+
+    // The token program of the mint (SPL Token or Token-2022)
+    pub token_program: Pubkey,
+
+    // The vault holding the asset, the USDonManagerState's associated token account
+    pub vault: Pubkey,
+
+    // Whether the asset is exchanged at par without an oracle check
+    // Set explicitly by the admin, the oracle configuration below must then be unset
+    pub priced_at_par: bool,
+
+    // The oracle price account for the asset
+    pub price_update: Pubkey,
+
+    // The owner of the price update account
+    // Pubkey::default() means the default owner of the oracle adapter
+    pub price_update_owner: Pubkey,
+
+    // The Pyth price feed ID for the asset, unused by other oracle adapters
+    pub feed_id: [u8; 32],
+
+    // The length of time (in seconds) that an oracle price is considered valid
+    pub oracle_max_age: u64,
+
+    // The maximum accepted oracle confidence interval, in basis points of the price
+    pub confidence_threshold_bps: u64,
+
+    // The depeg floor, scaled by USDC_PRICE_DECIMALS
+    // Swaps halt while the oracle price is below it
+    pub min_price: u64,
+
+    // The depeg ceiling, scaled by USDC_PRICE_DECIMALS
+    // Swaps halt while the oracle price is above it, so the asset is never paid out at par
+    // while it trades well above it
+    pub max_price: u64,
+
+    // The kind of oracle account the price is read from
+    pub oracle_adapter: OracleAdapter,
+
+    // Whether mints and redemptions may settle in this asset
+    pub enabled: bool,
+
+    // The bump used to derive the PDA for this account
+    // Stored so we don't need to recalculate it later
+    pub bump: u8,
+}
+
+impl SettlementAsset {
+    /// Returns true if the asset's price is checked against an oracle
+    pub fn has_oracle(&self) -> bool {
+        !self.priced_at_par
+    }
+
+    /// Returns the configured owner of the price update account,
+    /// or the owner of the selected oracle adapter if none is set
+    pub fn effective_price_update_owner(&self) -> Pubkey {
+        if self.price_update_owner == Pubkey::default() {
+            self.oracle_adapter.default_owner()
+        } else {
+            self.price_update_owner
+        }
+    }
+
+    /// Checks that the accounts of a settlement leg match the registered asset
+    /// # Arguments
+    /// * `mint` - The mint of the settlement leg
+    /// * `token_program` - The token program of the settlement leg
+    /// * `vault` - The protocol vault of the settlement leg
+    /// # Returns
+    /// * `Result<()>` - Ok if the asset is enabled and the accounts match it, Err otherwise
+    pub fn validate_leg(
+        &self,
+        mint: &Pubkey,
+        token_program: &Pubkey,
+        vault: &Pubkey,
+    ) -> Result<()> {
+        require!(self.enabled, OndoError::SettlementAssetDisabled);
+        require_keys_eq!(*mint, self.mint, OndoError::InvalidInputMint);
+        require_keys_eq!(
+            *token_program,
+            self.token_program,
+            OndoError::ProgramMismatch
+        );
+        require_keys_eq!(*vault, self.vault, OndoError::InvalidVault);
+
+        Ok(())
+    }
+
+    /// Validates the oracle configuration
+    /// An asset priced at par has no oracle configured. Otherwise the price account must be set,
+    /// the max age must be within `MAX_AGE_UPPER_BOUND`, the confidence threshold within
+    /// `MAX_CONFIDENCE_THRESHOLD_BPS`, the depeg floor between zero and par and the depeg ceiling
+    /// between par and `MAX_PRICE_UPPER_BOUND`.
+    pub fn validate_oracle_config(&self) -> Result<()> {
+        if self.priced_at_par {
+            require_keys_eq!(
+                self.price_update,
+                Pubkey::default(),
+                OndoError::InvalidOraclePriceAddress
+            );
+
+            return Ok(());
+        }
+
+        require_keys_neq!(
+            self.price_update,
+            Pubkey::default(),
+            OndoError::InvalidOraclePriceAddress
+        );
+
+        require_gt!(self.oracle_max_age, 0, OndoError::InvalidOraclePriceMaxAge);
+        require_gte!(
+            MAX_AGE_UPPER_BOUND,
+            self.oracle_max_age,
+            OndoError::InvalidOraclePriceMaxAge
+        );
+
+        require_gt!(
+            self.confidence_threshold_bps,
+            0,
+            OndoError::InvalidConfidenceThreshold
+        );
+        require_gte!(
+            MAX_CONFIDENCE_THRESHOLD_BPS,
+            self.confidence_threshold_bps,
+            OndoError::InvalidConfidenceThreshold
+        );
+
+        require_gt!(self.min_price, 0, OndoError::InvalidPrice);
+        require_gte!(
+            10u64.pow(USDC_PRICE_DECIMALS as u32),
+            self.min_price,
+            OndoError::InvalidPrice
+        );
+
+        require!(
+            (USDC_PEG_PRICE..=MAX_PRICE_UPPER_BOUND).contains(&self.max_price),
+            OndoError::InvalidPrice
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_asset() -> SettlementAsset {
+        SettlementAsset {
+            mint: Pubkey::new_unique(),
+            token_program: Pubkey::new_unique(),
+            vault: Pubkey::new_unique(),
+            priced_at_par: false,
+            price_update: Pubkey::new_unique(),
+            price_update_owner: Pubkey::default(),
+            feed_id: [0; 32],
+            oracle_max_age: 60,
+            confidence_threshold_bps: 100,
+            min_price: 98_000_000,
+            max_price: 101_000_000,
+            oracle_adapter: OracleAdapter::Pyth,
+            enabled: true,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn test_asset_without_oracle_skips_config_checks() {
+        let mut asset = SettlementAsset {
+            priced_at_par: true,
+            price_update: Pubkey::default(),
+            oracle_max_age: 0,
+            confidence_threshold_bps: 0,
+            min_price: 0,
+            max_price: 0,
+            ..create_test_asset()
+        };
+        assert!(!asset.has_oracle());
+        assert!(asset.validate_oracle_config().is_ok());
+
+        // Par pricing cannot be combined with an oracle account
+        asset.price_update = Pubkey::new_unique();
+        assert!(asset.validate_oracle_config().is_err());
+    }
+
+    #[test]
+    fn test_oracle_priced_asset_requires_price_account() {
+        let asset = SettlementAsset {
+            price_update: Pubkey::default(),
+            ..create_test_asset()
+        };
+        assert!(asset.has_oracle());
+        assert!(asset.validate_oracle_config().is_err());
+    }
+
+    #[test]
+    fn test_price_update_owner_defaults_to_adapter_owner() {
+        let mut asset = create_test_asset();
+        assert_eq!(
+            asset.effective_price_update_owner(),
+            OracleAdapter::Pyth.default_owner()
+        );
+
+        let owner = Pubkey::new_unique();
+        asset.price_update_owner = owner;
+        assert_eq!(asset.effective_price_update_owner(), owner);
+    }
+
+    #[test]
+    fn test_validate_oracle_config() {
+        let mut asset = create_test_asset();
+        assert!(asset.validate_oracle_config().is_ok());
+
+        asset.min_price = 100_000_000;
+        assert!(asset.validate_oracle_config().is_ok());
+        asset.min_price = 98_000_000;
+
+        // Zero or excessive max age
+        asset.oracle_max_age = 0;
+        assert!(asset.validate_oracle_config().is_err());
+        asset.oracle_max_age = MAX_AGE_UPPER_BOUND + 1;
+        assert!(asset.validate_oracle_config().is_err());
+        asset.oracle_max_age = 60;
+
+        // Confidence threshold must be set and within MAX_CONFIDENCE_THRESHOLD_BPS
+        asset.confidence_threshold_bps = 0;
+        assert!(asset.validate_oracle_config().is_err());
+        asset.confidence_threshold_bps = MAX_CONFIDENCE_THRESHOLD_BPS + 1;
+        assert!(asset.validate_oracle_config().is_err());
+        asset.confidence_threshold_bps = MAX_CONFIDENCE_THRESHOLD_BPS;
+        assert!(asset.validate_oracle_config().is_ok());
+        asset.confidence_threshold_bps = 100;
+
+        // Depeg floor must be between zero and par
+        asset.min_price = 0;
+        assert!(asset.validate_oracle_config().is_err());
+        asset.min_price = 100_000_001;
+        assert!(asset.validate_oracle_config().is_err());
+        asset.min_price = 98_000_000;
+
+        // Depeg ceiling must be between par and MAX_PRICE_UPPER_BOUND
+        asset.max_price = 100_000_000;
+        assert!(asset.validate_oracle_config().is_ok());
+        asset.max_price = 0;
+        assert!(asset.validate_oracle_config().is_err());
+        asset.max_price = 99_999_999;
+        assert!(asset.validate_oracle_config().is_err());
+        asset.max_price = MAX_PRICE_UPPER_BOUND + 1;
+        assert!(asset.validate_oracle_config().is_err());
+    }
+
+    #[test]
+    fn test_leg_must_match_registered_asset() {
+        let mut asset = create_test_asset();
+        let (mint, token_program, vault) = (asset.mint, asset.token_program, asset.vault);
+
+        assert!(asset.validate_leg(&mint, &token_program, &vault).is_ok());
+        assert_eq!(
+            asset
+                .validate_leg(&Pubkey::new_unique(), &token_program, &vault)
+                .unwrap_err(),
+            OndoError::InvalidInputMint.into()
+        );
+        assert_eq!(
+            asset
+                .validate_leg(&mint, &Pubkey::new_unique(), &vault)
+                .unwrap_err(),
+            OndoError::ProgramMismatch.into()
+        );
+        assert_eq!(
+            asset
+                .validate_leg(&mint, &token_program, &Pubkey::new_unique())
+                .unwrap_err(),
+            OndoError::InvalidVault.into()
+        );
+
+        asset.enabled = false;
+        assert_eq!(
+            asset
+                .validate_leg(&mint, &token_program, &vault)
+                .unwrap_err(),
+            OndoError::SettlementAssetDisabled.into()
+        );
+    }
+}
@@ -1,5 +1,11 @@
 use anchor_lang::prelude::*;
 
+use crate::{
+    constants::MAX_TOKEN_LIMIT_TIERS,
+    errors::OndoError,
+    utils::{available_capacity, available_net_flow, consume_capacity},
+};
+
 /// TokenLimit state account - tracks global token limit parameters for a specific GM Token
 #[account]
 #[derive(InitSpace)]
@@ -7,10 +13,12 @@ pub struct TokenLimit {
     // The GM Token mint associated with this TokenLimit account
     pub mint: Pubkey,
 
-    // Rate limit defines the maximum amount of tokens that can be minted/redeemed globally
+    // Rate limit defines the maximum amount of tokens that can be minted globally
+    // Also applies to redemptions unless a redeem rate limit is set
     pub rate_limit: Option<u64>,
 
-    // Limit window defines the time frame (in seconds) for the global rate limit
+    // Limit window defines the time frame (in seconds) for the global mint rate limit
+    // Also applies to redemptions unless a redeem limit window is set
     pub limit_window: Option<u64>,
 
     // The amount of mint capacity used in the current limit window
@@ -33,13 +41,327 @@ pub struct TokenLimit {
     // If true, then minting is not allowed
     pub minting_paused: bool,
 
-    // Default user rate limit for this token
+    // Default user mint rate limit for this token
     pub default_user_rate_limit: Option<u64>,
 
-    // Default user limit window for this token
+    // Default user mint limit window for this token
     pub default_user_limit_window: Option<u64>,
 
     // The bump used to derive the PDA for this account
     // Stored so we don't need to recalculate it later
     pub bump: u8,
+
+    // Fields below were added after the initial deployment
+    // Accounts created before then are grown by `migrate_token_limit` and read as None
+
+    // The maximum amount of tokens that can be redeemed globally
+    // None falls back to `rate_limit`
+    pub redeem_rate_limit: Option<u64>,
+
+    // The time frame (in seconds) for the global redeem rate limit
+    // None falls back to `limit_window`
+    pub redeem_limit_window: Option<u64>,
+
+    // Default user redeem rate limit for this token
+    // None falls back to `default_user_rate_limit`
+    pub default_user_redeem_rate_limit: Option<u64>,
+
+    // Default user redeem limit window for this token
+    // None falls back to `default_user_limit_window`
+    pub default_user_redeem_limit_window: Option<u64>,
+
+    // Whether mints and redemptions are limited by their net flow instead of
+    // two independent buckets. Uses the mint rate limit and limit window
+    pub net_flow_enabled: bool,
+
+    // The signed net flow: mints add, redemptions subtract, decaying toward zero
+    pub net_flow_position: i64,
+
+    // Unix timestamp of the last net flow update
+    pub net_flow_last_updated: i64,
+
+    // Additional rate limits evaluated alongside the limits above, e.g. an hourly burst cap
+    // next to a daily cap. Each tier caps the mint and the redeem volume separately
+    pub limit_tiers: [LimitTier; MAX_TOKEN_LIMIT_TIERS],
+
+    // The LimitProfile new OndoUser accounts are linked to, instead of copying the default user limits
+    pub default_limit_profile: Option<Pubkey>,
+}
+
+/// A rate limit tier of a TokenLimit account
+/// A tier with a zero limit window is not configured
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, AnchorSerialize, AnchorDeserialize, InitSpace,
+)]
+pub struct LimitTier {
+    // The maximum amount of tokens that can be minted, and separately redeemed, in the limit window
+    pub rate_limit: u64,
+
+    // The limit window, in seconds
+    pub limit_window: u64,
+
+    // The amount minted, decaying linearly over the limit window
+    pub mint_capacity_used: u64,
+
+    // Unix timestamp of the last mint
+    pub mint_last_updated: i64,
+
+    // The amount redeemed, decaying linearly over the limit window
+    pub redeem_capacity_used: u64,
+
+    // Unix timestamp of the last redemption
+    pub redeem_last_updated: i64,
+}
+
+impl LimitTier {
+    /// Returns the capacity used by mints (`is_buy`) or redemptions and when it was last updated
+    pub fn usage(&self, is_buy: bool) -> (u64, i64) {
+        if is_buy {
+            (self.mint_capacity_used, self.mint_last_updated)
+        } else {
+            (self.redeem_capacity_used, self.redeem_last_updated)
+        }
+    }
+}
+
+impl TokenLimit {
+    /// Returns the global rate limit and limit window for mints (`is_buy`) or redemptions,
+    /// None if the limit is not configured
+    pub fn limit(&self, is_buy: bool) -> Option<(u64, u64)> {
+        if is_buy {
+            self.rate_limit.zip(self.limit_window)
+        } else {
+            self.redeem_rate_limit
+                .or(self.rate_limit)
+                .zip(self.redeem_limit_window.or(self.limit_window))
+        }
+    }
+
+    /// Returns the largest amount the token-level checks would accept for a mint (`is_buy`)
+    /// or redemption at `current_timestamp`, including the limit tiers.
+    /// None if the trade would fail because the limit is not configured
+    pub fn available_capacity(&self, is_buy: bool, current_timestamp: i64) -> Result<Option<u64>> {
+        let mut available = if self.net_flow_enabled {
+            let Some((rate_limit, limit_window)) = self.limit(true) else {
+                return Ok(None);
+            };
+            let time_since_last_update = current_timestamp
+                .checked_sub(self.net_flow_last_updated)
+                .ok_or(OndoError::MathOverflow)?;
+
+            available_net_flow(
+                is_buy,
+                time_since_last_update,
+                limit_window,
+                self.net_flow_position,
+                rate_limit,
+            )?
+        } else {
+            let Some((rate_limit, limit_window)) = self.limit(is_buy) else {
+                return Ok(None);
+            };
+            let (capacity_used, last_updated) = if is_buy {
+                (self.mint_capacity_used, self.mint_last_updated)
+            } else {
+                (self.redeem_capacity_used, self.redeem_last_updated)
+            };
+            let Some(capacity_used) = capacity_used else {
+                return Ok(None);
+            };
+            let time_since_last_update = current_timestamp
+                .checked_sub(last_updated.unwrap_or(current_timestamp))
+                .ok_or(OndoError::MathOverflow)?;
+
+            available_capacity(
+                time_since_last_update,
+                limit_window,
+                capacity_used,
+                rate_limit,
+            )?
+        };
+
+        for tier in self
+            .limit_tiers
+            .iter()
+            .filter(|tier| tier.limit_window != 0)
+        {
+            let (capacity_used, last_updated) = tier.usage(is_buy);
+            let time_since_last_update = current_timestamp
+                .checked_sub(last_updated)
+                .ok_or(OndoError::MathOverflow)?;
+
+            available = available.min(available_capacity(
+                time_since_last_update,
+                tier.limit_window,
+                capacity_used,
+                tier.rate_limit,
+            )?);
+        }
+
+        Ok(Some(available))
+    }
+
+    /// Consumes `amount` of the mint (`is_buy`) or redeem usage of every configured limit tier
+    /// # Arguments
+    /// * `amount` - The amount minted or redeemed
+    /// * `is_buy` - True for a mint, false for a redemption
+    /// * `current_timestamp` - The current timestamp
+    /// # Returns
+    /// * `Result<()>` - Ok if the amount fits in every tier, Err(InvalidRateLimit) otherwise
+    pub fn consume_limit_tiers(
+        &mut self,
+        amount: u64,
+        is_buy: bool,
+        current_timestamp: i64,
+    ) -> Result<()> {
+        for (index, tier) in self.limit_tiers.iter_mut().enumerate() {
+            if tier.limit_window == 0 {
+                continue;
+            }
+
+            let (capacity_used, last_updated) = tier.usage(is_buy);
+            let time_since_last_update = current_timestamp
+                .checked_sub(last_updated)
+                .ok_or(OndoError::MathOverflow)?;
+
+            let capacity_used = consume_capacity(
+                amount,
+                time_since_last_update,
+                tier.limit_window,
+                capacity_used,
+                tier.rate_limit,
+            )
+            .inspect_err(|_| msg!("Token rate limit tier {} exceeded", index))?;
+
+            if is_buy {
+                tier.mint_capacity_used = capacity_used;
+                tier.mint_last_updated = current_timestamp;
+            } else {
+                tier.redeem_capacity_used = capacity_used;
+                tier.redeem_last_updated = current_timestamp;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_token_limit(rate_limit: Option<u64>, limit_window: Option<u64>) -> TokenLimit {
+        TokenLimit {
+            mint: Pubkey::default(),
+            rate_limit,
+            limit_window,
+            mint_capacity_used: None,
+            mint_last_updated: None,
+            redeem_capacity_used: None,
+            redeem_last_updated: None,
+            redemption_paused: false,
+            minting_paused: false,
+            default_user_rate_limit: None,
+            default_user_limit_window: None,
+            bump: 0,
+            redeem_rate_limit: None,
+            redeem_limit_window: None,
+            default_user_redeem_rate_limit: None,
+            default_user_redeem_limit_window: None,
+            net_flow_enabled: false,
+            net_flow_position: 0,
+            net_flow_last_updated: 0,
+            limit_tiers: [LimitTier::default(); MAX_TOKEN_LIMIT_TIERS],
+            default_limit_profile: None,
+        }
+    }
+
+    #[test]
+    fn test_redeem_limit_falls_back_to_mint_limit() {
+        let mut token_limit = create_test_token_limit(Some(100), Some(60));
+
+        assert_eq!(token_limit.limit(true), Some((100, 60)));
+        assert_eq!(token_limit.limit(false), Some((100, 60)));
+
+        token_limit.redeem_rate_limit = Some(1_000);
+        assert_eq!(token_limit.limit(true), Some((100, 60)));
+        assert_eq!(token_limit.limit(false), Some((1_000, 60)));
+
+        token_limit.rate_limit = None;
+        token_limit.redeem_limit_window = Some(3_600);
+        assert_eq!(token_limit.limit(true), None);
+        assert_eq!(token_limit.limit(false), Some((1_000, 3_600)));
+    }
+
+    #[test]
+    fn test_limit_tiers() {
+        let mut token_limit = create_test_token_limit(None, None);
+
+        // No tiers configured
+        token_limit.consume_limit_tiers(u64::MAX, true, 0).unwrap();
+
+        // Hourly burst cap of 100 and daily cap of 300
+        token_limit.limit_tiers[0].rate_limit = 100;
+        token_limit.limit_tiers[0].limit_window = 3_600;
+        token_limit.limit_tiers[2].rate_limit = 300;
+        token_limit.limit_tiers[2].limit_window = 86_400;
+
+        token_limit.consume_limit_tiers(100, true, 0).unwrap();
+        assert!(token_limit.consume_limit_tiers(1, true, 0).is_err());
+
+        // The hourly tier resets, the daily tier only restores 12 per hour
+        token_limit.consume_limit_tiers(100, true, 3_600).unwrap();
+        token_limit
+            .consume_limit_tiers(100, true, 2 * 3_600)
+            .unwrap();
+        assert_eq!(token_limit.limit_tiers[2].mint_capacity_used, 276);
+        assert!(token_limit
+            .consume_limit_tiers(100, true, 3 * 3_600)
+            .is_err());
+    }
+
+    #[test]
+    fn test_limit_tiers_track_mints_and_redemptions_separately() {
+        let mut token_limit = create_test_token_limit(None, None);
+        token_limit.limit_tiers[0].rate_limit = 100;
+        token_limit.limit_tiers[0].limit_window = 3_600;
+
+        // Exhausting the mint usage leaves the redeem usage untouched
+        token_limit.consume_limit_tiers(100, true, 0).unwrap();
+        assert!(token_limit.consume_limit_tiers(1, true, 0).is_err());
+        token_limit.consume_limit_tiers(100, false, 0).unwrap();
+        assert!(token_limit.consume_limit_tiers(1, false, 0).is_err());
+    }
+
+    #[test]
+    fn test_available_capacity() {
+        let mut token_limit = create_test_token_limit(Some(1_000), Some(100));
+        token_limit.mint_capacity_used = Some(400);
+        token_limit.mint_last_updated = Some(0);
+
+        assert_eq!(token_limit.available_capacity(true, 10).unwrap(), Some(700));
+        assert_eq!(token_limit.available_capacity(false, 10).unwrap(), None);
+
+        // The tightest tier caps the available capacity
+        token_limit.limit_tiers[1].rate_limit = 500;
+        token_limit.limit_tiers[1].limit_window = 1_000;
+        assert_eq!(token_limit.available_capacity(true, 10).unwrap(), Some(500));
+
+        // Tier usage is tracked per direction
+        token_limit.limit_tiers[1].redeem_capacity_used = 450;
+        token_limit.limit_tiers[1].redeem_last_updated = 10;
+        assert_eq!(token_limit.available_capacity(true, 10).unwrap(), Some(500));
+        token_limit.redeem_capacity_used = Some(0);
+        assert_eq!(token_limit.available_capacity(false, 10).unwrap(), Some(50));
+
+        // Net-flow mode uses the mint limit in both directions
+        token_limit.net_flow_enabled = true;
+        token_limit.net_flow_position = 300;
+        token_limit.limit_tiers[1] = LimitTier::default();
+        assert_eq!(token_limit.available_capacity(true, 0).unwrap(), Some(700));
+        assert_eq!(
+            token_limit.available_capacity(false, 0).unwrap(),
+            Some(1_300)
+        );
+    }
 }
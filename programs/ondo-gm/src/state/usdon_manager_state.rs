@@ -1,4 +1,12 @@
 use anchor_lang::prelude::*;
+use pyth_solana_receiver_sdk::price_update::get_feed_id_from_hex;
+
+use super::OracleAdapter;
+use crate::{
+    constants::{CONFIDENCE_THRESHOLD_BPS, MIN_PRICE, USDC_PYTH_ID},
+    errors::OndoError,
+    utils::consume_capacity,
+};
 
 /// USDonManagerState state account - tracks global configuration for the USDon stablecoin system
 #[account]
@@ -28,4 +36,447 @@ pub struct USDonManagerState {
     // The bump used to derive the PDA for this account
     // Stored so we don't need to recalculate it later
     pub bump: u8,
+
+    // Fields below were added after the initial deployment
+    // Accounts created before then are grown by `migrate_usdon_manager_state` and read as zero
+
+    // The Pyth price feed ID for USDC/USD
+    // All zeroes falls back to USDC_PYTH_ID
+    pub usdc_feed_id: [u8; 32],
+
+    // The program that must own the USDC price update account
+    // Pubkey::default() falls back to the owner of the selected oracle adapter
+    pub usdc_price_update_owner: Pubkey,
+
+    // The kind of oracle account the USDC price is read from
+    pub usdc_oracle_adapter: OracleAdapter,
+
+    // The secondary USDC price oracle account, used when the primary price is unusable
+    // Pubkey::default() means no secondary oracle is configured
+    pub usdc_secondary_price_update: Pubkey,
+
+    // The kind of oracle account the secondary USDC price is read from
+    pub usdc_secondary_oracle_adapter: OracleAdapter,
+
+    // The Pyth price feed ID of the secondary oracle
+    // All zeroes falls back to USDC_PYTH_ID
+    pub usdc_secondary_feed_id: [u8; 32],
+
+    // The program that must own the secondary USDC price update account
+    // Pubkey::default() falls back to the owner of the secondary oracle adapter
+    pub usdc_secondary_price_update_owner: Pubkey,
+
+    // The maximum divergence (in basis points) allowed between the primary and secondary prices
+    // 0 disables the cross-check
+    pub usdc_oracle_max_divergence_bps: u64,
+
+    // The minimum accepted USDC price (depeg floor), scaled by USDC_PRICE_DECIMALS
+    // 0 falls back to MIN_PRICE
+    pub usdc_min_price: u64,
+
+    // The maximum accepted USDC price (depeg ceiling), scaled by USDC_PRICE_DECIMALS
+    // 0 disables the upper bound
+    pub usdc_max_price: u64,
+
+    // The maximum oracle confidence interval, in basis points of the price
+    // 0 falls back to CONFIDENCE_THRESHOLD_BPS
+    pub usdc_confidence_threshold_bps: u64,
+
+    // Whether USDC is converted at the oracle price instead of 1:1
+    // Only applies when oracle pricing is enabled
+    pub usdc_oracle_conversion_enabled: bool,
+
+    // The band around the peg (in basis points) within which USDC is converted at the oracle price
+    // Trades are rejected while the price is outside the band
+    pub usdc_conversion_band_bps: u64,
+
+    // How redemptions are settled when the USDC vault cannot cover the payout
+    pub usdc_vault_shortfall_mode: VaultShortfallMode,
+
+    // The fee (in basis points) charged on PSM deposits and redemptions
+    pub psm_fee_bps: u64,
+
+    // Whether USDC deposits into the PSM are paused
+    pub psm_deposit_paused: bool,
+
+    // Whether USDon redemptions from the PSM are paused
+    pub psm_redemption_paused: bool,
+
+    // The maximum amount of USDon minted or redeemed through the PSM in the limit window
+    // 0 means the PSM rate limit is not configured and the PSM is closed
+    pub psm_rate_limit: u64,
+
+    // The PSM rate limit window, in seconds
+    pub psm_limit_window: u64,
+
+    // The USDon capacity used by PSM deposits, decaying linearly over the limit window
+    pub psm_deposit_capacity_used: u64,
+
+    // Unix timestamp of the last PSM deposit
+    pub psm_deposit_last_updated: i64,
+
+    // The USDon capacity used by PSM redemptions, decaying linearly over the limit window
+    pub psm_redemption_capacity_used: u64,
+
+    // Unix timestamp of the last PSM redemption
+    pub psm_redemption_last_updated: i64,
+
+    // Whether USDon minting is paused
+    // Set by `reconcile_reserves` when USDon backing drops below the collateralization threshold
+    // Applies to every USDon mint, including the USDon minted by GM Token redemptions
+    pub usdon_minting_paused: bool,
+
+    // The maximum USDon supply, in USDon base units
+    // 0 means no supply ceiling
+    pub usdon_supply_cap: u64,
+
+    // The USDC owed to queued redemption claims
+    // Reserved in the USDC vault: no other payout, retrieval or reserve count may use it
+    pub queued_usdc: u64,
+}
+
+/// How a redemption paid out of a vault is settled when the vault balance is too low
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, AnchorSerialize, AnchorDeserialize, InitSpace,
+)]
+pub enum VaultShortfallMode {
+    // Reject the redemption
+    #[default]
+    Revert,
+    // Pay out what the vault holds and settle the remainder in USDon
+    SettleInUSDon,
+    // Pay out what the vault holds and queue the remainder in the redemption queue
+    Queue,
+}
+
+impl USDonManagerState {
+    /// Returns the configured USDC/USD feed ID, or the default Pyth feed if none is set
+    pub fn effective_usdc_feed_id(&self) -> Result<[u8; 32]> {
+        if self.usdc_feed_id == [0; 32] {
+            Ok(get_feed_id_from_hex(USDC_PYTH_ID)?)
+        } else {
+            Ok(self.usdc_feed_id)
+        }
+    }
+
+    /// Returns whether a secondary USDC price oracle is configured
+    pub fn has_secondary_usdc_oracle(&self) -> bool {
+        self.usdc_secondary_price_update != Pubkey::default()
+    }
+
+    /// Returns the configured secondary USDC/USD feed ID, or the default Pyth feed if none is set
+    pub fn effective_usdc_secondary_feed_id(&self) -> Result<[u8; 32]> {
+        if self.usdc_secondary_feed_id == [0; 32] {
+            Ok(get_feed_id_from_hex(USDC_PYTH_ID)?)
+        } else {
+            Ok(self.usdc_secondary_feed_id)
+        }
+    }
+
+    /// Returns the configured USDC depeg floor, or MIN_PRICE if none is set
+    pub fn effective_usdc_min_price(&self) -> u64 {
+        if self.usdc_min_price == 0 {
+            MIN_PRICE
+        } else {
+            self.usdc_min_price
+        }
+    }
+
+    /// Returns the configured USDC depeg ceiling, if any
+    pub fn effective_usdc_max_price(&self) -> Option<u64> {
+        (self.usdc_max_price != 0).then_some(self.usdc_max_price)
+    }
+
+    /// Returns the configured oracle confidence threshold, or CONFIDENCE_THRESHOLD_BPS if none is set
+    pub fn effective_usdc_confidence_threshold_bps(&self) -> u64 {
+        if self.usdc_confidence_threshold_bps == 0 {
+            CONFIDENCE_THRESHOLD_BPS
+        } else {
+            self.usdc_confidence_threshold_bps
+        }
+    }
+
+    /// Returns the configured owner of the USDC price update account,
+    /// or the owner of the selected oracle adapter if none is set
+    pub fn effective_usdc_price_update_owner(&self) -> Pubkey {
+        if self.usdc_price_update_owner == Pubkey::default() {
+            self.usdc_oracle_adapter.default_owner()
+        } else {
+            self.usdc_price_update_owner
+        }
+    }
+
+    /// Returns the configured owner of the secondary USDC price update account,
+    /// or the owner of the secondary oracle adapter if none is set
+    pub fn effective_usdc_secondary_price_update_owner(&self) -> Pubkey {
+        if self.usdc_secondary_price_update_owner == Pubkey::default() {
+            self.usdc_secondary_oracle_adapter.default_owner()
+        } else {
+            self.usdc_secondary_price_update_owner
+        }
+    }
+
+    /// Returns the USDC vault balance not reserved for queued redemption claims
+    pub fn unreserved_usdc(&self, usdc_vault_balance: u64) -> u64 {
+        usdc_vault_balance.saturating_sub(self.queued_usdc)
+    }
+
+    /// Returns the balance of a settlement vault available for payouts
+    /// Queued redemption claims are owed USDC, so only the USDC vault holds a reserve for them
+    /// # Arguments
+    /// * `vault_balance` - The balance of the vault
+    /// * `is_usdc_vault` - Whether the vault is the USDC vault
+    pub fn vault_liquidity(&self, vault_balance: u64, is_usdc_vault: bool) -> u64 {
+        if is_usdc_vault {
+            self.unreserved_usdc(vault_balance)
+        } else {
+            vault_balance
+        }
+    }
+
+    /// Rejects mints that would take the USDon supply above the supply ceiling
+    /// # Arguments
+    /// * `supply` - The current USDon supply
+    /// * `amount` - The amount of USDon to mint
+    /// # Returns
+    /// * `Result<()>` - Ok if the supply stays within the ceiling, Err(USDonSupplyCapExceeded) otherwise
+    pub fn check_usdon_supply_cap(&self, supply: u64, amount: u64) -> Result<()> {
+        if self.usdon_supply_cap == 0 {
+            return Ok(());
+        }
+
+        let new_supply = supply.checked_add(amount).ok_or(OndoError::MathOverflow)?;
+
+        require_gte!(
+            self.usdon_supply_cap,
+            new_supply,
+            OndoError::USDonSupplyCapExceeded
+        );
+
+        Ok(())
+    }
+
+    /// Consumes PSM rate limit capacity, restoring capacity linearly over the limit window
+    /// # Arguments
+    /// * `amount` - The amount of USDon minted or redeemed
+    /// * `current_timestamp` - The current timestamp
+    /// * `is_deposit` - Whether the capacity is consumed by a deposit (true) or a redemption (false)
+    /// # Returns
+    /// * `Result<()>` - Ok if the amount fits in the available capacity, Err otherwise
+    pub fn consume_psm_capacity(
+        &mut self,
+        amount: u64,
+        current_timestamp: i64,
+        is_deposit: bool,
+    ) -> Result<()> {
+        // PSM limits are not configured - fail the transaction
+        require!(
+            self.psm_rate_limit != 0 && self.psm_limit_window != 0,
+            OndoError::InvalidRateLimit
+        );
+
+        let (capacity_used, last_updated) = if is_deposit {
+            (
+                self.psm_deposit_capacity_used,
+                self.psm_deposit_last_updated,
+            )
+        } else {
+            (
+                self.psm_redemption_capacity_used,
+                self.psm_redemption_last_updated,
+            )
+        };
+
+        let time_since_last_update = current_timestamp
+            .checked_sub(last_updated)
+            .ok_or(OndoError::MathOverflow)?;
+
+        let new_capacity_used = consume_capacity(
+            amount,
+            time_since_last_update,
+            self.psm_limit_window,
+            capacity_used,
+            self.psm_rate_limit,
+        )?;
+
+        if is_deposit {
+            self.psm_deposit_capacity_used = new_capacity_used;
+            self.psm_deposit_last_updated = current_timestamp;
+        } else {
+            self.psm_redemption_capacity_used = new_capacity_used;
+            self.psm_redemption_last_updated = current_timestamp;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::SWITCHBOARD_ON_DEMAND_PROGRAM_ID;
+
+    fn create_test_state(
+        usdc_feed_id: [u8; 32],
+        usdc_price_update_owner: Pubkey,
+    ) -> USDonManagerState {
+        USDonManagerState {
+            owner: Pubkey::default(),
+            usdon_mint: Pubkey::default(),
+            oracle_price_enabled: true,
+            oracle_price_max_age: 60,
+            usdc_price_update: Pubkey::default(),
+            usdc_vault: Pubkey::default(),
+            usdon_vault: Pubkey::default(),
+            bump: 0,
+            usdc_feed_id,
+            usdc_price_update_owner,
+            usdc_oracle_adapter: OracleAdapter::Pyth,
+            usdc_secondary_price_update: Pubkey::default(),
+            usdc_secondary_oracle_adapter: OracleAdapter::Pyth,
+            usdc_secondary_feed_id: [0; 32],
+            usdc_secondary_price_update_owner: Pubkey::default(),
+            usdc_oracle_max_divergence_bps: 0,
+            usdc_min_price: 0,
+            usdc_max_price: 0,
+            usdc_confidence_threshold_bps: 0,
+            usdc_oracle_conversion_enabled: false,
+            usdc_conversion_band_bps: 0,
+            usdc_vault_shortfall_mode: VaultShortfallMode::Revert,
+            psm_fee_bps: 0,
+            psm_deposit_paused: false,
+            psm_redemption_paused: false,
+            psm_rate_limit: 0,
+            psm_limit_window: 0,
+            psm_deposit_capacity_used: 0,
+            psm_deposit_last_updated: 0,
+            psm_redemption_capacity_used: 0,
+            psm_redemption_last_updated: 0,
+            usdon_minting_paused: false,
+            usdon_supply_cap: 0,
+            queued_usdc: 0,
+        }
+    }
+
+    #[test]
+    fn test_unset_oracle_config_falls_back_to_defaults() {
+        let state = create_test_state([0; 32], Pubkey::default());
+
+        assert_eq!(
+            state.effective_usdc_feed_id().unwrap(),
+            get_feed_id_from_hex(USDC_PYTH_ID).unwrap()
+        );
+        assert_eq!(
+            state.effective_usdc_price_update_owner(),
+            pyth_solana_receiver_sdk::ID
+        );
+    }
+
+    #[test]
+    fn test_depeg_policy_defaults_and_overrides() {
+        let mut state = create_test_state([0; 32], Pubkey::default());
+
+        assert_eq!(state.effective_usdc_min_price(), MIN_PRICE);
+        assert_eq!(state.effective_usdc_max_price(), None);
+        assert_eq!(
+            state.effective_usdc_confidence_threshold_bps(),
+            CONFIDENCE_THRESHOLD_BPS
+        );
+
+        state.usdc_min_price = 99_000_000;
+        state.usdc_max_price = 101_000_000;
+        state.usdc_confidence_threshold_bps = 50;
+
+        assert_eq!(state.effective_usdc_min_price(), 99_000_000);
+        assert_eq!(state.effective_usdc_max_price(), Some(101_000_000));
+        assert_eq!(state.effective_usdc_confidence_threshold_bps(), 50);
+    }
+
+    #[test]
+    fn test_unset_owner_follows_oracle_adapter() {
+        let mut state = create_test_state([0; 32], Pubkey::default());
+
+        state.usdc_oracle_adapter = OracleAdapter::Switchboard;
+        assert_eq!(
+            state.effective_usdc_price_update_owner(),
+            SWITCHBOARD_ON_DEMAND_PROGRAM_ID
+        );
+
+        #[cfg(not(feature = "mainnet"))]
+        {
+            state.usdc_oracle_adapter = OracleAdapter::Pushed;
+            assert_eq!(state.effective_usdc_price_update_owner(), crate::ID);
+        }
+    }
+
+    #[test]
+    fn test_secondary_owner_defaults_to_its_adapter() {
+        let mut state = create_test_state([0; 32], Pubkey::new_unique());
+
+        state.usdc_secondary_oracle_adapter = OracleAdapter::Switchboard;
+        assert_eq!(
+            state.effective_usdc_secondary_price_update_owner(),
+            SWITCHBOARD_ON_DEMAND_PROGRAM_ID
+        );
+
+        let owner = Pubkey::new_unique();
+        state.usdc_secondary_price_update_owner = owner;
+        assert_eq!(state.effective_usdc_secondary_price_update_owner(), owner);
+    }
+
+    #[test]
+    fn test_configured_oracle_config_is_used() {
+        let feed_id = [7; 32];
+        let owner = Pubkey::new_unique();
+        let state = create_test_state(feed_id, owner);
+
+        assert_eq!(state.effective_usdc_feed_id().unwrap(), feed_id);
+        assert_eq!(state.effective_usdc_price_update_owner(), owner);
+    }
+
+    #[test]
+    fn test_psm_capacity() {
+        let mut state = create_test_state([0; 32], Pubkey::default());
+
+        // Unconfigured limits keep the PSM closed
+        assert!(state.consume_psm_capacity(1, 0, true).is_err());
+
+        state.psm_rate_limit = 1_000;
+        state.psm_limit_window = 100;
+
+        state.consume_psm_capacity(800, 0, true).unwrap();
+        assert!(state.consume_psm_capacity(201, 0, true).is_err());
+
+        // Deposits and redemptions are limited separately
+        state.consume_psm_capacity(1_000, 0, false).unwrap();
+
+        // Half the window restores half the rate limit
+        state.consume_psm_capacity(700, 50, true).unwrap();
+        assert_eq!(state.psm_deposit_capacity_used, 1_000);
+        assert_eq!(state.psm_deposit_last_updated, 50);
+    }
+
+    #[test]
+    fn test_usdon_supply_cap() {
+        let mut state = create_test_state([0; 32], Pubkey::default());
+
+        // No ceiling by default
+        state.check_usdon_supply_cap(u64::MAX - 1, 1).unwrap();
+
+        state.usdon_supply_cap = 1_000;
+        state.check_usdon_supply_cap(900, 100).unwrap();
+        assert!(state.check_usdon_supply_cap(900, 101).is_err());
+    }
+
+    #[test]
+    fn test_queued_usdc_only_reserved_in_usdc_vault() {
+        let mut state = create_test_state([0; 32], Pubkey::default());
+        state.queued_usdc = 600;
+
+        assert_eq!(state.vault_liquidity(1_000, true), 400);
+        assert_eq!(state.vault_liquidity(500, true), 0);
+
+        // A registered settlement asset's vault owes nothing to queued USDC claims
+        assert_eq!(state.vault_liquidity(1_000, false), 1_000);
+    }
 }
@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::BASIS_POINTS_DIVISOR, errors::OndoError};
+
+/// USDonReserves state account - off-chain reserves backing USDon as attested by the custodian,
+/// and the outcome of the last reconciliation against the USDon supply
+#[account]
+#[derive(InitSpace)]
+pub struct USDonReserves {
+    // The custodian key allowed to attest off-chain reserves
+    pub custodian: Pubkey,
+
+    // The attested off-chain reserves, in USDon base units
+    pub offchain_reserves: u64,
+
+    // Unix timestamp of the last attestation
+    pub attested_at: i64,
+
+    // The length of time (in seconds) an attestation is counted towards the backing
+    pub max_attestation_age: u64,
+
+    // The collateralization (in basis points of the USDon supply) below which
+    // `reconcile_reserves` pauses USDon minting
+    pub min_collateralization_bps: u64,
+
+    // The collateralization (in basis points) computed by the last reconciliation
+    pub last_collateralization_bps: u64,
+
+    // Unix timestamp of the last reconciliation
+    pub last_reconciled_at: i64,
+
+    // The bump used to derive the PDA for this account
+    // Stored so we don't need to recalculate it later
+    pub bump: u8,
+}
+
+impl USDonReserves {
+    /// Returns the attested off-chain reserves, or None if no attestation was made
+    /// or the last one is stale
+    pub fn attested_offchain_reserves(&self, current_timestamp: i64) -> Option<u64> {
+        if self.attested_at == 0 {
+            return None;
+        }
+
+        let age = current_timestamp.saturating_sub(self.attested_at);
+
+        (age >= 0 && (age as u64) <= self.max_attestation_age).then_some(self.offchain_reserves)
+    }
+}
+
+/// Returns the backing of the USDon supply in basis points, u64::MAX if there is no supply
+/// # Arguments
+/// * `backing` - The total reserves, in USDon base units
+/// * `supply` - The USDon supply
+/// # Returns
+/// * `Result<u64>` - The collateralization in basis points, capped at u64::MAX
+pub fn collateralization_bps(backing: u64, supply: u64) -> Result<u64> {
+    if supply == 0 {
+        return Ok(u64::MAX);
+    }
+
+    // Round down: undercollateralization is detected as early as possible
+    let bps = (backing as u128)
+        .checked_mul(BASIS_POINTS_DIVISOR as u128)
+        .ok_or(OndoError::MathOverflow)?
+        / supply as u128;
+
+    Ok(u64::try_from(bps).unwrap_or(u64::MAX))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collateralization_bps() {
+        assert_eq!(collateralization_bps(0, 0).unwrap(), u64::MAX);
+        assert_eq!(collateralization_bps(100, 100).unwrap(), 10_000);
+        assert_eq!(collateralization_bps(99_999, 100_000).unwrap(), 9_999);
+        assert_eq!(collateralization_bps(u64::MAX, 1).unwrap(), u64::MAX);
+    }
+
+    #[test]
+    fn test_missing_or_stale_attestation_is_rejected() {
+        let reserves = USDonReserves {
+            custodian: Pubkey::default(),
+            offchain_reserves: 1_000,
+            attested_at: 100,
+            max_attestation_age: 50,
+            min_collateralization_bps: 10_000,
+            last_collateralization_bps: 0,
+            last_reconciled_at: 0,
+            bump: 0,
+        };
+
+        assert_eq!(reserves.attested_offchain_reserves(150), Some(1_000));
+        assert_eq!(reserves.attested_offchain_reserves(151), None);
+        // Attestations from the future are not trusted
+        assert_eq!(reserves.attested_offchain_reserves(99), None);
+
+        let never_attested = USDonReserves {
+            attested_at: 0,
+            ..reserves
+        };
+        assert_eq!(never_attested.attested_offchain_reserves(0), None);
+    }
+}
@@ -1,4 +1,7 @@
 use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+
+use crate::errors::OndoError;
 
 /// Whitelist account - tracks whitelisted addresses.
 ///
@@ -9,3 +12,19 @@ pub struct Whitelist {
     // The whitelisted user
     pub user: Pubkey,
 }
+
+impl Whitelist {
+    /// Verifies that the given account is an initialized whitelist account.
+    /// The PDA derivation is checked by the caller's account constraints.
+    /// # Arguments
+    /// * `whitelist` - The whitelist account info to check.
+    /// # Returns
+    /// * `Result<()>` - Ok if the account is a whitelist account, Err(UserNotWhitelisted) otherwise.
+    pub fn verify(whitelist: &AccountInfo) -> Result<()> {
+        let whitelist_data = whitelist.try_borrow_data()?;
+        if whitelist_data.len() < 8 || whitelist_data[..8] != *Whitelist::DISCRIMINATOR {
+            return Err(OndoError::UserNotWhitelisted.into());
+        }
+        Ok(())
+    }
+}
@@ -44,6 +44,169 @@ pub fn calculate_capacity_used(
     }
 }
 
+/// Consume `amount` of a rate limit whose used capacity decays linearly over the limit window.
+/// # Arguments
+/// * `amount` - The amount to consume.
+/// * `time_since_last_update` - The time elapsed since the capacity was last consumed in seconds.
+/// * `limit_window` - The time window for the rate limit in seconds.
+/// * `capacity_used` - The capacity used as of the last update.
+/// * `rate_limit` - The maximum rate limit allowed in the limit window.
+/// # Returns
+/// * `Result<u64>` - The capacity used after consuming `amount`, Err(InvalidRateLimit) if it exceeds the rate limit.
+pub fn consume_capacity(
+    amount: u64,
+    time_since_last_update: i64,
+    limit_window: u64,
+    capacity_used: u64,
+    rate_limit: u64,
+) -> Result<u64> {
+    let current_capacity_used = calculate_capacity_used(
+        time_since_last_update,
+        limit_window,
+        capacity_used,
+        rate_limit,
+    )?;
+
+    let new_capacity_used = current_capacity_used
+        .checked_add(amount)
+        .ok_or(OndoError::MathOverflow)?;
+
+    if new_capacity_used > rate_limit {
+        msg!(
+            "Rate limit exceeded: requested {} > available {}. rate_limit={}, capacity_used={}, window={}",
+            amount,
+            rate_limit.saturating_sub(current_capacity_used),
+            rate_limit,
+            current_capacity_used,
+            limit_window
+        );
+        return Err(OndoError::InvalidRateLimit.into());
+    }
+
+    Ok(new_capacity_used)
+}
+
+/// Returns the capacity left in a rate limit whose used capacity decays linearly over the limit window.
+/// # Arguments
+/// * `time_since_last_update` - The time elapsed since the capacity was last consumed in seconds.
+/// * `limit_window` - The time window for the rate limit in seconds.
+/// * `capacity_used` - The capacity used as of the last update.
+/// * `rate_limit` - The maximum rate limit allowed in the limit window.
+/// # Returns
+/// * `Result<u64>` - The largest amount `consume_capacity` would accept.
+pub fn available_capacity(
+    time_since_last_update: i64,
+    limit_window: u64,
+    capacity_used: u64,
+    rate_limit: u64,
+) -> Result<u64> {
+    let current_capacity_used = calculate_capacity_used(
+        time_since_last_update,
+        limit_window,
+        capacity_used,
+        rate_limit,
+    )?;
+
+    Ok(rate_limit.saturating_sub(current_capacity_used))
+}
+
+/// Returns the largest trade a net-flow position allows in one direction, see `apply_net_flow`.
+/// A position beyond the limit can be reduced down to zero and then up to the limit on the other side,
+/// so the largest trade is always the rate limit plus the position in the direction of the trade.
+/// # Arguments
+/// * `is_buy` - Whether the trade is a mint (true) or a redemption (false).
+/// * `time_since_last_update` - The time elapsed since the position was last updated in seconds.
+/// * `limit_window` - The time window for the rate limit in seconds.
+/// * `net_position` - The net position as of the last update.
+/// * `rate_limit` - The maximum absolute net position allowed in the limit window.
+/// # Returns
+/// * `Result<u64>` - The largest amount `apply_net_flow` would accept.
+pub fn available_net_flow(
+    is_buy: bool,
+    time_since_last_update: i64,
+    limit_window: u64,
+    net_position: i64,
+    rate_limit: u64,
+) -> Result<u64> {
+    let current_magnitude = i128::from(calculate_capacity_used(
+        time_since_last_update,
+        limit_window,
+        net_position.unsigned_abs(),
+        rate_limit,
+    )?);
+    let current_position = if net_position < 0 {
+        -current_magnitude
+    } else {
+        current_magnitude
+    };
+
+    let available = if is_buy {
+        i128::from(rate_limit) - current_position
+    } else {
+        i128::from(rate_limit) + current_position
+    };
+
+    Ok(u64::try_from(available.max(0)).unwrap_or(u64::MAX))
+}
+
+/// Apply a trade to a signed net-flow position whose magnitude decays linearly toward zero
+/// over the limit window. Mints add to the position and redemptions subtract from it.
+/// Trades ending beyond the rate limit are rejected, unless they reduce the position without crossing zero,
+/// so a position above a lowered limit can still be reduced.
+/// # Arguments
+/// * `amount` - The amount traded.
+/// * `is_buy` - Whether the trade is a mint (true) or a redemption (false).
+/// * `time_since_last_update` - The time elapsed since the position was last updated in seconds.
+/// * `limit_window` - The time window for the rate limit in seconds.
+/// * `net_position` - The net position as of the last update.
+/// * `rate_limit` - The maximum absolute net position allowed in the limit window.
+/// # Returns
+/// * `Result<i64>` - The net position after the trade, Err(InvalidRateLimit) if its magnitude exceeds the rate limit.
+pub fn apply_net_flow(
+    amount: u64,
+    is_buy: bool,
+    time_since_last_update: i64,
+    limit_window: u64,
+    net_position: i64,
+    rate_limit: u64,
+) -> Result<i64> {
+    let current_magnitude = calculate_capacity_used(
+        time_since_last_update,
+        limit_window,
+        net_position.unsigned_abs(),
+        rate_limit,
+    )?;
+    let current_position = if net_position < 0 {
+        -i128::from(current_magnitude)
+    } else {
+        i128::from(current_magnitude)
+    };
+
+    let new_position = if is_buy {
+        current_position + i128::from(amount)
+    } else {
+        current_position - i128::from(amount)
+    };
+    let new_magnitude = new_position.unsigned_abs();
+
+    let reduces_position = new_position.signum() == current_position.signum()
+        && new_magnitude <= u128::from(current_magnitude);
+
+    if new_magnitude > u128::from(rate_limit) && !reduces_position {
+        msg!(
+            "Net flow limit exceeded: requested {} {}, rate_limit={}, net_position={}, window={}",
+            if is_buy { "mint" } else { "redeem" },
+            amount,
+            rate_limit,
+            current_position,
+            limit_window
+        );
+        return Err(OndoError::InvalidRateLimit.into());
+    }
+
+    i64::try_from(new_position).map_err(|_| OndoError::MathOverflow.into())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -231,4 +394,64 @@ mod tests {
         // Result = 1000 - 1 = 999
         assert_eq!(result.unwrap(), 999);
     }
+
+    #[test]
+    fn test_consume_capacity() {
+        // Half the window restores half the rate limit
+        assert_eq!(consume_capacity(50, 30, 60, 100, 100).unwrap(), 100);
+        assert!(consume_capacity(51, 30, 60, 100, 100).is_err());
+        assert_eq!(consume_capacity(100, 60, 60, 100, 100).unwrap(), 100);
+    }
+
+    #[test]
+    fn test_net_flow_offsetting_trades() {
+        // Offsetting trades net to zero and never hit the limit
+        let position = apply_net_flow(100, true, 0, 60, 0, 100).unwrap();
+        assert_eq!(position, 100);
+        assert!(apply_net_flow(1, true, 0, 60, position, 100).is_err());
+        let position = apply_net_flow(200, false, 0, 60, position, 100).unwrap();
+        assert_eq!(position, -100);
+        assert!(apply_net_flow(1, false, 0, 60, position, 100).is_err());
+        assert_eq!(apply_net_flow(100, true, 0, 60, position, 100).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_net_flow_decays_toward_zero() {
+        // Half the window decays half the rate limit from either side
+        assert_eq!(apply_net_flow(0, true, 30, 60, 100, 100).unwrap(), 50);
+        assert_eq!(apply_net_flow(0, false, 30, 60, -100, 100).unwrap(), -50);
+        assert_eq!(apply_net_flow(50, false, 30, 60, -100, 100).unwrap(), -100);
+
+        // A position above a lowered limit can still be reduced
+        assert_eq!(apply_net_flow(10, false, 0, 60, 500, 100).unwrap(), 490);
+        assert!(apply_net_flow(10, true, 0, 60, 500, 100).is_err());
+
+        // Crossing zero ends beyond the limit on the other side
+        assert_eq!(apply_net_flow(600, false, 0, 60, 500, 100).unwrap(), -100);
+        assert!(apply_net_flow(999, false, 0, 60, 500, 100).is_err());
+    }
+
+    #[test]
+    fn test_available_capacity_matches_consume() {
+        let available = available_capacity(30, 60, 100, 100).unwrap();
+        assert_eq!(available, 50);
+        assert!(consume_capacity(available, 30, 60, 100, 100).is_ok());
+        assert!(consume_capacity(available + 1, 30, 60, 100, 100).is_err());
+    }
+
+    #[test]
+    fn test_available_net_flow_matches_apply() {
+        for (is_buy, position) in [
+            (true, 60),
+            (false, 60),
+            (true, -60),
+            (false, -500),
+            (false, 500),
+            (true, -500),
+        ] {
+            let available = available_net_flow(is_buy, 0, 60, position, 100).unwrap();
+            assert!(apply_net_flow(available, is_buy, 0, 60, position, 100).is_ok());
+            assert!(apply_net_flow(available + 1, is_buy, 0, 60, position, 100).is_err());
+        }
+    }
 }
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    close_account, transfer_checked, CloseAccount, TokenAccount, TransferChecked,
+};
+
+/// Returns the remaining balance of a PDA-owned escrow token account and closes it
+/// # Arguments
+/// * `token_program` - The token program that owns the escrow
+/// * `escrow` - The escrow token account
+/// * `mint` - The mint of the escrowed tokens
+/// * `decimals` - The decimals of the escrowed tokens
+/// * `destination` - The token account receiving the remaining balance
+/// * `rent_receiver` - The account receiving the escrow's rent
+/// * `authority` - The PDA that owns the escrow
+/// * `signer_seeds` - The signer seeds of the escrow authority
+/// # Returns
+/// * `Result<u64>` - The amount returned to the destination
+#[allow(clippy::too_many_arguments)]
+pub fn release_escrow<'info>(
+    token_program: &AccountInfo<'info>,
+    escrow: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    decimals: u8,
+    destination: &AccountInfo<'info>,
+    rent_receiver: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<u64> {
+    // Read the balance after any transfers made earlier in the instruction
+    let remaining = TokenAccount::try_deserialize(&mut &escrow.try_borrow_data()?[..])?.amount;
+
+    if remaining > 0 {
+        transfer_checked(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                TransferChecked {
+                    from: escrow.clone(),
+                    mint: mint.clone(),
+                    to: destination.clone(),
+                    authority: authority.clone(),
+                },
+                signer_seeds,
+            ),
+            remaining,
+            decimals,
+        )?;
+    }
+
+    close_account(CpiContext::new_with_signer(
+        token_program.clone(),
+        CloseAccount {
+            account: escrow.clone(),
+            destination: rent_receiver.clone(),
+            authority: authority.clone(),
+        },
+        signer_seeds,
+    ))?;
+
+    Ok(remaining)
+}
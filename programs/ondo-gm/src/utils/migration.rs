@@ -0,0 +1,56 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+
+use crate::errors::OndoError;
+
+/// Grows a program account created with an older layout to `new_len` bytes,
+/// topping up rent from `payer` if needed. The new bytes are zero-initialized.
+/// Does nothing if the account already has at least `new_len` bytes.
+/// # Arguments
+/// * `account` - The account to grow, must be owned by the program
+/// * `discriminator` - The discriminator the account data must start with
+/// * `new_len` - The size of the current layout, including the discriminator
+/// * `payer` - The account paying for the extra rent
+/// * `system_program` - The system program
+/// # Returns
+/// * `Result<()>` - Ok if the account is migrated, Err(DataMismatch) if it has another type
+pub fn migrate_account_len<'info>(
+    account: &AccountInfo<'info>,
+    discriminator: &[u8],
+    new_len: usize,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    // Make sure this is the expected account type and not another program account
+    require!(
+        account.try_borrow_data()?.starts_with(discriminator),
+        OndoError::DataMismatch
+    );
+
+    if account.data_len() >= new_len {
+        return Ok(());
+    }
+
+    let rent_exempt_lamports = Rent::get()?.minimum_balance(new_len);
+    let lamports_needed = rent_exempt_lamports.saturating_sub(account.lamports());
+
+    if lamports_needed > 0 {
+        transfer(
+            CpiContext::new(
+                system_program.clone(),
+                Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            lamports_needed,
+        )?;
+    }
+
+    // New bytes are zero-initialized
+    account.resize(new_len)?;
+
+    Ok(())
+}
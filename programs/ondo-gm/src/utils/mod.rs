@@ -1,7 +1,15 @@
 pub mod capacity;
 pub mod decimals;
+pub mod escrow;
+pub mod migration;
 pub mod mul_div;
+pub mod oracle;
+pub mod usdon;
 
 pub use capacity::*;
 pub use decimals::*;
+pub use escrow::*;
+pub use migration::*;
 pub use mul_div::*;
+pub use oracle::*;
+pub use usdon::*;
@@ -0,0 +1,559 @@
+use anchor_lang::prelude::*;
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+
+#[cfg(not(feature = "mainnet"))]
+use crate::state::PushedPrice;
+
+use crate::{
+    constants::{BASIS_POINTS_DIVISOR, USDC_PEG_PRICE, USDC_PRICE_DECIMALS},
+    errors::OndoError,
+    events::USDCSecondaryOracleUnusable,
+    state::{OracleAdapter, USDonManagerState},
+    utils::{mul_div, normalize_decimals},
+};
+
+/// Anchor discriminator of a Switchboard On-Demand `PullFeedAccountData` account
+const SWITCHBOARD_PULL_FEED_DISCRIMINATOR: [u8; 8] = [196, 27, 108, 196, 10, 215, 219, 40];
+
+/// Number of decimals of Switchboard On-Demand results
+const SWITCHBOARD_DECIMALS: u32 = 18;
+
+/// Offsets into a Switchboard On-Demand `PullFeedAccountData` account, discriminator included
+/// The feed is a `#[repr(C)]` struct: 32 oracle submissions of 64 bytes, followed by the
+/// feed configuration, the last update timestamp and the current result.
+const SWITCHBOARD_LAST_UPDATE_OFFSET: usize = 2216;
+const SWITCHBOARD_RESULT_VALUE_OFFSET: usize = 2264;
+const SWITCHBOARD_RESULT_STD_DEV_OFFSET: usize = 2280;
+
+/// A price read from an oracle
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OraclePrice {
+    // The price, scaled by USDC_PRICE_DECIMALS
+    pub price: u64,
+
+    // The confidence interval of the price, scaled by USDC_PRICE_DECIMALS
+    pub conf: u64,
+}
+
+impl OraclePrice {
+    /// Rejects prices whose confidence interval is wider than `threshold_bps` of the price
+    pub fn check_confidence(&self, threshold_bps: u64) -> Result<()> {
+        // Check: conf * BPS <= price * threshold (equivalent to conf/price <= threshold bps)
+        let conf_times_bps = (self.conf as u128)
+            .checked_mul(BASIS_POINTS_DIVISOR as u128)
+            .ok_or(OndoError::MathOverflow)?;
+        let price_times_threshold = (self.price as u128)
+            .checked_mul(threshold_bps as u128)
+            .ok_or(OndoError::MathOverflow)?;
+
+        require!(
+            conf_times_bps <= price_times_threshold,
+            OndoError::ConfidenceThresholdExceeded
+        );
+
+        Ok(())
+    }
+}
+
+/// Reads a price from an oracle account, rejecting stale prices.
+/// # Arguments
+/// * `adapter` - The kind of oracle account
+/// * `price_update_info` - The oracle account
+/// * `owner` - The program expected to own the oracle account
+/// * `feed_id` - The expected price feed ID, only used by Pyth
+/// * `max_age` - The maximum age of the price in seconds
+/// # Returns
+/// * `Result<OraclePrice>` - The price and its confidence interval
+pub fn read_oracle_price(
+    adapter: OracleAdapter,
+    price_update_info: &AccountInfo,
+    owner: &Pubkey,
+    feed_id: &[u8; 32],
+    max_age: u64,
+) -> Result<OraclePrice> {
+    require_keys_eq!(
+        *price_update_info.owner,
+        *owner,
+        OndoError::InvalidOracleOwner
+    );
+
+    let data = price_update_info.try_borrow_data()?;
+    let now = Clock::get()?.unix_timestamp;
+
+    match adapter {
+        OracleAdapter::Pyth => read_pyth_price(&data, feed_id, max_age),
+        OracleAdapter::Switchboard => read_switchboard_price(&data, now, max_age),
+        #[cfg(not(feature = "mainnet"))]
+        OracleAdapter::Pushed => read_pushed_price(&data, now, max_age),
+    }
+}
+
+/// Picks the price to use from a primary and an optional secondary oracle.
+/// The secondary price is only used when the primary price is unusable (stale, too
+/// uncertain or invalid). When both are usable they must agree within `max_divergence_bps`.
+/// An unusable secondary price skips the cross-check and is logged.
+/// # Arguments
+/// * `primary` - The primary price, or the error raised while reading it
+/// * `secondary` - The secondary price or its read error, None if no secondary oracle is configured
+/// * `max_divergence_bps` - The maximum divergence between both prices, 0 disables the cross-check
+/// # Returns
+/// * `Result<OraclePrice>` - The selected price, or the primary error if no price is usable
+pub fn select_oracle_price(
+    primary: Result<OraclePrice>,
+    secondary: Option<Result<OraclePrice>>,
+    max_divergence_bps: u64,
+) -> Result<OraclePrice> {
+    match (primary, secondary) {
+        (Ok(primary), Some(Ok(secondary))) => {
+            check_divergence(&primary, &secondary, max_divergence_bps)?;
+            Ok(primary)
+        }
+        (Ok(primary), Some(Err(err))) => {
+            msg!(
+                "Secondary oracle price unusable, divergence check skipped: {:?}",
+                err
+            );
+            Ok(primary)
+        }
+        (Ok(primary), None) => Ok(primary),
+        (Err(_), Some(Ok(secondary))) => Ok(secondary),
+        (Err(err), _) => Err(err),
+    }
+}
+
+/// Rejects two prices differing by more than `max_divergence_bps` of the first one
+fn check_divergence(
+    primary: &OraclePrice,
+    secondary: &OraclePrice,
+    max_divergence_bps: u64,
+) -> Result<()> {
+    if max_divergence_bps == 0 {
+        return Ok(());
+    }
+
+    require!(
+        is_within_bps(primary.price, secondary.price, max_divergence_bps)?,
+        OndoError::OracleDivergence
+    );
+
+    Ok(())
+}
+
+/// Rejects conversion rates further than `band_bps` from the USDC peg
+pub fn check_conversion_band(rate: u64, band_bps: u64) -> Result<()> {
+    require!(
+        is_within_bps(USDC_PEG_PRICE, rate, band_bps)?,
+        OndoError::ConversionRateOutOfBand
+    );
+
+    Ok(())
+}
+
+/// Converts a USD value into an amount of an asset priced at `rate`, in the same decimals
+/// # Arguments
+/// * `usd_amount` - The USD value to convert
+/// * `rate` - The USD price of the asset, scaled by USDC_PRICE_DECIMALS
+/// * `round_up` - Whether to round the result up
+/// # Returns
+/// * `Result<u64>` - The amount of the asset worth `usd_amount`
+pub fn usd_to_asset_amount(usd_amount: u64, rate: u64, round_up: bool) -> Result<u64> {
+    require_gt!(rate, 0, OndoError::InvalidPrice);
+
+    mul_div(usd_amount, USDC_PEG_PRICE, rate, round_up)
+}
+
+/// Converts an amount of an asset priced at `rate` into its USD value, in the same decimals
+/// # Arguments
+/// * `asset_amount` - The amount of the asset to convert
+/// * `rate` - The USD price of the asset, scaled by USDC_PRICE_DECIMALS
+/// * `round_up` - Whether to round the result up
+/// # Returns
+/// * `Result<u64>` - The USD value of `asset_amount`
+pub fn asset_to_usd_amount(asset_amount: u64, rate: u64, round_up: bool) -> Result<u64> {
+    require_gt!(rate, 0, OndoError::InvalidPrice);
+
+    mul_div(asset_amount, rate, USDC_PEG_PRICE, round_up)
+}
+
+/// Returns the rate USDC is converted to USDon at, checking the USDC price against the
+/// USDonManagerState oracle configuration when oracle pricing is enabled.
+/// # Arguments
+/// * `state` - The USDonManagerState holding the USDC oracle configuration
+/// * `price_update` - The primary USDC oracle account
+/// * `secondary_price_update` - The secondary USDC oracle account, only required when one is configured
+/// # Returns
+/// * `Result<u64>` - The conversion rate, scaled by USDC_PRICE_DECIMALS
+pub fn usdc_conversion_rate(
+    state: &USDonManagerState,
+    price_update: Option<&AccountInfo>,
+    secondary_price_update: Option<&AccountInfo>,
+) -> Result<u64> {
+    if !state.oracle_price_enabled {
+        return Ok(USDC_PEG_PRICE);
+    }
+
+    let usdc_price = check_usdc_price(state, price_update, secondary_price_update)?;
+
+    if !state.usdc_oracle_conversion_enabled {
+        return Ok(USDC_PEG_PRICE);
+    }
+
+    // Only convert at the oracle price within the configured band around the peg
+    check_conversion_band(usdc_price, state.usdc_conversion_band_bps)?;
+
+    Ok(usdc_price)
+}
+
+/// Checks the USDC price against the USDonManagerState oracle configuration
+/// and returns it, scaled by USDC_PRICE_DECIMALS.
+fn check_usdc_price(
+    state: &USDonManagerState,
+    price_update: Option<&AccountInfo>,
+    secondary_price_update: Option<&AccountInfo>,
+) -> Result<u64> {
+    let usdc_price_update_info = price_update.ok_or(OndoError::USDCOracleNotProvided)?;
+
+    // Only the configured price update account is accepted
+    require_keys_eq!(
+        usdc_price_update_info.key(),
+        state.usdc_price_update,
+        OndoError::InvalidOraclePriceAddress
+    );
+
+    let confidence_threshold_bps = state.effective_usdc_confidence_threshold_bps();
+
+    // Reject prices with high uncertainty to prevent using unreliable oracle data
+    let primary_price = read_oracle_price(
+        state.usdc_oracle_adapter,
+        usdc_price_update_info,
+        &state.effective_usdc_price_update_owner(),
+        &state.effective_usdc_feed_id()?,
+        state.oracle_price_max_age,
+    )
+    .and_then(|price| {
+        price
+            .check_confidence(confidence_threshold_bps)
+            .map(|_| price)
+    });
+
+    let secondary_price = if state.has_secondary_usdc_oracle() {
+        let secondary_info = secondary_price_update.ok_or(OndoError::USDCOracleNotProvided)?;
+
+        require_keys_eq!(
+            secondary_info.key(),
+            state.usdc_secondary_price_update,
+            OndoError::InvalidOraclePriceAddress
+        );
+
+        Some(
+            read_oracle_price(
+                state.usdc_secondary_oracle_adapter,
+                secondary_info,
+                &state.effective_usdc_secondary_price_update_owner(),
+                &state.effective_usdc_secondary_feed_id()?,
+                state.oracle_price_max_age,
+            )
+            .and_then(|price| {
+                price
+                    .check_confidence(confidence_threshold_bps)
+                    .map(|_| price)
+            }),
+        )
+    } else {
+        None
+    };
+
+    // Signal that the cross-check is skipped while the secondary oracle is broken or stale
+    if let (Ok(primary), Some(Err(_))) = (&primary_price, &secondary_price) {
+        emit!(USDCSecondaryOracleUnusable {
+            price_update: state.usdc_secondary_price_update,
+            primary_price: primary.price,
+        });
+    }
+
+    let usdc_price = select_oracle_price(
+        primary_price,
+        secondary_price,
+        state.usdc_oracle_max_divergence_bps,
+    )?;
+
+    // Validate that USDC price is within the configured depeg bounds
+    require_gte!(
+        usdc_price.price,
+        state.effective_usdc_min_price(),
+        OndoError::USDCBelowMinimumPrice
+    );
+
+    if let Some(max_price) = state.effective_usdc_max_price() {
+        require_gte!(
+            max_price,
+            usdc_price.price,
+            OndoError::USDCAboveMaximumPrice
+        );
+    }
+
+    Ok(usdc_price.price)
+}
+
+/// Returns whether `value` differs from `reference` by at most `bps` of `reference`
+fn is_within_bps(reference: u64, value: u64, bps: u64) -> Result<bool> {
+    // Check: |reference - value| * BPS <= reference * bps
+    let difference = (reference.abs_diff(value) as u128)
+        .checked_mul(BASIS_POINTS_DIVISOR as u128)
+        .ok_or(OndoError::MathOverflow)?;
+    let allowed = (reference as u128)
+        .checked_mul(bps as u128)
+        .ok_or(OndoError::MathOverflow)?;
+
+    Ok(difference <= allowed)
+}
+
+fn read_pyth_price(data: &[u8], feed_id: &[u8; 32], max_age: u64) -> Result<OraclePrice> {
+    // Deserialize the account data into PriceUpdateV2 struct
+    let price_update = PriceUpdateV2::try_deserialize(&mut &data[..])?;
+
+    // Retrieve the current price from the Pyth oracle with freshness validation
+    // This ensures we're using recent price data to prevent stale price attacks
+    let price_update_data =
+        price_update.get_price_no_older_than(&Clock::get()?, max_age, feed_id)?;
+
+    require!(price_update_data.price > 0, OndoError::InvalidPrice);
+
+    // Check exponent is negative (Pyth convention)
+    require!(
+        price_update_data.exponent < 0,
+        OndoError::InvalidPriceExponent
+    );
+
+    let from_decimals =
+        u8::try_from(-price_update_data.exponent).map_err(|_| OndoError::InvalidPriceExponent)?;
+
+    Ok(OraclePrice {
+        // Safe to cast as we required price > 0 above
+        price: normalize_decimals(
+            price_update_data.price as u64,
+            from_decimals,
+            USDC_PRICE_DECIMALS,
+            false,
+        )?,
+        // Round up: a wider interval is the conservative reading
+        conf: normalize_decimals(
+            price_update_data.conf,
+            from_decimals,
+            USDC_PRICE_DECIMALS,
+            true,
+        )?,
+    })
+}
+
+fn read_switchboard_price(data: &[u8], now: i64, max_age: u64) -> Result<OraclePrice> {
+    require!(
+        data.starts_with(&SWITCHBOARD_PULL_FEED_DISCRIMINATOR),
+        OndoError::DataMismatch
+    );
+
+    let read_i128 = |offset: usize| -> Result<i128> {
+        let bytes = data
+            .get(offset..offset + 16)
+            .ok_or(OndoError::DataMismatch)?;
+        Ok(i128::from_le_bytes(bytes.try_into().unwrap()))
+    };
+
+    let last_update = i64::from_le_bytes(
+        data.get(SWITCHBOARD_LAST_UPDATE_OFFSET..SWITCHBOARD_LAST_UPDATE_OFFSET + 8)
+            .ok_or(OndoError::DataMismatch)?
+            .try_into()
+            .unwrap(),
+    );
+    check_staleness(last_update, now, max_age)?;
+
+    let value = read_i128(SWITCHBOARD_RESULT_VALUE_OFFSET)?;
+    let std_dev = read_i128(SWITCHBOARD_RESULT_STD_DEV_OFFSET)?;
+
+    require!(value > 0, OndoError::InvalidPrice);
+    require!(std_dev >= 0, OndoError::InvalidPrice);
+
+    let divisor = 10i128.pow(SWITCHBOARD_DECIMALS - USDC_PRICE_DECIMALS as u32);
+
+    Ok(OraclePrice {
+        price: u64::try_from(value / divisor).map_err(|_| OndoError::MathOverflow)?,
+        // Round up: a wider interval is the conservative reading
+        conf: u64::try_from((std_dev + divisor - 1) / divisor)
+            .map_err(|_| OndoError::MathOverflow)?,
+    })
+}
+
+#[cfg(not(feature = "mainnet"))]
+fn read_pushed_price(data: &[u8], now: i64, max_age: u64) -> Result<OraclePrice> {
+    let pushed_price = PushedPrice::try_deserialize(&mut &data[..])?;
+
+    check_staleness(pushed_price.updated_at, now, max_age)?;
+
+    require!(pushed_price.price > 0, OndoError::InvalidPrice);
+
+    Ok(OraclePrice {
+        price: pushed_price.price,
+        conf: pushed_price.conf,
+    })
+}
+
+fn check_staleness(updated_at: i64, now: i64, max_age: u64) -> Result<()> {
+    let age = now.saturating_sub(updated_at);
+
+    require_gte!(max_age as i64, age, OndoError::MaxTimeDelayExceeded);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_switchboard_data(last_update: i64, value: i128, std_dev: i128) -> Vec<u8> {
+        let mut data = vec![0u8; 3208];
+        data[..8].copy_from_slice(&SWITCHBOARD_PULL_FEED_DISCRIMINATOR);
+        data[SWITCHBOARD_LAST_UPDATE_OFFSET..SWITCHBOARD_LAST_UPDATE_OFFSET + 8]
+            .copy_from_slice(&last_update.to_le_bytes());
+        data[SWITCHBOARD_RESULT_VALUE_OFFSET..SWITCHBOARD_RESULT_VALUE_OFFSET + 16]
+            .copy_from_slice(&value.to_le_bytes());
+        data[SWITCHBOARD_RESULT_STD_DEV_OFFSET..SWITCHBOARD_RESULT_STD_DEV_OFFSET + 16]
+            .copy_from_slice(&std_dev.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn test_read_switchboard_price() {
+        // 0.9998 with a 0.0001 standard deviation, 18 decimals
+        let data = create_switchboard_data(1_000, 999_800_000_000_000_000, 100_000_000_000_000);
+
+        let price = read_switchboard_price(&data, 1_030, 60).unwrap();
+        assert_eq!(
+            price,
+            OraclePrice {
+                price: 99_980_000,
+                conf: 10_000,
+            }
+        );
+
+        // Stale
+        assert!(read_switchboard_price(&data, 1_061, 60).is_err());
+
+        // Wrong discriminator
+        let mut bad = data.clone();
+        bad[0] ^= 1;
+        assert!(read_switchboard_price(&bad, 1_030, 60).is_err());
+
+        // Non-positive price
+        let data = create_switchboard_data(1_000, 0, 0);
+        assert!(read_switchboard_price(&data, 1_030, 60).is_err());
+    }
+
+    #[test]
+    fn test_select_oracle_price() {
+        let primary = OraclePrice {
+            price: 100_000_000,
+            conf: 0,
+        };
+        let secondary = OraclePrice {
+            price: 99_500_000,
+            conf: 0,
+        };
+
+        // No secondary oracle configured
+        assert_eq!(
+            select_oracle_price(Ok(primary), None, 100).unwrap(),
+            primary
+        );
+        assert!(select_oracle_price(err!(OndoError::MaxTimeDelayExceeded), None, 100).is_err());
+
+        // Both usable and within 0.5%: the primary wins
+        assert_eq!(
+            select_oracle_price(Ok(primary), Some(Ok(secondary)), 50).unwrap(),
+            primary
+        );
+
+        // Both usable but diverging by more than 0.49%
+        assert!(select_oracle_price(Ok(primary), Some(Ok(secondary)), 49).is_err());
+
+        // Cross-check disabled
+        assert_eq!(
+            select_oracle_price(Ok(primary), Some(Ok(secondary)), 0).unwrap(),
+            primary
+        );
+
+        // Primary unusable: fall back to the secondary
+        assert_eq!(
+            select_oracle_price(
+                err!(OndoError::MaxTimeDelayExceeded),
+                Some(Ok(secondary)),
+                1
+            )
+            .unwrap(),
+            secondary
+        );
+
+        // Secondary unusable: keep the primary
+        assert_eq!(
+            select_oracle_price(
+                Ok(primary),
+                Some(err!(OndoError::ConfidenceThresholdExceeded)),
+                1
+            )
+            .unwrap(),
+            primary
+        );
+
+        // Neither usable
+        assert!(select_oracle_price(
+            err!(OndoError::MaxTimeDelayExceeded),
+            Some(err!(OndoError::MaxTimeDelayExceeded)),
+            1
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_usd_to_asset_amount() {
+        // At par the amount is unchanged
+        assert_eq!(
+            usd_to_asset_amount(1_000_000, USDC_PEG_PRICE, true).unwrap(),
+            1_000_000
+        );
+
+        // At 0.99 more of the asset is needed, rounding in the protocol's favour
+        assert_eq!(
+            usd_to_asset_amount(1_000_000, 99_000_000, true).unwrap(),
+            1_010_102
+        );
+        assert_eq!(
+            usd_to_asset_amount(1_000_000, 99_000_000, false).unwrap(),
+            1_010_101
+        );
+
+        assert!(usd_to_asset_amount(1_000_000, 0, false).is_err());
+    }
+
+    #[test]
+    fn test_check_conversion_band() {
+        assert!(check_conversion_band(USDC_PEG_PRICE, 0).is_ok());
+        assert!(check_conversion_band(99_500_000, 50).is_ok());
+        assert!(check_conversion_band(100_500_000, 50).is_ok());
+        assert!(check_conversion_band(99_499_999, 50).is_err());
+        assert!(check_conversion_band(100_500_001, 50).is_err());
+    }
+
+    #[test]
+    fn test_check_confidence() {
+        let price = OraclePrice {
+            price: 100_000_000,
+            conf: 1_000_000,
+        };
+        assert!(price.check_confidence(100).is_ok());
+
+        let price = OraclePrice {
+            price: 100_000_000,
+            conf: 1_000_001,
+        };
+        assert!(price.check_confidence(100).is_err());
+    }
+}
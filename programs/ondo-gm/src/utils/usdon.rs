@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{mint_to, Mint, MintTo};
+
+use crate::{errors::OndoError, state::USDonManagerState};
+
+/// Mints USDon. Every USDon mint of the program goes through this function,
+/// so the USDon minting pause and supply cap apply to all of them, GM Token redemptions included
+/// # Arguments
+/// * `usdon_manager_state` - The USDon manager state
+/// * `token_program` - The token program of the USDon mint
+/// * `usdon_mint` - The USDon mint
+/// * `destination` - The token account receiving the USDon
+/// * `mint_authority` - The mint authority PDA
+/// * `signer_seeds` - The signer seeds of the mint authority
+/// * `amount` - The amount of USDon to mint
+/// # Returns
+/// * `Result<()>` - Ok if the mint succeeds, Err otherwise
+/// # Errors
+/// * `OndoError::USDonMintingPaused` - If USDon minting is paused
+/// * `OndoError::USDonSupplyCapExceeded` - If the mint would exceed the USDon supply cap
+pub fn mint_usdon_to<'info>(
+    usdon_manager_state: &USDonManagerState,
+    token_program: &AccountInfo<'info>,
+    usdon_mint: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+    mint_authority: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    amount: u64,
+) -> Result<()> {
+    require!(
+        !usdon_manager_state.usdon_minting_paused,
+        OndoError::USDonMintingPaused
+    );
+
+    // Read the supply after any mints made earlier in the instruction
+    let supply = Mint::try_deserialize(&mut &usdon_mint.try_borrow_data()?[..])?.supply;
+    usdon_manager_state.check_usdon_supply_cap(supply, amount)?;
+
+    mint_to(
+        CpiContext::new_with_signer(
+            token_program.clone(),
+            MintTo {
+                mint: usdon_mint.clone(),
+                to: destination.clone(),
+                authority: mint_authority.clone(),
+            },
+            signer_seeds,
+        ),
+        amount,
+    )
+}